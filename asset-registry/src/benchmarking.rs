@@ -0,0 +1,65 @@
+//! Benchmarks for the asset registry module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+
+/// The location of an asset on parachain `para_id`.
+fn asset_location(para_id: u32) -> MultiLocation {
+	(Parent, Parachain(para_id), GeneralKey(b"asset".to_vec())).into()
+}
+
+/// A name or symbol of the max length.
+fn max_string<T: Config>() -> Vec<u8> {
+	sp_std::vec![b'A'; T::StringLimit::get() as usize]
+}
+
+benchmarks! {
+	where_clause { where T::CurrencyId: Default }
+
+	register_asset {
+		let currency_id = T::CurrencyId::default();
+		let location = asset_location(1);
+	}: _(
+		T::RegisterOrigin::successful_origin(),
+		currency_id,
+		Box::new(location.clone()),
+		max_string::<T>(),
+		max_string::<T>(),
+		12,
+		1u32.into()
+	)
+	verify {
+		assert_eq!(Pallet::<T>::location_to_currency_id(location), Some(currency_id));
+	}
+
+	// worst case: the location changes
+	update_asset {
+		let currency_id = T::CurrencyId::default();
+		Pallet::<T>::register_asset(
+			T::RegisterOrigin::successful_origin(),
+			currency_id,
+			Box::new(asset_location(1)),
+			max_string::<T>(),
+			max_string::<T>(),
+			12,
+			1u32.into(),
+		)?;
+		let location = asset_location(2);
+	}: _(
+		T::RegisterOrigin::successful_origin(),
+		currency_id,
+		Box::new(location.clone()),
+		max_string::<T>(),
+		max_string::<T>(),
+		18,
+		2u32.into()
+	)
+	verify {
+		assert_eq!(Pallet::<T>::location_to_currency_id(location), Some(currency_id));
+		assert_eq!(Pallet::<T>::location_to_currency_id(asset_location(1)), None);
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::para_ext(1), crate::mock::para::Runtime,);
@@ -0,0 +1,355 @@
+//! # Asset Registry Module
+//!
+//! ## Overview
+//!
+//! The asset registry module maps currency IDs to XCM locations, metadata and
+//! existential deposits, so that new foreign assets can be supported by
+//! governance without a runtime upgrade.
+//!
+//! The asset registry module provides implementations for
+//! - `Convert<CurrencyId, Option<MultiLocation>>` by `Pallet`, to be used as
+//!   `orml_xtokens::Config::CurrencyIdConvert`.
+//! - `Convert<MultiLocation, Option<CurrencyId>>` and `Convert<MultiAsset,
+//!   Option<CurrencyId>>` by `LocationToCurrencyId`, to be used with
+//!   `orml_xcm_support::IsNativeConcrete` and
+//!   `orml_xcm_support::MultiCurrencyAdapter`.
+//! - `GetByKey<CurrencyId, Balance>` by `Pallet`, to be used as
+//!   `orml_tokens::Config::ExistentialDeposits`.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable functions
+//!
+//! - `register_asset`: Register an asset, privileged origin required.
+//! - `update_asset`: Update a registered asset, privileged origin required.
+//!
+//! ### Genesis Config
+//!
+//! Assets could be registered in genesis configs.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::{pallet_prelude::*, BoundedVec};
+use frame_system::pallet_prelude::*;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Bounded, Convert, MaybeSerializeDeserialize, Member},
+	DispatchResult, RuntimeDebug,
+};
+use sp_std::{convert::TryInto, marker::PhantomData, prelude::*};
+
+use xcm::latest::prelude::*;
+
+use orml_traits::GetByKey;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+/// The location, metadata and existential deposit of an asset.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AssetMetadata<Balance, BoundedString> {
+	/// The location of the asset.
+	#[cfg_attr(feature = "std", serde(with = "location_serde"))]
+	pub location: MultiLocation,
+	/// The user friendly name of the asset.
+	pub name: BoundedString,
+	/// The ticker symbol of the asset.
+	pub symbol: BoundedString,
+	/// The number of decimals the asset uses to represent one unit.
+	pub decimals: u8,
+	/// The minimum amount required to keep an account of the asset.
+	pub existential_deposit: Balance,
+}
+
+/// Serde of `MultiLocation`, which doesn't implement serde, as its SCALE
+/// encoded bytes.
+#[cfg(feature = "std")]
+mod location_serde {
+	use super::*;
+	use serde::{de::Error, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer>(location: &MultiLocation, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_bytes(&location.encode())
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<MultiLocation, D::Error> {
+		let encoded = Vec::<u8>::deserialize(deserializer)?;
+		MultiLocation::decode(&mut &encoded[..]).map_err(|e| D::Error::custom(e.what()))
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency ID type.
+		type CurrencyId: Parameter + Member + Copy + MaybeSerializeDeserialize + Ord;
+
+		/// The balance type.
+		type Balance: Parameter + Member + AtLeast32BitUnsigned + Default + Copy + MaybeSerializeDeserialize;
+
+		/// The origin which may register and update assets.
+		type RegisterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum length of the name and symbol of an asset.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The currency ID is already registered.
+		AssetAlreadyRegistered,
+		/// The currency ID is not registered.
+		AssetNotRegistered,
+		/// The location is already registered for another currency ID.
+		LocationAlreadyRegistered,
+		/// The name or symbol is longer than `T::StringLimit`.
+		BadMetadata,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	#[pallet::metadata(T::CurrencyId = "CurrencyId")]
+	pub enum Event<T: Config> {
+		/// An asset was registered. \[currency_id, location\]
+		AssetRegistered(T::CurrencyId, MultiLocation),
+		/// An asset was updated. \[currency_id, location\]
+		AssetUpdated(T::CurrencyId, MultiLocation),
+	}
+
+	/// The registered assets.
+	///
+	/// map: currency_id => AssetMetadata
+	#[pallet::storage]
+	#[pallet::getter(fn assets)]
+	pub type Assets<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::CurrencyId,
+		AssetMetadata<T::Balance, BoundedVec<u8, T::StringLimit>>,
+		OptionQuery,
+	>;
+
+	/// The reverse index of registered assets.
+	///
+	/// map: location => currency_id
+	#[pallet::storage]
+	#[pallet::getter(fn location_to_currency_id)]
+	pub type LocationToCurrencyIds<T: Config> =
+		StorageMap<_, Blake2_128Concat, MultiLocation, T::CurrencyId, OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The registered assets. \[currency_id, metadata\]
+		pub assets: Vec<(T::CurrencyId, AssetMetadata<T::Balance, Vec<u8>>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			GenesisConfig { assets: vec![] }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			self.assets.iter().for_each(|(currency_id, metadata)| {
+				Pallet::<T>::do_register_asset(*currency_id, metadata.clone())
+					.expect("assets in genesis should be valid.");
+			});
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register an asset.
+		///
+		/// The dispatch origin for this call must be `T::RegisterOrigin`.
+		///
+		/// - `currency_id`: the currency ID of the asset.
+		/// - `location`: the location of the asset, not registered for any
+		///   other currency ID.
+		/// - `name`: the user friendly name, limited to `T::StringLimit` bytes.
+		/// - `symbol`: the ticker symbol, limited to `T::StringLimit` bytes.
+		/// - `decimals`: the number of decimals of one unit.
+		/// - `existential_deposit`: the minimum amount required to keep an
+		///   account.
+		#[pallet::weight(T::WeightInfo::register_asset())]
+		pub fn register_asset(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			location: Box<MultiLocation>,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+			#[pallet::compact] existential_deposit: T::Balance,
+		) -> DispatchResult {
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			let metadata = AssetMetadata {
+				location: *location,
+				name,
+				symbol,
+				decimals,
+				existential_deposit,
+			};
+			let location = metadata.location.clone();
+			Self::do_register_asset(currency_id, metadata)?;
+
+			Self::deposit_event(Event::AssetRegistered(currency_id, location));
+			Ok(())
+		}
+
+		/// Update a registered asset.
+		///
+		/// The dispatch origin for this call must be `T::RegisterOrigin`.
+		///
+		/// NOTE: Changing the existential deposit doesn't reap existing
+		/// accounts below the new one.
+		///
+		/// - `currency_id`: the currency ID of the asset.
+		/// - `location`: the location of the asset, not registered for any
+		///   other currency ID.
+		/// - `name`: the user friendly name, limited to `T::StringLimit` bytes.
+		/// - `symbol`: the ticker symbol, limited to `T::StringLimit` bytes.
+		/// - `decimals`: the number of decimals of one unit.
+		/// - `existential_deposit`: the minimum amount required to keep an
+		///   account.
+		#[pallet::weight(T::WeightInfo::update_asset())]
+		pub fn update_asset(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			location: Box<MultiLocation>,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+			#[pallet::compact] existential_deposit: T::Balance,
+		) -> DispatchResult {
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			let metadata = Self::bounded_metadata(AssetMetadata {
+				location: *location,
+				name,
+				symbol,
+				decimals,
+				existential_deposit,
+			})?;
+			let location = metadata.location.clone();
+
+			Assets::<T>::try_mutate(currency_id, |maybe_asset| -> DispatchResult {
+				let asset = maybe_asset.as_mut().ok_or(Error::<T>::AssetNotRegistered)?;
+				if asset.location != location {
+					ensure!(
+						!LocationToCurrencyIds::<T>::contains_key(&location),
+						Error::<T>::LocationAlreadyRegistered
+					);
+					LocationToCurrencyIds::<T>::remove(&asset.location);
+					LocationToCurrencyIds::<T>::insert(&location, currency_id);
+				}
+				*asset = metadata;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::AssetUpdated(currency_id, location));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Bound the name and symbol of `metadata` by `T::StringLimit`.
+	fn bounded_metadata(
+		metadata: AssetMetadata<T::Balance, Vec<u8>>,
+	) -> Result<AssetMetadata<T::Balance, BoundedVec<u8, T::StringLimit>>, DispatchError> {
+		Ok(AssetMetadata {
+			location: metadata.location,
+			name: metadata.name.try_into().map_err(|_| Error::<T>::BadMetadata)?,
+			symbol: metadata.symbol.try_into().map_err(|_| Error::<T>::BadMetadata)?,
+			decimals: metadata.decimals,
+			existential_deposit: metadata.existential_deposit,
+		})
+	}
+
+	fn do_register_asset(currency_id: T::CurrencyId, metadata: AssetMetadata<T::Balance, Vec<u8>>) -> DispatchResult {
+		let metadata = Self::bounded_metadata(metadata)?;
+		ensure!(
+			!Assets::<T>::contains_key(currency_id),
+			Error::<T>::AssetAlreadyRegistered
+		);
+		ensure!(
+			!LocationToCurrencyIds::<T>::contains_key(&metadata.location),
+			Error::<T>::LocationAlreadyRegistered
+		);
+
+		LocationToCurrencyIds::<T>::insert(&metadata.location, currency_id);
+		Assets::<T>::insert(currency_id, metadata);
+		Ok(())
+	}
+}
+
+/// Convert the currency ID of a registered asset to its location.
+impl<T: Config> Convert<T::CurrencyId, Option<MultiLocation>> for Pallet<T> {
+	fn convert(currency_id: T::CurrencyId) -> Option<MultiLocation> {
+		Pallet::<T>::assets(currency_id).map(|asset| asset.location)
+	}
+}
+
+/// The existential deposit of a registered asset. Unregistered assets can't
+/// be held, their existential deposit is the max balance.
+impl<T: Config> GetByKey<T::CurrencyId, T::Balance> for Pallet<T> {
+	fn get(currency_id: &T::CurrencyId) -> T::Balance {
+		Pallet::<T>::assets(currency_id)
+			.map(|asset| asset.existential_deposit)
+			.unwrap_or_else(T::Balance::max_value)
+	}
+}
+
+/// Convert the location of a registered asset to its currency ID.
+///
+/// NOTE: It's separated from `Pallet`, as a generic type can't implement both
+/// `Convert<T::CurrencyId, _>` and `Convert<MultiLocation, _>`.
+pub struct LocationToCurrencyId<T>(PhantomData<T>);
+
+impl<T: Config> Convert<MultiLocation, Option<T::CurrencyId>> for LocationToCurrencyId<T> {
+	fn convert(location: MultiLocation) -> Option<T::CurrencyId> {
+		Pallet::<T>::location_to_currency_id(location)
+	}
+}
+
+impl<T: Config> Convert<MultiAsset, Option<T::CurrencyId>> for LocationToCurrencyId<T> {
+	fn convert(asset: MultiAsset) -> Option<T::CurrencyId> {
+		if let MultiAsset {
+			fun: Fungible(_),
+			id: Concrete(location),
+		} = asset
+		{
+			Self::convert(location)
+		} else {
+			None
+		}
+	}
+}
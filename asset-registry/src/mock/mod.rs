@@ -0,0 +1,154 @@
+#![cfg(test)]
+
+use super::*;
+use crate as orml_asset_registry;
+
+use sp_io::TestExternalities;
+use sp_runtime::AccountId32;
+
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain};
+
+pub mod para;
+pub mod relay;
+
+pub const ALICE: AccountId32 = AccountId32::new([0u8; 32]);
+pub const BOB: AccountId32 = AccountId32::new([1u8; 32]);
+
+pub type CurrencyId = u32;
+/// Relay chain token.
+pub const R: CurrencyId = 0;
+/// Parachain A token.
+pub const A: CurrencyId = 1;
+/// Parachain B token.
+pub const B: CurrencyId = 2;
+
+pub type Balance = u128;
+pub type Amount = i128;
+
+pub fn relay_location() -> MultiLocation {
+	MultiLocation::parent()
+}
+
+pub fn para_a_token_location() -> MultiLocation {
+	(Parent, Parachain(1), GeneralKey("A".into())).into()
+}
+
+pub fn para_b_token_location() -> MultiLocation {
+	(Parent, Parachain(2), GeneralKey("B".into())).into()
+}
+
+pub struct CurrencyIdConvert;
+impl Convert<CurrencyId, Option<MultiLocation>> for CurrencyIdConvert {
+	fn convert(id: CurrencyId) -> Option<MultiLocation> {
+		ParaAssetRegistry::convert(id)
+	}
+}
+impl Convert<MultiLocation, Option<CurrencyId>> for CurrencyIdConvert {
+	fn convert(location: MultiLocation) -> Option<CurrencyId> {
+		LocationToCurrencyId::<para::Runtime>::convert(location)
+	}
+}
+impl Convert<MultiAsset, Option<CurrencyId>> for CurrencyIdConvert {
+	fn convert(asset: MultiAsset) -> Option<CurrencyId> {
+		LocationToCurrencyId::<para::Runtime>::convert(asset)
+	}
+}
+
+decl_test_parachain! {
+	pub struct ParaA {
+		Runtime = para::Runtime,
+		XcmpMessageHandler = para::XcmpQueue,
+		DmpMessageHandler = para::DmpQueue,
+		new_ext = para_ext(1),
+	}
+}
+
+decl_test_parachain! {
+	pub struct ParaB {
+		Runtime = para::Runtime,
+		XcmpMessageHandler = para::XcmpQueue,
+		DmpMessageHandler = para::DmpQueue,
+		new_ext = para_ext(2),
+	}
+}
+
+decl_test_relay_chain! {
+	pub struct Relay {
+		Runtime = relay::Runtime,
+		XcmConfig = relay::XcmConfig,
+		new_ext = relay_ext(),
+	}
+}
+
+decl_test_network! {
+	pub struct TestNet {
+		relay_chain = Relay,
+		parachains = vec![
+			(1, ParaA),
+			(2, ParaB),
+		],
+	}
+}
+
+pub type RelayBalances = pallet_balances::Pallet<relay::Runtime>;
+pub type ParaTokens = orml_tokens::Pallet<para::Runtime>;
+pub type ParaXTokens = orml_xtokens::Pallet<para::Runtime>;
+pub type ParaAssetRegistry = orml_asset_registry::Pallet<para::Runtime>;
+
+pub fn para_ext(para_id: u32) -> TestExternalities {
+	use para::{Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+
+	let parachain_info_config = parachain_info::GenesisConfig {
+		parachain_id: para_id.into(),
+	};
+	<parachain_info::GenesisConfig as GenesisBuild<Runtime, _>>::assimilate_storage(&parachain_info_config, &mut t)
+		.unwrap();
+
+	orml_asset_registry::GenesisConfig::<Runtime> {
+		assets: vec![(
+			R,
+			AssetMetadata::<Balance, Vec<u8>> {
+				location: relay_location(),
+				name: b"Relay".to_vec(),
+				symbol: b"R".to_vec(),
+				decimals: 12,
+				existential_deposit: 1,
+			},
+		)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	orml_tokens::GenesisConfig::<Runtime> {
+		balances: vec![(ALICE, R, 1_000)],
+		metadata: vec![],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn relay_ext() -> sp_io::TestExternalities {
+	use relay::{Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(ALICE, 1_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
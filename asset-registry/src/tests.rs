@@ -0,0 +1,369 @@
+//! Unit tests for the asset registry module.
+
+#![cfg(test)]
+
+use super::*;
+use cumulus_primitives_core::ParaId;
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use mock::*;
+use orml_traits::MultiCurrency;
+use polkadot_parachain::primitives::Sibling;
+use sp_runtime::{traits::BadOrigin, AccountId32};
+use xcm_simulator::TestExt;
+
+fn sibling_b_account() -> AccountId32 {
+	use sp_runtime::traits::AccountIdConversion;
+	Sibling::from(2).into_account()
+}
+
+fn para_a_account() -> AccountId32 {
+	use polkadot_parachain::primitives::AccountIdConversion;
+	ParaId::from(1).into_account()
+}
+
+fn register_para_a_token() -> DispatchResult {
+	ParaAssetRegistry::register_asset(
+		para::Origin::root(),
+		A,
+		Box::new(para_a_token_location()),
+		b"TokenA".to_vec(),
+		b"A".to_vec(),
+		12,
+		2,
+	)
+}
+
+fn para_event_exists(e: para::Event) -> bool {
+	para::System::events().iter().any(|record| record.event == e)
+}
+
+#[test]
+fn genesis_assets_should_work() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_eq!(
+			ParaAssetRegistry::assets(R),
+			Some(AssetMetadata {
+				location: relay_location(),
+				name: b"Relay".to_vec().try_into().unwrap(),
+				symbol: b"R".to_vec().try_into().unwrap(),
+				decimals: 12,
+				existential_deposit: 1,
+			})
+		);
+		assert_eq!(ParaAssetRegistry::location_to_currency_id(relay_location()), Some(R));
+	});
+}
+
+#[test]
+fn register_asset_should_work() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(register_para_a_token());
+		assert!(para_event_exists(para::Event::AssetRegistry(
+			crate::Event::AssetRegistered(A, para_a_token_location())
+		)));
+
+		assert_eq!(
+			ParaAssetRegistry::assets(A),
+			Some(AssetMetadata {
+				location: para_a_token_location(),
+				name: b"TokenA".to_vec().try_into().unwrap(),
+				symbol: b"A".to_vec().try_into().unwrap(),
+				decimals: 12,
+				existential_deposit: 2,
+			})
+		);
+		assert_eq!(
+			ParaAssetRegistry::location_to_currency_id(para_a_token_location()),
+			Some(A)
+		);
+
+		assert_eq!(
+			<ParaAssetRegistry as Convert<_, Option<MultiLocation>>>::convert(A),
+			Some(para_a_token_location())
+		);
+		assert_eq!(
+			<LocationToCurrencyId<para::Runtime> as Convert<MultiLocation, _>>::convert(para_a_token_location()),
+			Some(A)
+		);
+		assert_eq!(
+			<LocationToCurrencyId<para::Runtime> as Convert<MultiAsset, _>>::convert(
+				(para_a_token_location(), 100).into()
+			),
+			Some(A)
+		);
+		assert_eq!(<ParaAssetRegistry as GetByKey<_, _>>::get(&A), 2);
+	});
+}
+
+#[test]
+fn register_asset_fails_if_invalid() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_noop!(
+			ParaAssetRegistry::register_asset(
+				Some(ALICE).into(),
+				A,
+				Box::new(para_a_token_location()),
+				b"TokenA".to_vec(),
+				b"A".to_vec(),
+				12,
+				2,
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			ParaAssetRegistry::register_asset(
+				para::Origin::root(),
+				R,
+				Box::new(para_a_token_location()),
+				b"TokenA".to_vec(),
+				b"A".to_vec(),
+				12,
+				2,
+			),
+			Error::<para::Runtime>::AssetAlreadyRegistered
+		);
+		assert_noop!(
+			ParaAssetRegistry::register_asset(
+				para::Origin::root(),
+				A,
+				Box::new(relay_location()),
+				b"TokenA".to_vec(),
+				b"A".to_vec(),
+				12,
+				2,
+			),
+			Error::<para::Runtime>::LocationAlreadyRegistered
+		);
+		assert_noop!(
+			ParaAssetRegistry::register_asset(
+				para::Origin::root(),
+				A,
+				Box::new(para_a_token_location()),
+				b"TooLongName".to_vec(),
+				b"A".to_vec(),
+				12,
+				2,
+			),
+			Error::<para::Runtime>::BadMetadata
+		);
+	});
+}
+
+#[test]
+fn update_asset_should_work() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(register_para_a_token());
+
+		assert_ok!(ParaAssetRegistry::update_asset(
+			para::Origin::root(),
+			A,
+			Box::new(para_b_token_location()),
+			b"TokenB".to_vec(),
+			b"B".to_vec(),
+			10,
+			3,
+		));
+		assert!(para_event_exists(para::Event::AssetRegistry(
+			crate::Event::AssetUpdated(A, para_b_token_location())
+		)));
+
+		assert_eq!(
+			ParaAssetRegistry::assets(A),
+			Some(AssetMetadata {
+				location: para_b_token_location(),
+				name: b"TokenB".to_vec().try_into().unwrap(),
+				symbol: b"B".to_vec().try_into().unwrap(),
+				decimals: 10,
+				existential_deposit: 3,
+			})
+		);
+		assert_eq!(
+			ParaAssetRegistry::location_to_currency_id(para_a_token_location()),
+			None
+		);
+		assert_eq!(
+			ParaAssetRegistry::location_to_currency_id(para_b_token_location()),
+			Some(A)
+		);
+		assert_eq!(<ParaAssetRegistry as GetByKey<_, _>>::get(&A), 3);
+	});
+}
+
+#[test]
+fn update_asset_fails_if_invalid() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_noop!(
+			ParaAssetRegistry::update_asset(
+				Some(ALICE).into(),
+				R,
+				Box::new(relay_location()),
+				b"Relay".to_vec(),
+				b"R".to_vec(),
+				12,
+				1,
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			ParaAssetRegistry::update_asset(
+				para::Origin::root(),
+				A,
+				Box::new(para_a_token_location()),
+				b"TokenA".to_vec(),
+				b"A".to_vec(),
+				12,
+				2,
+			),
+			Error::<para::Runtime>::AssetNotRegistered
+		);
+
+		assert_ok!(register_para_a_token());
+		assert_noop!(
+			ParaAssetRegistry::update_asset(
+				para::Origin::root(),
+				A,
+				Box::new(relay_location()),
+				b"TokenA".to_vec(),
+				b"A".to_vec(),
+				12,
+				2,
+			),
+			Error::<para::Runtime>::LocationAlreadyRegistered
+		);
+		assert_noop!(
+			ParaAssetRegistry::update_asset(
+				para::Origin::root(),
+				A,
+				Box::new(para_a_token_location()),
+				b"TokenA".to_vec(),
+				b"TooLongSymbol".to_vec(),
+				12,
+				2,
+			),
+			Error::<para::Runtime>::BadMetadata
+		);
+	});
+}
+
+#[test]
+fn unregistered_asset_cannot_be_held() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_eq!(<ParaAssetRegistry as GetByKey<_, _>>::get(&A), Balance::max_value());
+		assert_eq!(
+			<ParaAssetRegistry as Convert<_, Option<MultiLocation>>>::convert(A),
+			None
+		);
+		assert_eq!(
+			<LocationToCurrencyId<para::Runtime> as Convert<MultiLocation, _>>::convert(para_a_token_location()),
+			None
+		);
+		assert!(ParaTokens::deposit(A, &ALICE, 1_000).is_err());
+	});
+}
+
+#[test]
+fn send_relay_chain_asset_to_sibling() {
+	TestNet::reset();
+
+	Relay::execute_with(|| {
+		let _ = RelayBalances::deposit_creating(&para_a_account(), 1_000);
+	});
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			R,
+			500,
+			Box::new(MultiLocation::new(
+				1,
+				X2(
+					Parachain(2),
+					Junction::AccountId32 {
+						network: NetworkId::Any,
+						id: BOB.into(),
+					}
+				)
+			)),
+			30,
+		));
+		assert_eq!(ParaTokens::free_balance(R, &ALICE), 500);
+	});
+
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(R, &BOB), 440);
+	});
+}
+
+#[test]
+fn send_registered_asset_to_sibling_without_runtime_upgrade() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(register_para_a_token());
+		assert_ok!(ParaTokens::deposit(A, &ALICE, 1_000));
+
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			A,
+			500,
+			Box::new(MultiLocation::new(
+				1,
+				X2(
+					Parachain(2),
+					Junction::AccountId32 {
+						network: NetworkId::Any,
+						id: BOB.into(),
+					}
+				)
+			)),
+			30,
+		));
+		assert_eq!(ParaTokens::free_balance(A, &ALICE), 500);
+		assert_eq!(ParaTokens::free_balance(A, &sibling_b_account()), 500);
+	});
+
+	// `A` isn't registered on parachain B yet, the transfer can't be deposited.
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(A, &BOB), 0);
+	});
+
+	ParaB::execute_with(|| {
+		assert_ok!(register_para_a_token());
+	});
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			A,
+			500,
+			Box::new(MultiLocation::new(
+				1,
+				X2(
+					Parachain(2),
+					Junction::AccountId32 {
+						network: NetworkId::Any,
+						id: BOB.into(),
+					}
+				)
+			)),
+			30,
+		));
+		assert_eq!(ParaTokens::free_balance(A, &ALICE), 0);
+	});
+
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(A, &BOB), 470);
+	});
+}
@@ -0,0 +1,38 @@
+//! Weights for orml_asset_registry
+//!
+//! NOTE: the weights are estimated from the storage accesses of the calls,
+//! until `register_asset` and `update_asset` are benchmarked with
+//! `benchmarking.rs`, by running:
+//!
+//! benchmark --pallet=orml_asset_registry --extrinsic=* --steps=50
+//! --repeat=20 --execution=wasm --wasm-execution=compiled --heap-pages=4096
+//! --template=../templates/orml-weight-template.hbs
+//! --output=./asset-registry/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for orml_asset_registry.
+pub trait WeightInfo {
+	fn register_asset() -> Weight;
+	fn update_asset() -> Weight;
+}
+
+/// Default weights.
+impl WeightInfo for () {
+	fn register_asset() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn update_asset() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+}
@@ -0,0 +1,56 @@
+//! Benchmarks for the currencies module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::One;
+
+const SEED: u32 = 0;
+
+/// The `i`th non-native currency, the currencies from `1_000` must be backed by
+/// `T::MultiCurrency`.
+fn non_native_currency<T: Config>(i: u32) -> CurrencyIdOf<T>
+where
+	CurrencyIdOf<T>: From<u32>,
+{
+	1_000u32.saturating_add(i).into()
+}
+
+/// An amount of `currency_id` well above its existential deposit.
+fn amount<T: Config>(currency_id: CurrencyIdOf<T>) -> BalanceOf<T> {
+	<Pallet<T> as MultiCurrency<T::AccountId>>::minimum_balance(currency_id)
+		.max(One::one())
+		.saturating_mul(1_000u32.into())
+}
+
+benchmarks! {
+	where_clause { where CurrencyIdOf<T>: From<u32> }
+
+	// every currency, and the native currency, creates an account of `to`
+	transfer_all_currencies {
+		let c in 0 .. T::MaxHeldCurrencies::get();
+
+		let from: T::AccountId = whitelisted_caller();
+		let to: T::AccountId = account("to", 0, SEED);
+		let native_currency_id = T::GetNativeCurrencyId::get();
+		<Pallet<T> as MultiCurrency<_>>::deposit(native_currency_id, &from, amount::<T>(native_currency_id))?;
+		for i in 0..c {
+			let currency_id = non_native_currency::<T>(i);
+			<Pallet<T> as MultiCurrency<_>>::deposit(currency_id, &from, amount::<T>(currency_id))?;
+		}
+	}: _(RawOrigin::Signed(from), T::Lookup::unlookup(to.clone()), false)
+	verify {
+		assert_eq!(
+			<Pallet<T> as MultiCurrency<_>>::free_balance(native_currency_id, &to),
+			amount::<T>(native_currency_id)
+		);
+		for i in 0..c {
+			let currency_id = non_native_currency::<T>(i);
+			assert_eq!(<Pallet<T> as MultiCurrency<_>>::free_balance(currency_id, &to), amount::<T>(currency_id));
+		}
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime,);
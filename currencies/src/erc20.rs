@@ -0,0 +1,116 @@
+use crate::{AmountOf, BalanceOf, Config, Error};
+use frame_support::traits::tokens::{DepositConsequence, WithdrawConsequence};
+use orml_traits::{arithmetic::Signed, Erc20Adapter, Erc20Error};
+use sp_core::H160;
+use sp_runtime::{
+	traits::{CheckedSub, Zero},
+	DispatchResult,
+};
+use sp_std::{convert::TryInto, marker::PhantomData};
+
+impl<T: Config> From<Erc20Error> for Error<T> {
+	fn from(error: Erc20Error) -> Self {
+		match error {
+			Erc20Error::ContractNotFound => Error::<T>::Erc20ContractNotFound,
+			Erc20Error::Reverted => Error::<T>::Erc20Reverted,
+			Erc20Error::OutOfGas => Error::<T>::Erc20OutOfGas,
+			Erc20Error::InvalidOutput => Error::<T>::Erc20InvalidOutput,
+		}
+	}
+}
+
+/// The currency of the ERC-20 contract, called through `T::Erc20Adapter`.
+pub(crate) struct Erc20Currency<T>(PhantomData<T>);
+
+impl<T: Config> Erc20Currency<T> {
+	pub fn total_issuance(contract: H160) -> BalanceOf<T> {
+		T::Erc20Adapter::total_supply(contract).unwrap_or_else(|_| Zero::zero())
+	}
+
+	pub fn free_balance(contract: H160, who: &T::AccountId) -> BalanceOf<T> {
+		T::Erc20Adapter::balance_of(contract, who).unwrap_or_else(|_| Zero::zero())
+	}
+
+	pub fn ensure_can_withdraw(contract: H160, who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		T::Erc20Adapter::balance_of(contract, who)
+			.map_err(Error::<T>::from)?
+			.checked_sub(&amount)
+			.ok_or(Error::<T>::BalanceTooLow)?;
+		Ok(())
+	}
+
+	pub fn transfer(contract: H160, from: &T::AccountId, to: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		Self::ensure_can_withdraw(contract, from, amount)?;
+		T::Erc20Adapter::transfer(contract, from, to, amount).map_err(Error::<T>::from)?;
+		Ok(())
+	}
+
+	pub fn deposit(contract: H160, who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		T::Erc20Adapter::mint(contract, who, amount).map_err(Error::<T>::from)?;
+		Ok(())
+	}
+
+	pub fn withdraw(contract: H160, who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		Self::ensure_can_withdraw(contract, who, amount)?;
+		T::Erc20Adapter::burn(contract, who, amount).map_err(Error::<T>::from)?;
+		Ok(())
+	}
+
+	/// Burn up to `amount` from the balance of `who`, return the amount
+	/// couldn't be slashed.
+	pub fn slash(contract: H160, who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+		amount - Self::decrease_balance_at_most(contract, who, amount)
+	}
+
+	pub fn can_deposit(contract: H160) -> DepositConsequence {
+		match T::Erc20Adapter::total_supply(contract) {
+			Ok(_) => DepositConsequence::Success,
+			Err(_) => DepositConsequence::UnknownAsset,
+		}
+	}
+
+	pub fn can_withdraw(contract: H160, who: &T::AccountId, amount: BalanceOf<T>) -> WithdrawConsequence<BalanceOf<T>> {
+		match T::Erc20Adapter::balance_of(contract, who) {
+			Ok(balance) if balance >= amount => WithdrawConsequence::Success,
+			Ok(_) => WithdrawConsequence::NoFunds,
+			Err(_) => WithdrawConsequence::UnknownAsset,
+		}
+	}
+
+	/// Burn up to `amount` from the balance of `who`, return the amount burned.
+	pub fn decrease_balance_at_most(contract: H160, who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+		let actual = amount.min(Self::free_balance(contract, who));
+		if actual.is_zero() || T::Erc20Adapter::burn(contract, who, actual).is_err() {
+			return Zero::zero();
+		}
+		actual
+	}
+
+	/// Mint `amount` to `who`, return the amount minted.
+	pub fn increase_balance_at_most(contract: H160, who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+		if amount.is_zero() || T::Erc20Adapter::mint(contract, who, amount).is_err() {
+			return Zero::zero();
+		}
+		amount
+	}
+
+	pub fn update_balance(contract: H160, who: &T::AccountId, by_amount: AmountOf<T>) -> DispatchResult {
+		if by_amount.is_zero() {
+			return Ok(());
+		}
+
+		let by_balance = by_amount
+			.abs()
+			.try_into()
+			.map_err(|_| Error::<T>::AmountIntoBalanceFailed)?;
+		if by_amount.is_positive() {
+			Self::deposit(contract, who, by_balance)
+		} else {
+			Self::withdraw(contract, who, by_balance)
+		}
+	}
+}
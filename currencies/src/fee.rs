@@ -0,0 +1,190 @@
+use crate::{BalanceOf, Config, CurrencyIdOf, FeeCurrency, Pallet};
+use codec::{Decode, Encode};
+use frame_support::traits::{Get, WithdrawReasons};
+use orml_traits::{MultiCurrency, MultiLockableCurrency, PriceProvider};
+use pallet_transaction_payment::{ChargeTransactionPayment, OnChargeTransaction};
+use sp_runtime::{
+	traits::{CheckedSub, DispatchInfoOf, One, PostDispatchInfoOf, Saturating, SignedExtension, Zero},
+	transaction_validity::{InvalidTransaction, TransactionValidity, TransactionValidityError},
+	FixedPointNumber, FixedPointOperand,
+};
+use sp_std::{fmt, marker::PhantomData};
+
+/// Pay transaction fees in the currency set by `SetFeeCurrency`, or in the
+/// native currency if not set.
+///
+/// The native fee is converted by the price of the native currency quoted in
+/// the fee currency from `Prices`. The fee is withdrawn from the payer before
+/// dispatch, the unused portion is refunded after dispatch, and the rest is
+/// deposited to `FeeReceiver`.
+///
+/// Like the native `CurrencyAdapter`, only the locks restricting transaction
+/// payment apply, and the payer must be kept alive.
+pub struct MultiCurrencyFeeAdapter<T, Prices, Price, FeeReceiver>(PhantomData<(T, Prices, Price, FeeReceiver)>);
+
+impl<T, Prices, Price, FeeReceiver> OnChargeTransaction<T> for MultiCurrencyFeeAdapter<T, Prices, Price, FeeReceiver>
+where
+	T: Config + pallet_transaction_payment::Config,
+	BalanceOf<T>: FixedPointOperand,
+	Prices: PriceProvider<CurrencyIdOf<T>, Price>,
+	Price: FixedPointNumber,
+	FeeReceiver: Get<T::AccountId>,
+{
+	type Balance = BalanceOf<T>;
+	/// The fee currency, the price used and the amount withdrawn.
+	type LiquidityInfo = Option<(CurrencyIdOf<T>, Price, BalanceOf<T>)>;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		_call: &T::Call,
+		_info: &DispatchInfoOf<T::Call>,
+		fee: Self::Balance,
+		_tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let native_currency_id = T::GetNativeCurrencyId::get();
+		// set by `SetFeeCurrency` around `ChargeTransactionPayment`
+		let currency_id = FeeCurrency::<T>::get().unwrap_or(native_currency_id);
+		if fee.is_zero() {
+			return Ok(None);
+		}
+
+		let price = if currency_id == native_currency_id {
+			Price::one()
+		} else {
+			Prices::get_price(native_currency_id, currency_id).ok_or(InvalidTransaction::Payment)?
+		};
+		let amount = price.saturating_mul_int(fee);
+		// a non-zero fee is never paid for free
+		if amount.is_zero() {
+			return Err(InvalidTransaction::Payment.into());
+		}
+
+		let new_free = <Pallet<T> as MultiCurrency<T::AccountId>>::free_balance(currency_id, who)
+			.checked_sub(&amount)
+			.ok_or(InvalidTransaction::Payment)?;
+		if new_free < <Pallet<T> as MultiCurrency<T::AccountId>>::minimum_balance(currency_id) {
+			return Err(InvalidTransaction::Payment.into());
+		}
+		<Pallet<T> as MultiLockableCurrency<T::AccountId>>::withdraw_with_reasons(
+			currency_id,
+			who,
+			amount,
+			WithdrawReasons::TRANSACTION_PAYMENT,
+		)
+		.map_err(|_| InvalidTransaction::Payment)?;
+
+		Ok(Some((currency_id, price, amount)))
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		_dispatch_info: &DispatchInfoOf<T::Call>,
+		_post_info: &PostDispatchInfoOf<T::Call>,
+		corrected_fee: Self::Balance,
+		_tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		if let Some((currency_id, price, paid)) = already_withdrawn {
+			let actual = price.saturating_mul_int(corrected_fee).min(paid);
+			let refund = paid.saturating_sub(actual);
+
+			// The refund is collected too if it can't be deposited back, i.e. the
+			// payer has been reaped and the refund is below the existential deposit.
+			let collected = if <Pallet<T> as MultiCurrency<T::AccountId>>::deposit(currency_id, who, refund).is_ok() {
+				actual
+			} else {
+				paid
+			};
+			// Like the native `CurrencyAdapter`, a failed deposit drops the fee.
+			let _ = <Pallet<T> as MultiCurrency<T::AccountId>>::deposit(currency_id, &FeeReceiver::get(), collected);
+		}
+		Ok(())
+	}
+}
+
+/// Pay the transaction fees in the named currency, `None` for the native
+/// currency, with the wrapped `ChargeTransactionPayment`.
+///
+/// It replaces `ChargeTransactionPayment` in the signed extensions, so the fee
+/// currency is always named before the fee is withdrawn.
+/// `MultiCurrencyFeeAdapter` must be the `OnChargeTransaction` of the runtime.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct SetFeeCurrency<T: Config + pallet_transaction_payment::Config>(
+	pub Option<CurrencyIdOf<T>>,
+	pub ChargeTransactionPayment<T>,
+);
+
+impl<T: Config + pallet_transaction_payment::Config> SetFeeCurrency<T> {
+	pub fn new(currency_id: Option<CurrencyIdOf<T>>, charge: ChargeTransactionPayment<T>) -> Self {
+		Self(currency_id, charge)
+	}
+
+	/// Run `f` with the fee currency set for `MultiCurrencyFeeAdapter`. The
+	/// fee currency is removed before returning, so it never leaks out of
+	/// `f`, even if `f` fails.
+	fn with_fee_currency<R>(&self, f: impl FnOnce() -> R) -> R {
+		FeeCurrency::<T>::set(self.0);
+		let result = f();
+		FeeCurrency::<T>::kill();
+		result
+	}
+}
+
+impl<T: Config + pallet_transaction_payment::Config> fmt::Debug for SetFeeCurrency<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "SetFeeCurrency({:?}, {:?})", self.0, self.1)
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + pallet_transaction_payment::Config> SignedExtension for SetFeeCurrency<T>
+where
+	CurrencyIdOf<T>: Send + Sync,
+	ChargeTransactionPayment<T>: SignedExtension<AccountId = T::AccountId, Call = T::Call, AdditionalSigned = ()>,
+{
+	const IDENTIFIER: &'static str = "SetFeeCurrency";
+	type AccountId = T::AccountId;
+	type Call = T::Call;
+	type AdditionalSigned = ();
+	/// The fee currency is carried by the liquidity info in `Pre`.
+	type Pre = <ChargeTransactionPayment<T> as SignedExtension>::Pre;
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		self.1.additional_signed()
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		self.with_fee_currency(|| self.1.validate(who, call, info, len))
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		self.with_fee_currency(|| self.1.clone().pre_dispatch(who, call, info, len))
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &sp_runtime::DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		ChargeTransactionPayment::<T>::post_dispatch(pre, info, post_info, len, result)
+	}
+}
@@ -0,0 +1,559 @@
+use crate::{AmountOf, BalanceOf, Config, CurrencyIdOf, Error, FungiblesLocks, FungiblesReserves};
+use frame_support::{
+	ensure,
+	traits::{
+		tokens::{
+			fungibles::{Inspect, Mutate, Transfer, Unbalanced},
+			AssetId, DepositConsequence, WithdrawConsequence,
+		},
+		Get,
+	},
+	transactional, BoundedVec,
+};
+use orml_traits::{
+	arithmetic::Signed, BalanceStatus, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
+	MultiReducibleCurrency, MultiReservableCurrency,
+};
+use sp_runtime::{
+	traits::{AccountIdConversion, CheckedSub, Saturating, Zero},
+	DispatchError, DispatchResult,
+};
+use sp_std::{convert::TryInto, marker::PhantomData, prelude::*, result};
+
+/// Adapt `fungibles` implementations, e.g. `pallet_assets`, into
+/// `MultiCurrencyExtended`, `MultiLockableCurrency` and
+/// `MultiReservableCurrency`.
+///
+/// The asset ID of `Assets` must be the currency ID. `CurrencyIds` are the
+/// currencies of `Assets` reported by `held_currencies`, as `fungibles` can't
+/// enumerate the assets of an account. They're bounded by
+/// `T::MaxFungiblesCurrencies`, as all of them are read by `held_currencies`.
+///
+/// The reserved balances are transferred to the account of `T::PalletId`,
+/// and recorded in `FungiblesReserves`. The reserve account is given a
+/// provider reference on the first reserve, so it could hold assets which are
+/// not sufficient. It's kept at the minimum balance of the asset while it
+/// holds the reserved balances of others, so a reserved balance may be only
+/// partly unreserved, slashed or repatriated.
+///
+/// The locks are recorded in `FungiblesLocks`. They're only respected by
+/// `Assets` itself if it's told about them, e.g. by setting
+/// `FungiblesFreezer` as `pallet_assets::Config::Freezer`, otherwise only by
+/// the operations through this adapter.
+pub struct FungiblesAdapter<T, Assets, CurrencyIds>(PhantomData<(T, Assets, CurrencyIds)>);
+
+type LocksOf<T> = BoundedVec<(LockIdentifier, BalanceOf<T>), <T as Config>::MaxLocks>;
+
+/// The max amount of the locks of `who` under `currency_id`.
+fn frozen_balance<T: Config>(currency_id: CurrencyIdOf<T>, who: &T::AccountId) -> BalanceOf<T> {
+	FungiblesLocks::<T>::get(currency_id, who)
+		.iter()
+		.map(|(_, amount)| *amount)
+		.max()
+		.unwrap_or_else(Zero::zero)
+}
+
+/// Tell `pallet_assets` about the locks of `FungiblesAdapter`, so they're
+/// respected by the calls of `pallet_assets` too. It must be set as
+/// `pallet_assets::Config::Freezer` of the runtime, if `pallet_assets` is the
+/// `Assets` of `FungiblesAdapter`.
+///
+/// NOTE: `pallet_assets` keeps the minimum balance of the asset on top of the
+/// locked balance.
+#[cfg(any(feature = "assets-freezer", test))]
+pub struct FungiblesFreezer<T>(PhantomData<T>);
+
+#[cfg(any(feature = "assets-freezer", test))]
+impl<T: Config> pallet_assets::FrozenBalance<CurrencyIdOf<T>, T::AccountId, BalanceOf<T>> for FungiblesFreezer<T> {
+	fn frozen_balance(asset: CurrencyIdOf<T>, who: &T::AccountId) -> Option<BalanceOf<T>> {
+		let frozen = frozen_balance::<T>(asset, who);
+		if frozen.is_zero() {
+			None
+		} else {
+			Some(frozen)
+		}
+	}
+
+	fn died(asset: CurrencyIdOf<T>, who: &T::AccountId) {
+		FungiblesLocks::<T>::remove(asset, who);
+	}
+}
+
+impl<T, Assets, CurrencyIds> FungiblesAdapter<T, Assets, CurrencyIds>
+where
+	T: Config,
+	CurrencyIds: Get<BoundedVec<CurrencyIdOf<T>, <T as Config>::MaxFungiblesCurrencies>>,
+	Assets: Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>
+		+ Mutate<T::AccountId>
+		+ Transfer<T::AccountId>,
+{
+	/// The account holding the reserved balances.
+	fn reserve_account() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// The account holding the reserved balances, given a provider reference
+	/// if it has none, to be able to receive any asset.
+	fn ensure_reserve_account() -> T::AccountId {
+		let reserve_account = Self::reserve_account();
+		if frame_system::Pallet::<T>::providers(&reserve_account).is_zero() {
+			frame_system::Pallet::<T>::inc_providers(&reserve_account);
+		}
+		reserve_account
+	}
+
+	/// The part of `amount` which could be moved out of the reserve account,
+	/// and whether the reserve account must be kept alive to move it.
+	///
+	/// A transfer or burn reaping the reserve account would sweep the balance
+	/// left below the minimum balance of `Assets` too, which is reserved for
+	/// others. So the reserve account is only reaped by moving all of its
+	/// balance, and is kept alive otherwise.
+	fn reserve_account_outflow(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> (BalanceOf<T>, bool) {
+		let reserve_account = Self::reserve_account();
+		let balance = Assets::balance(currency_id, &reserve_account);
+		if amount >= balance {
+			(balance, false)
+		} else {
+			(
+				amount.min(Assets::reducible_balance(currency_id, &reserve_account, true)),
+				true,
+			)
+		}
+	}
+
+	fn frozen_balance(currency_id: CurrencyIdOf<T>, who: &T::AccountId) -> BalanceOf<T> {
+		frozen_balance::<T>(currency_id, who)
+	}
+
+	fn set_reserved_balance(currency_id: CurrencyIdOf<T>, who: &T::AccountId, reserved: BalanceOf<T>) {
+		if reserved.is_zero() {
+			FungiblesReserves::<T>::remove(currency_id, who);
+		} else {
+			FungiblesReserves::<T>::insert(currency_id, who, reserved);
+		}
+	}
+
+	fn mutate_locks(
+		currency_id: CurrencyIdOf<T>,
+		who: &T::AccountId,
+		f: impl FnOnce(&mut LocksOf<T>) -> DispatchResult,
+	) -> DispatchResult {
+		FungiblesLocks::<T>::try_mutate_exists(currency_id, who, |maybe_locks| -> DispatchResult {
+			let mut locks = maybe_locks.take().unwrap_or_default();
+			f(&mut locks)?;
+			if !locks.is_empty() {
+				*maybe_locks = Some(locks);
+			}
+			Ok(())
+		})
+	}
+}
+
+impl<T, Assets, CurrencyIds> MultiCurrency<T::AccountId> for FungiblesAdapter<T, Assets, CurrencyIds>
+where
+	T: Config,
+	CurrencyIds: Get<BoundedVec<CurrencyIdOf<T>, <T as Config>::MaxFungiblesCurrencies>>,
+	Assets: Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>
+		+ Mutate<T::AccountId>
+		+ Transfer<T::AccountId>,
+{
+	type CurrencyId = CurrencyIdOf<T>;
+	type Balance = BalanceOf<T>;
+
+	fn minimum_balance(currency_id: Self::CurrencyId) -> Self::Balance {
+		Assets::minimum_balance(currency_id)
+	}
+
+	fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance {
+		Assets::total_issuance(currency_id)
+	}
+
+	fn total_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		Assets::balance(currency_id, who).saturating_add(FungiblesReserves::<T>::get(currency_id, who))
+	}
+
+	fn free_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		Assets::balance(currency_id, who)
+	}
+
+	fn ensure_can_withdraw(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		let new_balance = Self::free_balance(currency_id, who)
+			.checked_sub(&amount)
+			.ok_or(Error::<T>::BalanceTooLow)?;
+		ensure!(
+			new_balance >= Self::frozen_balance(currency_id, who),
+			Error::<T>::LiquidityRestrictions
+		);
+		Assets::can_withdraw(currency_id, who, amount).into_result()?;
+		Ok(())
+	}
+
+	fn transfer(
+		currency_id: Self::CurrencyId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() || from == to {
+			return Ok(());
+		}
+
+		Self::ensure_can_withdraw(currency_id, from, amount)?;
+		Assets::transfer(currency_id, from, to, amount, false)?;
+		Ok(())
+	}
+
+	fn deposit(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		Assets::mint_into(currency_id, who, amount)
+	}
+
+	fn withdraw(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		Self::ensure_can_withdraw(currency_id, who, amount)?;
+		Assets::burn_from(currency_id, who, amount)?;
+		Ok(())
+	}
+
+	fn can_slash(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> bool {
+		if value.is_zero() {
+			return true;
+		}
+		Self::free_balance(currency_id, who) >= value
+	}
+
+	/// Slash the free balance first, then the reserved balance. The locks are
+	/// not respected.
+	fn slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
+		if amount.is_zero() {
+			return amount;
+		}
+
+		let free_slash_amount = amount.min(Assets::reducible_balance(currency_id, who, false));
+		let free_slashed = Assets::burn_from(currency_id, who, free_slash_amount).unwrap_or_else(|_| Zero::zero());
+		Self::slash_reserved(currency_id, who, amount.saturating_sub(free_slashed))
+	}
+}
+
+impl<T, Assets, CurrencyIds> MultiCurrencyExtended<T::AccountId> for FungiblesAdapter<T, Assets, CurrencyIds>
+where
+	T: Config,
+	CurrencyIds: Get<BoundedVec<CurrencyIdOf<T>, <T as Config>::MaxFungiblesCurrencies>>,
+	Assets: Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>
+		+ Mutate<T::AccountId>
+		+ Transfer<T::AccountId>,
+{
+	type Amount = AmountOf<T>;
+
+	fn update_balance(currency_id: Self::CurrencyId, who: &T::AccountId, by_amount: Self::Amount) -> DispatchResult {
+		if by_amount.is_zero() {
+			return Ok(());
+		}
+
+		let by_balance = by_amount
+			.abs()
+			.try_into()
+			.map_err(|_| Error::<T>::AmountIntoBalanceFailed)?;
+		if by_amount.is_positive() {
+			Self::deposit(currency_id, who, by_balance)
+		} else {
+			Self::withdraw(currency_id, who, by_balance)
+		}
+	}
+}
+
+impl<T, Assets, CurrencyIds> MultiLockableCurrency<T::AccountId> for FungiblesAdapter<T, Assets, CurrencyIds>
+where
+	T: Config,
+	CurrencyIds: Get<BoundedVec<CurrencyIdOf<T>, <T as Config>::MaxFungiblesCurrencies>>,
+	Assets: Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>
+		+ Mutate<T::AccountId>
+		+ Transfer<T::AccountId>,
+{
+	type Moment = T::BlockNumber;
+
+	fn set_lock(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Self::remove_lock(lock_id, currency_id, who);
+		}
+
+		Self::mutate_locks(currency_id, who, |locks| {
+			if let Some(lock) = locks.iter_mut().find(|(id, _)| *id == lock_id) {
+				lock.1 = amount;
+				Ok(())
+			} else {
+				locks
+					.try_push((lock_id, amount))
+					.map_err(|_| Error::<T>::MaxLocksExceeded.into())
+			}
+		})
+	}
+
+	fn extend_lock(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		Self::mutate_locks(currency_id, who, |locks| {
+			if let Some(lock) = locks.iter_mut().find(|(id, _)| *id == lock_id) {
+				lock.1 = lock.1.max(amount);
+				Ok(())
+			} else {
+				locks
+					.try_push((lock_id, amount))
+					.map_err(|_| Error::<T>::MaxLocksExceeded.into())
+			}
+		})
+	}
+
+	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
+		Self::mutate_locks(currency_id, who, |locks| {
+			locks.retain(|(id, _)| *id != lock_id);
+			Ok(())
+		})
+	}
+}
+
+impl<T, Assets, CurrencyIds> MultiReservableCurrency<T::AccountId> for FungiblesAdapter<T, Assets, CurrencyIds>
+where
+	T: Config,
+	CurrencyIds: Get<BoundedVec<CurrencyIdOf<T>, <T as Config>::MaxFungiblesCurrencies>>,
+	Assets: Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>
+		+ Mutate<T::AccountId>
+		+ Transfer<T::AccountId>,
+{
+	fn can_reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> bool {
+		if value.is_zero() {
+			return true;
+		}
+		Self::ensure_can_withdraw(currency_id, who, value).is_ok()
+			&& value <= Assets::reducible_balance(currency_id, who, true)
+	}
+
+	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
+		if value.is_zero() {
+			return value;
+		}
+
+		let reserved_balance = Self::reserved_balance(currency_id, who);
+		let (actual, _) = Self::reserve_account_outflow(currency_id, value.min(reserved_balance));
+		let actual = Assets::burn_from(currency_id, &Self::reserve_account(), actual).unwrap_or_else(|_| Zero::zero());
+		Self::set_reserved_balance(currency_id, who, reserved_balance.saturating_sub(actual));
+		value.saturating_sub(actual)
+	}
+
+	fn reserved_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		FungiblesReserves::<T>::get(currency_id, who)
+	}
+
+	/// `who` is kept alive, so the reserved balance could always be
+	/// unreserved back to it.
+	#[transactional]
+	fn reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> DispatchResult {
+		if value.is_zero() {
+			return Ok(());
+		}
+
+		Self::ensure_can_withdraw(currency_id, who, value)?;
+		let reserved = Assets::transfer(currency_id, who, &Self::ensure_reserve_account(), value, true)?;
+		ensure!(reserved == value, Error::<T>::BalanceTooLow);
+		Self::set_reserved_balance(
+			currency_id,
+			who,
+			Self::reserved_balance(currency_id, who).saturating_add(reserved),
+		);
+		Ok(())
+	}
+
+	fn unreserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
+		if value.is_zero() {
+			return value;
+		}
+
+		// The reserve account is reaped with the last reserved balance. It keeps
+		// its provider reference anyway.
+		let reserved_balance = Self::reserved_balance(currency_id, who);
+		let (actual, keep_alive) = Self::reserve_account_outflow(currency_id, value.min(reserved_balance));
+		let unreserved = match Assets::transfer(currency_id, &Self::reserve_account(), who, actual, keep_alive) {
+			Ok(unreserved) => unreserved,
+			Err(_) => return value,
+		};
+		Self::set_reserved_balance(currency_id, who, reserved_balance.saturating_sub(unreserved));
+		value.saturating_sub(unreserved)
+	}
+
+	fn repatriate_reserved(
+		currency_id: Self::CurrencyId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError> {
+		if value.is_zero() {
+			return Ok(value);
+		}
+
+		if slashed == beneficiary {
+			return match status {
+				BalanceStatus::Free => Ok(Self::unreserve(currency_id, slashed, value)),
+				BalanceStatus::Reserved => Ok(value.saturating_sub(Self::reserved_balance(currency_id, slashed))),
+			};
+		}
+
+		let from_reserved_balance = Self::reserved_balance(currency_id, slashed);
+		let actual = value.min(from_reserved_balance);
+		let actual = match status {
+			BalanceStatus::Free => {
+				let (actual, keep_alive) = Self::reserve_account_outflow(currency_id, actual);
+				Assets::transfer(currency_id, &Self::reserve_account(), beneficiary, actual, keep_alive)?
+			}
+			BalanceStatus::Reserved => {
+				Self::set_reserved_balance(
+					currency_id,
+					beneficiary,
+					Self::reserved_balance(currency_id, beneficiary).saturating_add(actual),
+				);
+				actual
+			}
+		};
+		Self::set_reserved_balance(currency_id, slashed, from_reserved_balance.saturating_sub(actual));
+		Ok(value.saturating_sub(actual))
+	}
+}
+
+impl<T, Assets, CurrencyIds> MultiReducibleCurrency<T::AccountId> for FungiblesAdapter<T, Assets, CurrencyIds>
+where
+	T: Config,
+	CurrencyIds: Get<BoundedVec<CurrencyIdOf<T>, <T as Config>::MaxFungiblesCurrencies>>,
+	Assets: Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>
+		+ Mutate<T::AccountId>
+		+ Transfer<T::AccountId>,
+{
+	/// The currencies of `CurrencyIds` which `who` has a free or reserved
+	/// balance of.
+	fn held_currencies(who: &T::AccountId, limit: u32) -> Vec<Self::CurrencyId> {
+		CurrencyIds::get()
+			.into_inner()
+			.into_iter()
+			.filter(|currency_id| !Self::total_balance(*currency_id, who).is_zero())
+			.take(limit as usize)
+			.collect()
+	}
+
+	fn reducible_balance(currency_id: Self::CurrencyId, who: &T::AccountId, keep_alive: bool) -> Self::Balance {
+		let liquid = Self::free_balance(currency_id, who).saturating_sub(Self::frozen_balance(currency_id, who));
+		Assets::reducible_balance(currency_id, who, keep_alive).min(liquid)
+	}
+}
+
+impl<T, Assets, CurrencyIds> Inspect<T::AccountId> for FungiblesAdapter<T, Assets, CurrencyIds>
+where
+	T: Config,
+	CurrencyIds: Get<BoundedVec<CurrencyIdOf<T>, <T as Config>::MaxFungiblesCurrencies>>,
+	CurrencyIdOf<T>: AssetId,
+	Assets: Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>
+		+ Mutate<T::AccountId>
+		+ Transfer<T::AccountId>,
+{
+	type AssetId = CurrencyIdOf<T>;
+	type Balance = BalanceOf<T>;
+
+	fn total_issuance(asset_id: Self::AssetId) -> Self::Balance {
+		Assets::total_issuance(asset_id)
+	}
+
+	fn minimum_balance(asset_id: Self::AssetId) -> Self::Balance {
+		Assets::minimum_balance(asset_id)
+	}
+
+	fn balance(asset_id: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		Assets::balance(asset_id, who)
+	}
+
+	fn reducible_balance(asset_id: Self::AssetId, who: &T::AccountId, keep_alive: bool) -> Self::Balance {
+		<Self as MultiReducibleCurrency<_>>::reducible_balance(asset_id, who, keep_alive)
+	}
+
+	fn can_deposit(asset_id: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DepositConsequence {
+		Assets::can_deposit(asset_id, who, amount)
+	}
+
+	fn can_withdraw(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		let free = Self::free_balance(asset_id, who);
+		if amount <= free && free - amount < Self::frozen_balance(asset_id, who) {
+			return WithdrawConsequence::Frozen;
+		}
+		Assets::can_withdraw(asset_id, who, amount)
+	}
+}
+
+/// The locks are respected when decreasing the balances.
+impl<T, Assets, CurrencyIds> Unbalanced<T::AccountId> for FungiblesAdapter<T, Assets, CurrencyIds>
+where
+	T: Config,
+	CurrencyIds: Get<BoundedVec<CurrencyIdOf<T>, <T as Config>::MaxFungiblesCurrencies>>,
+	CurrencyIdOf<T>: AssetId,
+	Assets: Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>
+		+ Mutate<T::AccountId>
+		+ Transfer<T::AccountId>
+		+ Unbalanced<T::AccountId>,
+{
+	fn set_balance(asset_id: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		<Assets as Unbalanced<_>>::set_balance(asset_id, who, amount)
+	}
+
+	fn set_total_issuance(asset_id: Self::AssetId, amount: Self::Balance) {
+		<Assets as Unbalanced<_>>::set_total_issuance(asset_id, amount)
+	}
+
+	fn decrease_balance(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> result::Result<Self::Balance, DispatchError> {
+		Self::ensure_can_withdraw(asset_id, who, amount)?;
+		<Assets as Unbalanced<_>>::decrease_balance(asset_id, who, amount)
+	}
+
+	fn decrease_balance_at_most(asset_id: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
+		let liquid = Self::free_balance(asset_id, who).saturating_sub(Self::frozen_balance(asset_id, who));
+		<Assets as Unbalanced<_>>::decrease_balance_at_most(asset_id, who, amount.min(liquid))
+	}
+
+	fn increase_balance(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> result::Result<Self::Balance, DispatchError> {
+		<Assets as Unbalanced<_>>::increase_balance(asset_id, who, amount)
+	}
+
+	fn increase_balance_at_most(asset_id: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
+		<Assets as Unbalanced<_>>::increase_balance_at_most(asset_id, who, amount)
+	}
+}
@@ -0,0 +1,209 @@
+use crate::{BalanceOf, Config, CurrencyIdOf};
+use frame_support::traits::{
+	tokens::fungibles::{Balanced, CreditOf},
+	Get, Imbalance, OnUnbalanced, SameOrOther, TryDrop,
+};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	Perbill,
+};
+use sp_std::{marker::PhantomData, mem, result};
+
+/// Opaque, move-only struct with private fields that serves as a token
+/// denoting that funds have been created without any equal and opposite
+/// accounting.
+///
+/// The balances of the currencies module are always in line with the total
+/// issuance, this is only an accounting token and dropping it doesn't change
+/// the total issuance.
+#[must_use]
+pub struct PositiveImbalance<T: Config, GetCurrencyId: Get<CurrencyIdOf<T>>>(BalanceOf<T>, PhantomData<GetCurrencyId>);
+
+impl<T: Config, GetCurrencyId: Get<CurrencyIdOf<T>>> PositiveImbalance<T, GetCurrencyId> {
+	/// Create a new positive imbalance from a balance.
+	pub fn new(amount: BalanceOf<T>) -> Self {
+		PositiveImbalance(amount, PhantomData::<GetCurrencyId>)
+	}
+}
+
+impl<T: Config, GetCurrencyId: Get<CurrencyIdOf<T>>> Default for PositiveImbalance<T, GetCurrencyId> {
+	fn default() -> Self {
+		Self::zero()
+	}
+}
+
+/// Opaque, move-only struct with private fields that serves as a token
+/// denoting that funds have been destroyed without any equal and opposite
+/// accounting.
+///
+/// The balances of the currencies module are always in line with the total
+/// issuance, this is only an accounting token and dropping it doesn't change
+/// the total issuance.
+#[must_use]
+pub struct NegativeImbalance<T: Config, GetCurrencyId: Get<CurrencyIdOf<T>>>(BalanceOf<T>, PhantomData<GetCurrencyId>);
+
+impl<T: Config, GetCurrencyId: Get<CurrencyIdOf<T>>> NegativeImbalance<T, GetCurrencyId> {
+	/// Create a new negative imbalance from a balance.
+	pub fn new(amount: BalanceOf<T>) -> Self {
+		NegativeImbalance(amount, PhantomData::<GetCurrencyId>)
+	}
+}
+
+impl<T: Config, GetCurrencyId: Get<CurrencyIdOf<T>>> Default for NegativeImbalance<T, GetCurrencyId> {
+	fn default() -> Self {
+		Self::zero()
+	}
+}
+
+impl<T: Config, GetCurrencyId: Get<CurrencyIdOf<T>>> TryDrop for PositiveImbalance<T, GetCurrencyId> {
+	fn try_drop(self) -> result::Result<(), Self> {
+		self.drop_zero()
+	}
+}
+
+impl<T: Config, GetCurrencyId: Get<CurrencyIdOf<T>>> Imbalance<BalanceOf<T>> for PositiveImbalance<T, GetCurrencyId> {
+	type Opposite = NegativeImbalance<T, GetCurrencyId>;
+
+	fn zero() -> Self {
+		Self::new(Zero::zero())
+	}
+	fn drop_zero(self) -> result::Result<(), Self> {
+		if self.0.is_zero() {
+			Ok(())
+		} else {
+			Err(self)
+		}
+	}
+	fn split(self, amount: BalanceOf<T>) -> (Self, Self) {
+		let first = self.0.min(amount);
+		let second = self.0 - first;
+
+		mem::forget(self);
+		(Self::new(first), Self::new(second))
+	}
+	fn merge(mut self, other: Self) -> Self {
+		self.0 = self.0.saturating_add(other.0);
+		mem::forget(other);
+
+		self
+	}
+	fn subsume(&mut self, other: Self) {
+		self.0 = self.0.saturating_add(other.0);
+		mem::forget(other);
+	}
+	// allow to make the impl same with `pallet-balances`
+	#[allow(clippy::comparison_chain)]
+	fn offset(self, other: Self::Opposite) -> SameOrOther<Self, Self::Opposite> {
+		let (a, b) = (self.0, other.0);
+		mem::forget((self, other));
+
+		if a > b {
+			SameOrOther::Same(Self::new(a - b))
+		} else if b > a {
+			SameOrOther::Other(NegativeImbalance::new(b - a))
+		} else {
+			SameOrOther::None
+		}
+	}
+	fn peek(&self) -> BalanceOf<T> {
+		self.0
+	}
+}
+
+impl<T: Config, GetCurrencyId: Get<CurrencyIdOf<T>>> TryDrop for NegativeImbalance<T, GetCurrencyId> {
+	fn try_drop(self) -> result::Result<(), Self> {
+		self.drop_zero()
+	}
+}
+
+impl<T: Config, GetCurrencyId: Get<CurrencyIdOf<T>>> Imbalance<BalanceOf<T>> for NegativeImbalance<T, GetCurrencyId> {
+	type Opposite = PositiveImbalance<T, GetCurrencyId>;
+
+	fn zero() -> Self {
+		Self::new(Zero::zero())
+	}
+	fn drop_zero(self) -> result::Result<(), Self> {
+		if self.0.is_zero() {
+			Ok(())
+		} else {
+			Err(self)
+		}
+	}
+	fn split(self, amount: BalanceOf<T>) -> (Self, Self) {
+		let first = self.0.min(amount);
+		let second = self.0 - first;
+
+		mem::forget(self);
+		(Self::new(first), Self::new(second))
+	}
+	fn merge(mut self, other: Self) -> Self {
+		self.0 = self.0.saturating_add(other.0);
+		mem::forget(other);
+
+		self
+	}
+	fn subsume(&mut self, other: Self) {
+		self.0 = self.0.saturating_add(other.0);
+		mem::forget(other);
+	}
+	// allow to make the impl same with `pallet-balances`
+	#[allow(clippy::comparison_chain)]
+	fn offset(self, other: Self::Opposite) -> SameOrOther<Self, Self::Opposite> {
+		let (a, b) = (self.0, other.0);
+		mem::forget((self, other));
+
+		if a > b {
+			SameOrOther::Same(Self::new(a - b))
+		} else if b > a {
+			SameOrOther::Other(PositiveImbalance::new(b - a))
+		} else {
+			SameOrOther::None
+		}
+	}
+	fn peek(&self) -> BalanceOf<T> {
+		self.0
+	}
+}
+
+/// Split a multi-currency credit by the ratio of `Part1` to `Part2`, and pass
+/// the parts to `Target1` and `Target2`.
+///
+/// Like `frame_support::traits::SplitTwoWays`, but for the credits of
+/// `fungibles::Balanced`, e.g. of `orml_tokens` or the currencies module.
+pub struct SplitTwoWays<AccountId, F, Part1, Target1, Part2, Target2>(
+	PhantomData<(AccountId, F, Part1, Target1, Part2, Target2)>,
+);
+
+impl<AccountId, F, Part1, Target1, Part2, Target2> OnUnbalanced<CreditOf<AccountId, F>>
+	for SplitTwoWays<AccountId, F, Part1, Target1, Part2, Target2>
+where
+	F: Balanced<AccountId>,
+	Part1: Get<u32>,
+	Target1: OnUnbalanced<CreditOf<AccountId, F>>,
+	Part2: Get<u32>,
+	Target2: OnUnbalanced<CreditOf<AccountId, F>>,
+{
+	fn on_nonzero_unbalanced(amount: CreditOf<AccountId, F>) {
+		let total = Part1::get().saturating_add(Part2::get());
+		let amount1 = Perbill::from_rational(Part1::get(), total) * amount.peek();
+		let (imbalance1, imbalance2) = amount.split(amount1);
+		Target1::on_unbalanced(imbalance1);
+		Target2::on_unbalanced(imbalance2);
+	}
+}
+
+/// Resolve a multi-currency credit into the account of `Who`.
+///
+/// The credit is dropped, and so burned, if it can't be resolved, e.g. it's
+/// below the existential deposit of a new account.
+pub struct ResolveTo<AccountId, F, Who>(PhantomData<(AccountId, F, Who)>);
+
+impl<AccountId, F, Who> OnUnbalanced<CreditOf<AccountId, F>> for ResolveTo<AccountId, F, Who>
+where
+	F: Balanced<AccountId>,
+	Who: Get<AccountId>,
+{
+	fn on_nonzero_unbalanced(amount: CreditOf<AccountId, F>) {
+		let _ = F::resolve(&Who::get(), amount);
+	}
+}
@@ -9,6 +9,30 @@
 //! It also provides an adapter, to adapt `frame_support::traits::Currency`
 //! implementations into `BasicCurrencyExtended`.
 //!
+//! Non-native currencies are routed by `Config::CurrencyBackends` to
+//! `Config::MultiCurrency`, usually `orml_tokens`,
+//! `Config::FungiblesCurrency`, usually `FungiblesAdapter` of `pallet_assets`,
+//! or an ERC-20 contract called through `Config::Erc20Adapter`.
+//! `FungiblesAdapter` adapts `fungibles` implementations into
+//! `MultiCurrencyExtended`, `MultiLockableCurrency` and
+//! `MultiReservableCurrency`, keeping the locks and reserves in this module.
+//! For the locks to be respected by `pallet_assets` itself, the runtime must
+//! set `FungiblesFreezer` as `pallet_assets::Config::Freezer`, which requires
+//! the `assets-freezer` feature.
+//! ERC-20 currencies, e.g. `CurrencyId::Erc20(address)` of a runtime, are
+//! routed by `CurrencyBackend::Erc20(address)`, and don't support locks and
+//! reserves.
+//!
+//! If all the backends implement `fungible::Unbalanced` or
+//! `fungibles::Unbalanced`, the currencies module implements
+//! `fungibles::Unbalanced`, and so `fungibles::Balanced`. The multi-currency
+//! credits, `CreditOf`, could be passed to `OnUnbalanced` handlers like
+//! `SplitTwoWays` and `ResolveTo`.
+//!
+//! `CurrencyAdapter` adapts any currency of this module into
+//! `frame_support::traits::{Currency, ReservableCurrency, LockableCurrency}`,
+//! to be used by frame pallets like `pallet_treasury`.
+//!
 //! The currencies module provides functionality of both `MultiCurrencyExtended`
 //! and `BasicCurrencyExtended`, via unified interfaces, and all calls would be
 //! delegated to the underlying multi-currency and base currency system.
@@ -35,6 +59,17 @@
 //! `Config::NativeCurrency`.
 //! - `update_balance` - Update balance by signed integer amount, in a given
 //!   currency, root origin required.
+//! - `transfer_all_currencies` - Transfer all transferable balances of native
+//!   and non-native currencies to another account.
+//!
+//! ### Transaction Fees
+//!
+//! `MultiCurrencyFeeAdapter` implements
+//! `pallet_transaction_payment::OnChargeTransaction`, to pay transaction fees
+//! in any currency. The fee currency is named by the `SetFeeCurrency` signed
+//! extension, which wraps and replaces `ChargeTransactionPayment`. Fees in
+//! non-native currencies are converted from the native fee by a
+//! `PriceProvider`. Both require the `transaction-payment` feature.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -43,35 +78,66 @@ use codec::Codec;
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
-		Currency as PalletCurrency, ExistenceRequirement, Get, LockableCurrency as PalletLockableCurrency,
-		ReservableCurrency as PalletReservableCurrency, WithdrawReasons,
+		tokens::{fungible, fungibles, AssetId, DepositConsequence, WithdrawConsequence},
+		Currency as PalletCurrency, ExistenceRequirement, Get, Imbalance, LockableCurrency as PalletLockableCurrency,
+		ReservableCurrency as PalletReservableCurrency, SignedImbalance, WithdrawReasons,
 	},
+	transactional, BoundedVec, PalletId,
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
 use orml_traits::{
 	arithmetic::{Signed, SimpleArithmetic},
 	currency::TransferAll,
-	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
-	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
+	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReducibleCurrency,
+	BasicReservableCurrency, Erc20Adapter, GetByKey, LockIdentifier, MultiCurrency, MultiCurrencyExtended,
+	MultiLockableCurrency, MultiReducibleCurrency, MultiReservableCurrency,
 };
 use orml_utilities::with_transaction_result;
+use sp_core::H160;
 use sp_runtime::{
-	traits::{CheckedSub, MaybeSerializeDeserialize, StaticLookup, Zero},
+	traits::{CheckedSub, MaybeSerializeDeserialize, Saturating, StaticLookup, Zero},
 	DispatchError, DispatchResult,
 };
 use sp_std::{
 	convert::{TryFrom, TryInto},
 	fmt::Debug,
-	marker, result,
+	marker,
+	prelude::*,
+	result,
 };
 
+mod benchmarking;
+mod erc20;
+#[cfg(any(feature = "transaction-payment", test))]
+mod fee;
+mod fungibles_adapter;
+mod imbalances;
 mod mock;
 mod tests;
 mod weights;
 
+use erc20::Erc20Currency;
+#[cfg(any(feature = "transaction-payment", test))]
+pub use fee::{MultiCurrencyFeeAdapter, SetFeeCurrency};
+pub use fungibles_adapter::FungiblesAdapter;
+#[cfg(any(feature = "assets-freezer", test))]
+pub use fungibles_adapter::FungiblesFreezer;
+pub use imbalances::{NegativeImbalance, PositiveImbalance, ResolveTo, SplitTwoWays};
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The backend of a non-native currency.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum CurrencyBackend {
+	/// Routed to `Config::MultiCurrency`, usually `orml_tokens`.
+	MultiCurrency,
+	/// Routed to `Config::FungiblesCurrency`, usually `FungiblesAdapter`.
+	Fungibles,
+	/// Routed to the ERC-20 contract at the address through
+	/// `Config::Erc20Adapter`.
+	Erc20(H160),
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -90,15 +156,55 @@ pub mod module {
 		type MultiCurrency: TransferAll<Self::AccountId>
 			+ MultiCurrencyExtended<Self::AccountId>
 			+ MultiLockableCurrency<Self::AccountId>
-			+ MultiReservableCurrency<Self::AccountId>;
+			+ MultiReservableCurrency<Self::AccountId>
+			+ MultiReducibleCurrency<Self::AccountId>;
 
 		type NativeCurrency: BasicCurrencyExtended<Self::AccountId, Balance = BalanceOf<Self>, Amount = AmountOf<Self>>
 			+ BasicLockableCurrency<Self::AccountId, Balance = BalanceOf<Self>>
-			+ BasicReservableCurrency<Self::AccountId, Balance = BalanceOf<Self>>;
+			+ BasicReservableCurrency<Self::AccountId, Balance = BalanceOf<Self>>
+			+ BasicReducibleCurrency<Self::AccountId, Balance = BalanceOf<Self>>;
+
+		/// The `fungibles` backed currencies, e.g. `FungiblesAdapter` of
+		/// `pallet_assets`.
+		type FungiblesCurrency: MultiCurrencyExtended<
+				Self::AccountId,
+				CurrencyId = CurrencyIdOf<Self>,
+				Balance = BalanceOf<Self>,
+				Amount = AmountOf<Self>,
+			> + MultiLockableCurrency<Self::AccountId, CurrencyId = CurrencyIdOf<Self>, Balance = BalanceOf<Self>>
+			+ MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyIdOf<Self>, Balance = BalanceOf<Self>>
+			+ MultiReducibleCurrency<Self::AccountId, CurrencyId = CurrencyIdOf<Self>, Balance = BalanceOf<Self>>;
 
 		#[pallet::constant]
 		type GetNativeCurrencyId: Get<CurrencyIdOf<Self>>;
 
+		/// The backend of each non-native currency.
+		type CurrencyBackends: GetByKey<CurrencyIdOf<Self>, CurrencyBackend>;
+
+		/// The executor of the ERC-20 contract calls, e.g. through an EVM
+		/// pallet.
+		type Erc20Adapter: Erc20Adapter<Self::AccountId, BalanceOf<Self>>;
+
+		/// The account holding the reserved balances of `FungiblesAdapter`
+		/// currencies.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The maximum number of locks of `FungiblesAdapter` currencies per
+		/// account, also the `MaxLocks` of `CurrencyAdapter`.
+		#[pallet::constant]
+		type MaxLocks: Get<u32>;
+
+		/// The maximum number of non-native currencies an account could hold
+		/// for `transfer_all_currencies`.
+		#[pallet::constant]
+		type MaxHeldCurrencies: Get<u32>;
+
+		/// The maximum number of `CurrencyIds` of `FungiblesAdapter`, the
+		/// balances of each of which are read by `transfer_all_currencies`.
+		#[pallet::constant]
+		type MaxFungiblesCurrencies: Get<u32>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -109,6 +215,26 @@ pub mod module {
 		AmountIntoBalanceFailed,
 		/// Balance is too low.
 		BalanceTooLow,
+		/// The account holds more than `T::MaxHeldCurrencies` currencies.
+		TooManyCurrencies,
+		/// Failed because liquidity restrictions due to locking.
+		LiquidityRestrictions,
+		/// Failed because the maximum locks was exceeded.
+		MaxLocksExceeded,
+		/// No ERC-20 contract is deployed at the address.
+		Erc20ContractNotFound,
+		/// The ERC-20 contract call reverted.
+		Erc20Reverted,
+		/// The ERC-20 contract call ran out of gas.
+		Erc20OutOfGas,
+		/// The output of the ERC-20 contract call is invalid.
+		Erc20InvalidOutput,
+		/// Locks and reserves are not supported by ERC-20 currencies.
+		Erc20Unsupported,
+		/// Transfer/payment would kill account.
+		KeepAlive,
+		/// Beneficiary account must pre-exist.
+		DeadAccount,
 	}
 
 	#[pallet::event]
@@ -123,7 +249,39 @@ pub mod module {
 		Deposited(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
 		/// Withdraw success. \[currency_id, who, amount\]
 		Withdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
-	}
+		/// All transferable balances transferred, except the skipped
+		/// currencies. \[from, to, skipped_currency_ids\]
+		AllCurrenciesTransferred(T::AccountId, T::AccountId, Vec<CurrencyIdOf<T>>),
+	}
+
+	/// The currency to pay the fees of the current extrinsic in, set by
+	/// `SetFeeCurrency` only while `ChargeTransactionPayment` runs, and read by
+	/// `MultiCurrencyFeeAdapter`.
+	#[pallet::storage]
+	pub(crate) type FeeCurrency<T: Config> = StorageValue<_, CurrencyIdOf<T>, OptionQuery>;
+
+	/// The reserved balances of `FungiblesAdapter` currencies.
+	///
+	/// double_map: currency_id, who => reserved_balance
+	#[pallet::storage]
+	#[pallet::getter(fn fungibles_reserves)]
+	pub type FungiblesReserves<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The locks of `FungiblesAdapter` currencies.
+	///
+	/// double_map: currency_id, who => Vec<(lock_id, amount)>
+	#[pallet::storage]
+	#[pallet::getter(fn fungibles_locks)]
+	pub type FungiblesLocks<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(LockIdentifier, BalanceOf<T>), T::MaxLocks>,
+		ValueQuery,
+	>;
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
@@ -183,7 +341,99 @@ pub mod module {
 			<Self as MultiCurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
 			Ok(())
 		}
+
+		/// Transfer all transferable balances of native and non-native
+		/// currencies to another account. The locked and reserved balances
+		/// are kept.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `dest`: The recipient of the transfer.
+		/// - `keep_alive`: Whether to keep the existential deposit of each
+		///   currency, to keep the accounts of the sender alive.
+		///
+		/// The currencies with nothing to transfer, or failed to transfer,
+		/// e.g. below the existential deposit of `dest`, are skipped and
+		/// reported in the event.
+		#[pallet::weight(Pallet::<T>::transfer_all_currencies_weight(T::MaxHeldCurrencies::get()))]
+		#[transactional]
+		pub fn transfer_all_currencies(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			keep_alive: bool,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+
+			// read one more than the max, to tell if there are too many
+			let currency_ids = Self::held_non_native_currencies(&from, T::MaxHeldCurrencies::get().saturating_add(1));
+			let held = currency_ids.len() as u32;
+			ensure!(held <= T::MaxHeldCurrencies::get(), Error::<T>::TooManyCurrencies);
+
+			let mut skipped = Vec::new();
+			// transfer native currency last, as other currencies may depend on it
+			for currency_id in currency_ids
+				.into_iter()
+				.chain(sp_std::iter::once(T::GetNativeCurrencyId::get()))
+			{
+				let amount =
+					<Self as MultiReducibleCurrency<T::AccountId>>::reducible_balance(currency_id, &from, keep_alive);
+				if amount.is_zero()
+					|| with_transaction_result(|| {
+						<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, &from, &to, amount)
+					})
+					.is_err()
+				{
+					skipped.push(currency_id);
+				}
+			}
+
+			Self::deposit_event(Event::AllCurrenciesTransferred(from, to, skipped));
+			Ok(Some(Self::transfer_all_currencies_weight(held)).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The weight of `transfer_all_currencies` of `held` non-native
+	/// currencies. The balance and the reserve of every `CurrencyIds` of
+	/// `FungiblesAdapter` are read to find the held ones, whatever `held` is.
+	fn transfer_all_currencies_weight(held: u32) -> Weight {
+		T::WeightInfo::transfer_all_currencies(held)
+			.saturating_add(T::DbWeight::get().reads((T::MaxFungiblesCurrencies::get() as Weight).saturating_mul(2)))
 	}
+
+	fn held_non_native_currencies(who: &T::AccountId, limit: u32) -> Vec<CurrencyIdOf<T>> {
+		let mut currency_ids = T::MultiCurrency::held_currencies(who, limit);
+		let remaining = limit.saturating_sub(currency_ids.len() as u32);
+		if !remaining.is_zero() {
+			currency_ids.extend(T::FungiblesCurrency::held_currencies(who, remaining));
+		}
+		currency_ids
+	}
+}
+
+/// Route a call of `$currency_id` to `T::NativeCurrency`, or to the backend
+/// of `T::CurrencyBackends`. The `MultiCurrency` and `Fungibles` backends are
+/// both called by `$method` of `$backend_trait` with the same arguments.
+macro_rules! dispatch {
+	(
+		$currency_id:expr,
+		$backend_trait:path => $method:ident($($arg:expr),* $(,)?),
+		native => $native:expr,
+		erc20($contract:pat) => $erc20:expr $(,)?
+	) => {
+		if $currency_id == T::GetNativeCurrencyId::get() {
+			$native
+		} else {
+			match T::CurrencyBackends::get(&$currency_id) {
+				CurrencyBackend::MultiCurrency => <T::MultiCurrency as $backend_trait>::$method($($arg),*),
+				CurrencyBackend::Fungibles => <T::FungiblesCurrency as $backend_trait>::$method($($arg),*),
+				CurrencyBackend::Erc20($contract) => $erc20,
+			}
+		}
+	};
 }
 
 impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
@@ -191,43 +441,48 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 	type Balance = BalanceOf<T>;
 
 	fn minimum_balance(currency_id: Self::CurrencyId) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::minimum_balance()
-		} else {
-			T::MultiCurrency::minimum_balance(currency_id)
-		}
+		dispatch!(
+			currency_id,
+			MultiCurrency<T::AccountId> => minimum_balance(currency_id),
+			native => T::NativeCurrency::minimum_balance(),
+			erc20(_) => Zero::zero(),
+		)
 	}
 
 	fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::total_issuance()
-		} else {
-			T::MultiCurrency::total_issuance(currency_id)
-		}
+		dispatch!(
+			currency_id,
+			MultiCurrency<T::AccountId> => total_issuance(currency_id),
+			native => T::NativeCurrency::total_issuance(),
+			erc20(contract) => Erc20Currency::<T>::total_issuance(contract),
+		)
 	}
 
 	fn total_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::total_balance(who)
-		} else {
-			T::MultiCurrency::total_balance(currency_id, who)
-		}
+		dispatch!(
+			currency_id,
+			MultiCurrency<T::AccountId> => total_balance(currency_id, who),
+			native => T::NativeCurrency::total_balance(who),
+			erc20(contract) => Erc20Currency::<T>::free_balance(contract, who),
+		)
 	}
 
 	fn free_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::free_balance(who)
-		} else {
-			T::MultiCurrency::free_balance(currency_id, who)
-		}
+		dispatch!(
+			currency_id,
+			MultiCurrency<T::AccountId> => free_balance(currency_id, who),
+			native => T::NativeCurrency::free_balance(who),
+			erc20(contract) => Erc20Currency::<T>::free_balance(contract, who),
+		)
 	}
 
 	fn ensure_can_withdraw(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::ensure_can_withdraw(who, amount)
-		} else {
-			T::MultiCurrency::ensure_can_withdraw(currency_id, who, amount)
-		}
+		dispatch!(
+			currency_id,
+			MultiCurrency<T::AccountId> => ensure_can_withdraw(currency_id, who, amount),
+			native => T::NativeCurrency::ensure_can_withdraw(who, amount),
+			erc20(contract) => Erc20Currency::<T>::ensure_can_withdraw(contract, who, amount),
+		)
 	}
 
 	fn transfer(
@@ -239,11 +494,12 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() || from == to {
 			return Ok(());
 		}
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::transfer(from, to, amount)?;
-		} else {
-			T::MultiCurrency::transfer(currency_id, from, to, amount)?;
-		}
+		dispatch!(
+			currency_id,
+			MultiCurrency<T::AccountId> => transfer(currency_id, from, to, amount),
+			native => T::NativeCurrency::transfer(from, to, amount),
+			erc20(contract) => Erc20Currency::<T>::transfer(contract, from, to, amount),
+		)?;
 		Self::deposit_event(Event::Transferred(currency_id, from.clone(), to.clone(), amount));
 		Ok(())
 	}
@@ -252,11 +508,12 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() {
 			return Ok(());
 		}
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::deposit(who, amount)?;
-		} else {
-			T::MultiCurrency::deposit(currency_id, who, amount)?;
-		}
+		dispatch!(
+			currency_id,
+			MultiCurrency<T::AccountId> => deposit(currency_id, who, amount),
+			native => T::NativeCurrency::deposit(who, amount),
+			erc20(contract) => Erc20Currency::<T>::deposit(contract, who, amount),
+		)?;
 		Self::deposit_event(Event::Deposited(currency_id, who.clone(), amount));
 		Ok(())
 	}
@@ -265,29 +522,32 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() {
 			return Ok(());
 		}
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::withdraw(who, amount)?;
-		} else {
-			T::MultiCurrency::withdraw(currency_id, who, amount)?;
-		}
+		dispatch!(
+			currency_id,
+			MultiCurrency<T::AccountId> => withdraw(currency_id, who, amount),
+			native => T::NativeCurrency::withdraw(who, amount),
+			erc20(contract) => Erc20Currency::<T>::withdraw(contract, who, amount),
+		)?;
 		Self::deposit_event(Event::Withdrawn(currency_id, who.clone(), amount));
 		Ok(())
 	}
 
 	fn can_slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> bool {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::can_slash(who, amount)
-		} else {
-			T::MultiCurrency::can_slash(currency_id, who, amount)
-		}
+		dispatch!(
+			currency_id,
+			MultiCurrency<T::AccountId> => can_slash(currency_id, who, amount),
+			native => T::NativeCurrency::can_slash(who, amount),
+			erc20(contract) => Erc20Currency::<T>::free_balance(contract, who) >= amount,
+		)
 	}
 
 	fn slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::slash(who, amount)
-		} else {
-			T::MultiCurrency::slash(currency_id, who, amount)
-		}
+		dispatch!(
+			currency_id,
+			MultiCurrency<T::AccountId> => slash(currency_id, who, amount),
+			native => T::NativeCurrency::slash(who, amount),
+			erc20(contract) => Erc20Currency::<T>::slash(contract, who, amount),
+		)
 	}
 }
 
@@ -295,16 +555,36 @@ impl<T: Config> MultiCurrencyExtended<T::AccountId> for Pallet<T> {
 	type Amount = AmountOf<T>;
 
 	fn update_balance(currency_id: Self::CurrencyId, who: &T::AccountId, by_amount: Self::Amount) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::update_balance(who, by_amount)?;
-		} else {
-			T::MultiCurrency::update_balance(currency_id, who, by_amount)?;
-		}
+		dispatch!(
+			currency_id,
+			MultiCurrencyExtended<T::AccountId> => update_balance(currency_id, who, by_amount),
+			native => T::NativeCurrency::update_balance(who, by_amount),
+			erc20(contract) => Erc20Currency::<T>::update_balance(contract, who, by_amount),
+		)?;
 		Self::deposit_event(Event::BalanceUpdated(currency_id, who.clone(), by_amount));
 		Ok(())
 	}
 }
 
+impl<T: Config> MultiReducibleCurrency<T::AccountId> for Pallet<T> {
+	fn held_currencies(who: &T::AccountId, limit: u32) -> Vec<Self::CurrencyId> {
+		let mut currency_ids = Self::held_non_native_currencies(who, limit);
+		if (currency_ids.len() as u32) < limit && !T::NativeCurrency::total_balance(who).is_zero() {
+			currency_ids.push(T::GetNativeCurrencyId::get());
+		}
+		currency_ids
+	}
+
+	fn reducible_balance(currency_id: Self::CurrencyId, who: &T::AccountId, keep_alive: bool) -> Self::Balance {
+		dispatch!(
+			currency_id,
+			MultiReducibleCurrency<T::AccountId> => reducible_balance(currency_id, who, keep_alive),
+			native => T::NativeCurrency::reducible_balance(who, keep_alive),
+			erc20(contract) => Erc20Currency::<T>::free_balance(contract, who),
+		)
+	}
+}
+
 impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 	type Moment = T::BlockNumber;
 
@@ -314,11 +594,12 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::set_lock(lock_id, who, amount)
-		} else {
-			T::MultiCurrency::set_lock(lock_id, currency_id, who, amount)
-		}
+		dispatch!(
+			currency_id,
+			MultiLockableCurrency<T::AccountId> => set_lock(lock_id, currency_id, who, amount),
+			native => T::NativeCurrency::set_lock(lock_id, who, amount),
+			erc20(_) => Err(Error::<T>::Erc20Unsupported.into()),
+		)
 	}
 
 	fn extend_lock(
@@ -327,61 +608,131 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::extend_lock(lock_id, who, amount)
-		} else {
-			T::MultiCurrency::extend_lock(lock_id, currency_id, who, amount)
-		}
+		dispatch!(
+			currency_id,
+			MultiLockableCurrency<T::AccountId> => extend_lock(lock_id, currency_id, who, amount),
+			native => T::NativeCurrency::extend_lock(lock_id, who, amount),
+			erc20(_) => Err(Error::<T>::Erc20Unsupported.into()),
+		)
+	}
+
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		dispatch!(
+			currency_id,
+			MultiLockableCurrency<T::AccountId> => set_lock_with_reasons(lock_id, currency_id, who, amount, reasons),
+			native => T::NativeCurrency::set_lock_with_reasons(lock_id, who, amount, reasons),
+			erc20(_) => Err(Error::<T>::Erc20Unsupported.into()),
+		)
+	}
+
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		dispatch!(
+			currency_id,
+			MultiLockableCurrency<T::AccountId> => extend_lock_with_reasons(lock_id, currency_id, who, amount, reasons),
+			native => T::NativeCurrency::extend_lock_with_reasons(lock_id, who, amount, reasons),
+			erc20(_) => Err(Error::<T>::Erc20Unsupported.into()),
+		)
 	}
 
 	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::remove_lock(lock_id, who)
-		} else {
-			T::MultiCurrency::remove_lock(lock_id, currency_id, who)
+		dispatch!(
+			currency_id,
+			MultiLockableCurrency<T::AccountId> => remove_lock(lock_id, currency_id, who),
+			native => T::NativeCurrency::remove_lock(lock_id, who),
+			erc20(_) => Err(Error::<T>::Erc20Unsupported.into()),
+		)
+	}
+
+	fn ensure_can_withdraw_with_reasons(
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		dispatch!(
+			currency_id,
+			MultiLockableCurrency<T::AccountId> => ensure_can_withdraw_with_reasons(currency_id, who, amount, reasons),
+			native => T::NativeCurrency::ensure_can_withdraw_with_reasons(who, amount, reasons),
+			erc20(contract) => Erc20Currency::<T>::ensure_can_withdraw(contract, who, amount),
+		)
+	}
+
+	fn withdraw_with_reasons(
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
 		}
+		dispatch!(
+			currency_id,
+			MultiLockableCurrency<T::AccountId> => withdraw_with_reasons(currency_id, who, amount, reasons),
+			native => T::NativeCurrency::withdraw_with_reasons(who, amount, reasons),
+			erc20(contract) => Erc20Currency::<T>::withdraw(contract, who, amount),
+		)?;
+		Self::deposit_event(Event::Withdrawn(currency_id, who.clone(), amount));
+		Ok(())
 	}
 }
 
 impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 	fn can_reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> bool {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::can_reserve(who, value)
-		} else {
-			T::MultiCurrency::can_reserve(currency_id, who, value)
-		}
+		dispatch!(
+			currency_id,
+			MultiReservableCurrency<T::AccountId> => can_reserve(currency_id, who, value),
+			native => T::NativeCurrency::can_reserve(who, value),
+			erc20(_) => value.is_zero(),
+		)
 	}
 
 	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::slash_reserved(who, value)
-		} else {
-			T::MultiCurrency::slash_reserved(currency_id, who, value)
-		}
+		dispatch!(
+			currency_id,
+			MultiReservableCurrency<T::AccountId> => slash_reserved(currency_id, who, value),
+			native => T::NativeCurrency::slash_reserved(who, value),
+			erc20(_) => value,
+		)
 	}
 
 	fn reserved_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::reserved_balance(who)
-		} else {
-			T::MultiCurrency::reserved_balance(currency_id, who)
-		}
+		dispatch!(
+			currency_id,
+			MultiReservableCurrency<T::AccountId> => reserved_balance(currency_id, who),
+			native => T::NativeCurrency::reserved_balance(who),
+			erc20(_) => Zero::zero(),
+		)
 	}
 
 	fn reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::reserve(who, value)
-		} else {
-			T::MultiCurrency::reserve(currency_id, who, value)
-		}
+		dispatch!(
+			currency_id,
+			MultiReservableCurrency<T::AccountId> => reserve(currency_id, who, value),
+			native => T::NativeCurrency::reserve(who, value),
+			erc20(_) => Err(Error::<T>::Erc20Unsupported.into()),
+		)
 	}
 
 	fn unreserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::unreserve(who, value)
-		} else {
-			T::MultiCurrency::unreserve(currency_id, who, value)
-		}
+		dispatch!(
+			currency_id,
+			MultiReservableCurrency<T::AccountId> => unreserve(currency_id, who, value),
+			native => T::NativeCurrency::unreserve(who, value),
+			erc20(_) => value,
+		)
 	}
 
 	fn repatriate_reserved(
@@ -391,11 +742,149 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		value: Self::Balance,
 		status: BalanceStatus,
 	) -> result::Result<Self::Balance, DispatchError> {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::repatriate_reserved(slashed, beneficiary, value, status)
-		} else {
-			T::MultiCurrency::repatriate_reserved(currency_id, slashed, beneficiary, value, status)
-		}
+		dispatch!(
+			currency_id,
+			MultiReservableCurrency<T::AccountId> => repatriate_reserved(currency_id, slashed, beneficiary, value, status),
+			native => T::NativeCurrency::repatriate_reserved(slashed, beneficiary, value, status),
+			erc20(_) => Ok(value),
+		)
+	}
+}
+
+/// The multi-currency credit, e.g. withdrawn by `fungibles::Balanced`, which
+/// decreases the total issuance if dropped.
+pub type CreditOf<T> = fungibles::CreditOf<<T as frame_system::Config>::AccountId, Pallet<T>>;
+
+/// The multi-currency debt, e.g. deposited by `fungibles::Balanced`, which
+/// increases the total issuance if dropped.
+pub type DebtOf<T> = fungibles::DebtOf<<T as frame_system::Config>::AccountId, Pallet<T>>;
+
+impl<T> fungibles::Inspect<T::AccountId> for Pallet<T>
+where
+	T: Config,
+	CurrencyIdOf<T>: AssetId,
+	T::NativeCurrency: fungible::Inspect<T::AccountId, Balance = BalanceOf<T>>,
+	T::MultiCurrency: fungibles::Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::FungiblesCurrency: fungibles::Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+{
+	type AssetId = CurrencyIdOf<T>;
+	type Balance = BalanceOf<T>;
+
+	fn total_issuance(asset_id: Self::AssetId) -> Self::Balance {
+		<Self as MultiCurrency<_>>::total_issuance(asset_id)
+	}
+
+	fn minimum_balance(asset_id: Self::AssetId) -> Self::Balance {
+		<Self as MultiCurrency<_>>::minimum_balance(asset_id)
+	}
+
+	fn balance(asset_id: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		dispatch!(
+			asset_id,
+			fungibles::Inspect<_> => balance(asset_id, who),
+			native => <T::NativeCurrency as fungible::Inspect<_>>::balance(who),
+			erc20(contract) => Erc20Currency::<T>::free_balance(contract, who),
+		)
+	}
+
+	fn reducible_balance(asset_id: Self::AssetId, who: &T::AccountId, keep_alive: bool) -> Self::Balance {
+		<Self as MultiReducibleCurrency<_>>::reducible_balance(asset_id, who, keep_alive)
+	}
+
+	fn can_deposit(asset_id: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DepositConsequence {
+		dispatch!(
+			asset_id,
+			fungibles::Inspect<_> => can_deposit(asset_id, who, amount),
+			native => <T::NativeCurrency as fungible::Inspect<_>>::can_deposit(who, amount),
+			erc20(contract) => Erc20Currency::<T>::can_deposit(contract),
+		)
+	}
+
+	fn can_withdraw(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		dispatch!(
+			asset_id,
+			fungibles::Inspect<_> => can_withdraw(asset_id, who, amount),
+			native => <T::NativeCurrency as fungible::Inspect<_>>::can_withdraw(who, amount),
+			erc20(contract) => Erc20Currency::<T>::can_withdraw(contract, who, amount),
+		)
+	}
+}
+
+impl<T> fungibles::Unbalanced<T::AccountId> for Pallet<T>
+where
+	T: Config,
+	CurrencyIdOf<T>: AssetId,
+	T::NativeCurrency: fungible::Unbalanced<T::AccountId> + fungible::Inspect<T::AccountId, Balance = BalanceOf<T>>,
+	T::MultiCurrency: fungibles::Unbalanced<T::AccountId>
+		+ fungibles::Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::FungiblesCurrency: fungibles::Unbalanced<T::AccountId>
+		+ fungibles::Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+{
+	fn set_balance(asset_id: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		dispatch!(
+			asset_id,
+			fungibles::Unbalanced<_> => set_balance(asset_id, who, amount),
+			native => <T::NativeCurrency as fungible::Unbalanced<_>>::set_balance(who, amount),
+			erc20(_) => Err(Error::<T>::Erc20Unsupported.into()),
+		)
+	}
+
+	fn set_total_issuance(asset_id: Self::AssetId, amount: Self::Balance) {
+		dispatch!(
+			asset_id,
+			fungibles::Unbalanced<_> => set_total_issuance(asset_id, amount),
+			native => <T::NativeCurrency as fungible::Unbalanced<_>>::set_total_issuance(amount),
+			// The total supply of ERC-20 contracts follows their balances.
+			erc20(_) => {},
+		)
+	}
+
+	fn decrease_balance(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> result::Result<Self::Balance, DispatchError> {
+		dispatch!(
+			asset_id,
+			fungibles::Unbalanced<_> => decrease_balance(asset_id, who, amount),
+			native => <T::NativeCurrency as fungible::Unbalanced<_>>::decrease_balance(who, amount),
+			erc20(contract) => Erc20Currency::<T>::withdraw(contract, who, amount).map(|_| amount),
+		)
+	}
+
+	fn decrease_balance_at_most(asset_id: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
+		dispatch!(
+			asset_id,
+			fungibles::Unbalanced<_> => decrease_balance_at_most(asset_id, who, amount),
+			native => <T::NativeCurrency as fungible::Unbalanced<_>>::decrease_balance_at_most(who, amount),
+			erc20(contract) => Erc20Currency::<T>::decrease_balance_at_most(contract, who, amount),
+		)
+	}
+
+	fn increase_balance(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> result::Result<Self::Balance, DispatchError> {
+		dispatch!(
+			asset_id,
+			fungibles::Unbalanced<_> => increase_balance(asset_id, who, amount),
+			native => <T::NativeCurrency as fungible::Unbalanced<_>>::increase_balance(who, amount),
+			erc20(contract) => Erc20Currency::<T>::deposit(contract, who, amount).map(|_| amount),
+		)
+	}
+
+	fn increase_balance_at_most(asset_id: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
+		dispatch!(
+			asset_id,
+			fungibles::Unbalanced<_> => increase_balance_at_most(asset_id, who, amount),
+			native => <T::NativeCurrency as fungible::Unbalanced<_>>::increase_balance_at_most(who, amount),
+			erc20(contract) => Erc20Currency::<T>::increase_balance_at_most(contract, who, amount),
+		)
 	}
 }
 
@@ -479,6 +968,58 @@ where
 	fn remove_lock(lock_id: LockIdentifier, who: &T::AccountId) -> DispatchResult {
 		<Pallet<T> as MultiLockableCurrency<T::AccountId>>::remove_lock(lock_id, GetCurrencyId::get(), who)
 	}
+
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		<Pallet<T> as MultiLockableCurrency<T::AccountId>>::set_lock_with_reasons(
+			lock_id,
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		)
+	}
+
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		<Pallet<T> as MultiLockableCurrency<T::AccountId>>::extend_lock_with_reasons(
+			lock_id,
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		)
+	}
+
+	fn ensure_can_withdraw_with_reasons(
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		<Pallet<T> as MultiLockableCurrency<T::AccountId>>::ensure_can_withdraw_with_reasons(
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		)
+	}
+
+	fn withdraw_with_reasons(who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) -> DispatchResult {
+		<Pallet<T> as MultiLockableCurrency<T::AccountId>>::withdraw_with_reasons(
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		)
+	}
 }
 
 impl<T, GetCurrencyId> BasicReservableCurrency<T::AccountId> for Currency<T, GetCurrencyId>
@@ -522,8 +1063,246 @@ where
 	}
 }
 
+impl<T, GetCurrencyId> BasicReducibleCurrency<T::AccountId> for Currency<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<CurrencyIdOf<T>>,
+{
+	fn reducible_balance(who: &T::AccountId, keep_alive: bool) -> Self::Balance {
+		<Pallet<T> as MultiReducibleCurrency<T::AccountId>>::reducible_balance(GetCurrencyId::get(), who, keep_alive)
+	}
+}
+
 pub type NativeCurrencyOf<T> = Currency<T, <T as Config>::GetNativeCurrencyId>;
 
+/// Adapt a currency of this module, set by `GetCurrencyId`, into
+/// `frame_support::traits::{Currency, ReservableCurrency, LockableCurrency}`.
+///
+/// Deposits, withdrawals and slashes change the total issuance immediately,
+/// so the imbalances are only accounting tokens, and `burn` and `issue` don't
+/// change the total issuance.
+pub struct CurrencyAdapter<T, GetCurrencyId>(marker::PhantomData<(T, GetCurrencyId)>);
+
+impl<T, GetCurrencyId> CurrencyAdapter<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<CurrencyIdOf<T>>,
+{
+	fn ensure_keep_alive(
+		who: &T::AccountId,
+		value: BalanceOf<T>,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		if existence_requirement == ExistenceRequirement::KeepAlive {
+			let currency_id = GetCurrencyId::get();
+			let new_balance = <Pallet<T>>::free_balance(currency_id, who)
+				.checked_sub(&value)
+				.ok_or(Error::<T>::BalanceTooLow)?;
+			ensure!(
+				new_balance >= <Pallet<T>>::minimum_balance(currency_id),
+				Error::<T>::KeepAlive
+			);
+		}
+		Ok(())
+	}
+}
+
+impl<T, GetCurrencyId> PalletCurrency<T::AccountId> for CurrencyAdapter<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<CurrencyIdOf<T>>,
+{
+	type Balance = BalanceOf<T>;
+	type PositiveImbalance = PositiveImbalance<T, GetCurrencyId>;
+	type NegativeImbalance = NegativeImbalance<T, GetCurrencyId>;
+
+	fn total_balance(who: &T::AccountId) -> Self::Balance {
+		<Pallet<T>>::total_balance(GetCurrencyId::get(), who)
+	}
+
+	fn can_slash(who: &T::AccountId, value: Self::Balance) -> bool {
+		<Pallet<T>>::can_slash(GetCurrencyId::get(), who, value)
+	}
+
+	fn total_issuance() -> Self::Balance {
+		<Pallet<T>>::total_issuance(GetCurrencyId::get())
+	}
+
+	fn minimum_balance() -> Self::Balance {
+		<Pallet<T>>::minimum_balance(GetCurrencyId::get())
+	}
+
+	fn burn(amount: Self::Balance) -> Self::PositiveImbalance {
+		PositiveImbalance::new(amount.min(Self::total_issuance()))
+	}
+
+	fn issue(amount: Self::Balance) -> Self::NegativeImbalance {
+		NegativeImbalance::new(amount)
+	}
+
+	fn free_balance(who: &T::AccountId) -> Self::Balance {
+		<Pallet<T>>::free_balance(GetCurrencyId::get(), who)
+	}
+
+	fn ensure_can_withdraw(
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+		_new_balance: Self::Balance,
+	) -> DispatchResult {
+		<Pallet<T> as MultiLockableCurrency<T::AccountId>>::ensure_can_withdraw_with_reasons(
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		)
+	}
+
+	fn transfer(
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		value: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		Self::ensure_keep_alive(source, value, existence_requirement)?;
+		<Pallet<T> as MultiCurrency<T::AccountId>>::transfer(GetCurrencyId::get(), source, dest, value)
+	}
+
+	fn slash(who: &T::AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
+		let remaining = <Pallet<T>>::slash(GetCurrencyId::get(), who, value);
+		(NegativeImbalance::new(value.saturating_sub(remaining)), remaining)
+	}
+
+	fn deposit_into_existing(
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> result::Result<Self::PositiveImbalance, DispatchError> {
+		if value.is_zero() {
+			return Ok(PositiveImbalance::zero());
+		}
+		ensure!(!Self::total_balance(who).is_zero(), Error::<T>::DeadAccount);
+		<Pallet<T>>::deposit(GetCurrencyId::get(), who, value)?;
+		Ok(PositiveImbalance::new(value))
+	}
+
+	fn deposit_creating(who: &T::AccountId, value: Self::Balance) -> Self::PositiveImbalance {
+		<Pallet<T>>::deposit(GetCurrencyId::get(), who, value)
+			.map_or_else(|_| PositiveImbalance::zero(), |_| PositiveImbalance::new(value))
+	}
+
+	fn withdraw(
+		who: &T::AccountId,
+		value: Self::Balance,
+		reasons: WithdrawReasons,
+		liveness: ExistenceRequirement,
+	) -> result::Result<Self::NegativeImbalance, DispatchError> {
+		Self::ensure_keep_alive(who, value, liveness)?;
+		<Pallet<T> as MultiLockableCurrency<T::AccountId>>::withdraw_with_reasons(
+			GetCurrencyId::get(),
+			who,
+			value,
+			reasons,
+		)?;
+		Ok(NegativeImbalance::new(value))
+	}
+
+	fn make_free_balance_be(
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> SignedImbalance<Self::Balance, Self::PositiveImbalance> {
+		let currency_id = GetCurrencyId::get();
+		let free_balance = Self::free_balance(who);
+		if value >= free_balance {
+			let amount = value - free_balance;
+			match <Pallet<T>>::deposit(currency_id, who, amount) {
+				Ok(()) => SignedImbalance::Positive(PositiveImbalance::new(amount)),
+				Err(_) => SignedImbalance::Positive(PositiveImbalance::zero()),
+			}
+		} else {
+			// the slash is taken from the free balance first, and can't exceed it
+			let amount = free_balance - value;
+			let remaining = <Pallet<T>>::slash(currency_id, who, amount);
+			SignedImbalance::Negative(NegativeImbalance::new(amount.saturating_sub(remaining)))
+		}
+	}
+}
+
+impl<T, GetCurrencyId> PalletReservableCurrency<T::AccountId> for CurrencyAdapter<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<CurrencyIdOf<T>>,
+{
+	fn can_reserve(who: &T::AccountId, value: Self::Balance) -> bool {
+		<Pallet<T> as MultiReservableCurrency<T::AccountId>>::can_reserve(GetCurrencyId::get(), who, value)
+	}
+
+	fn slash_reserved(who: &T::AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
+		let remaining =
+			<Pallet<T> as MultiReservableCurrency<T::AccountId>>::slash_reserved(GetCurrencyId::get(), who, value);
+		(NegativeImbalance::new(value.saturating_sub(remaining)), remaining)
+	}
+
+	fn reserved_balance(who: &T::AccountId) -> Self::Balance {
+		<Pallet<T> as MultiReservableCurrency<T::AccountId>>::reserved_balance(GetCurrencyId::get(), who)
+	}
+
+	fn reserve(who: &T::AccountId, value: Self::Balance) -> DispatchResult {
+		<Pallet<T> as MultiReservableCurrency<T::AccountId>>::reserve(GetCurrencyId::get(), who, value)
+	}
+
+	fn unreserve(who: &T::AccountId, value: Self::Balance) -> Self::Balance {
+		<Pallet<T> as MultiReservableCurrency<T::AccountId>>::unreserve(GetCurrencyId::get(), who, value)
+	}
+
+	fn repatriate_reserved(
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError> {
+		<Pallet<T> as MultiReservableCurrency<T::AccountId>>::repatriate_reserved(
+			GetCurrencyId::get(),
+			slashed,
+			beneficiary,
+			value,
+			status,
+		)
+	}
+}
+
+impl<T, GetCurrencyId> PalletLockableCurrency<T::AccountId> for CurrencyAdapter<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<CurrencyIdOf<T>>,
+{
+	type Moment = T::BlockNumber;
+	type MaxLocks = T::MaxLocks;
+
+	fn set_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) {
+		let _ = <Pallet<T> as MultiLockableCurrency<T::AccountId>>::set_lock_with_reasons(
+			id,
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		);
+	}
+
+	fn extend_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) {
+		let _ = <Pallet<T> as MultiLockableCurrency<T::AccountId>>::extend_lock_with_reasons(
+			id,
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		);
+	}
+
+	fn remove_lock(id: LockIdentifier, who: &T::AccountId) {
+		let _ = <Pallet<T> as MultiLockableCurrency<T::AccountId>>::remove_lock(id, GetCurrencyId::get(), who);
+	}
+}
+
 /// Adapt other currency traits implementation to `BasicCurrency`.
 pub struct BasicCurrencyAdapter<T, Currency, Amount, Moment>(marker::PhantomData<(T, Currency, Amount, Moment)>);
 
@@ -639,6 +1418,42 @@ where
 		Currency::remove_lock(lock_id, who);
 		Ok(())
 	}
+
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Currency::set_lock(lock_id, who, amount, reasons);
+		Ok(())
+	}
+
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Currency::extend_lock(lock_id, who, amount, reasons);
+		Ok(())
+	}
+
+	fn ensure_can_withdraw_with_reasons(
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		let new_balance = Currency::free_balance(who)
+			.checked_sub(&amount)
+			.ok_or(Error::<T>::BalanceTooLow)?;
+
+		Currency::ensure_can_withdraw(who, amount, reasons, new_balance)
+	}
+
+	fn withdraw_with_reasons(who: &AccountId, amount: Self::Balance, reasons: WithdrawReasons) -> DispatchResult {
+		Currency::withdraw(who, amount, reasons, ExistenceRequirement::AllowDeath).map(|_| ())
+	}
 }
 
 // Adapt `frame_support::traits::ReservableCurrency`
@@ -679,6 +1494,82 @@ where
 	}
 }
 
+// Adapt `frame_support::traits::tokens::fungible::Inspect`
+impl<T, AccountId, Currency, Amount, Moment> BasicReducibleCurrency<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: PalletCurrency<AccountId> + fungible::Inspect<AccountId, Balance = PalletBalanceOf<AccountId, Currency>>,
+	T: Config,
+{
+	fn reducible_balance(who: &AccountId, keep_alive: bool) -> Self::Balance {
+		<Currency as fungible::Inspect<AccountId>>::reducible_balance(who, keep_alive)
+	}
+}
+
+impl<T, AccountId, Currency, Amount, Moment> fungible::Inspect<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::Inspect<AccountId>,
+	T: Config,
+{
+	type Balance = <Currency as fungible::Inspect<AccountId>>::Balance;
+
+	fn total_issuance() -> Self::Balance {
+		<Currency as fungible::Inspect<AccountId>>::total_issuance()
+	}
+
+	fn minimum_balance() -> Self::Balance {
+		<Currency as fungible::Inspect<AccountId>>::minimum_balance()
+	}
+
+	fn balance(who: &AccountId) -> Self::Balance {
+		<Currency as fungible::Inspect<AccountId>>::balance(who)
+	}
+
+	fn reducible_balance(who: &AccountId, keep_alive: bool) -> Self::Balance {
+		<Currency as fungible::Inspect<AccountId>>::reducible_balance(who, keep_alive)
+	}
+
+	fn can_deposit(who: &AccountId, amount: Self::Balance) -> DepositConsequence {
+		<Currency as fungible::Inspect<AccountId>>::can_deposit(who, amount)
+	}
+
+	fn can_withdraw(who: &AccountId, amount: Self::Balance) -> WithdrawConsequence<Self::Balance> {
+		<Currency as fungible::Inspect<AccountId>>::can_withdraw(who, amount)
+	}
+}
+
+impl<T, AccountId, Currency, Amount, Moment> fungible::Unbalanced<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::Unbalanced<AccountId>,
+	T: Config,
+{
+	fn set_balance(who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		<Currency as fungible::Unbalanced<AccountId>>::set_balance(who, amount)
+	}
+
+	fn set_total_issuance(amount: Self::Balance) {
+		<Currency as fungible::Unbalanced<AccountId>>::set_total_issuance(amount)
+	}
+
+	fn decrease_balance(who: &AccountId, amount: Self::Balance) -> result::Result<Self::Balance, DispatchError> {
+		<Currency as fungible::Unbalanced<AccountId>>::decrease_balance(who, amount)
+	}
+
+	fn decrease_balance_at_most(who: &AccountId, amount: Self::Balance) -> Self::Balance {
+		<Currency as fungible::Unbalanced<AccountId>>::decrease_balance_at_most(who, amount)
+	}
+
+	fn increase_balance(who: &AccountId, amount: Self::Balance) -> result::Result<Self::Balance, DispatchError> {
+		<Currency as fungible::Unbalanced<AccountId>>::increase_balance(who, amount)
+	}
+
+	fn increase_balance_at_most(who: &AccountId, amount: Self::Balance) -> Self::Balance {
+		<Currency as fungible::Unbalanced<AccountId>>::increase_balance_at_most(who, amount)
+	}
+}
+
 impl<T: Config> TransferAll<T::AccountId> for Pallet<T> {
 	fn transfer_all(source: &T::AccountId, dest: &T::AccountId) -> DispatchResult {
 		with_transaction_result(|| {
@@ -3,23 +3,28 @@
 #![cfg(test)]
 
 use super::*;
+use codec::Encode;
+use frame_support::storage::unhashed;
 use frame_support::{
-	construct_runtime, parameter_types,
-	traits::{Everything, Nothing},
+	assert_ok, construct_runtime, parameter_types,
+	traits::{tokens::fungibles::Mutate, Everything, Nothing},
+	weights::{constants::RocksDbWeight, IdentityFee},
 	PalletId,
 };
 use orml_traits::parameter_type_with_key;
-use sp_core::H256;
+use orml_traits::{DataProvider, DefaultPriceProvider, Erc20Error};
+use sp_core::{H160, H256};
 use sp_runtime::{
 	testing::Header,
 	traits::{AccountIdConversion, IdentityLookup},
-	AccountId32,
+	AccountId32, FixedPointNumber, FixedU128,
 };
 
 use crate as currencies;
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
+	pub BlockWeights: frame_system::limits::BlockWeights = frame_system::limits::BlockWeights::simple_max(1024);
 }
 
 pub type AccountId = AccountId32;
@@ -35,14 +40,14 @@ impl frame_system::Config for Runtime {
 	type Header = Header;
 	type Event = Event;
 	type BlockHashCount = BlockHashCount;
-	type BlockWeights = ();
+	type BlockWeights = BlockWeights;
 	type BlockLength = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
 	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
-	type DbWeight = ();
+	type DbWeight = RocksDbWeight;
 	type BaseCallFilter = Everything;
 	type SystemWeightInfo = ();
 	type SS58Prefix = ();
@@ -74,9 +79,27 @@ parameter_type_with_key! {
 	};
 }
 
+parameter_type_with_key! {
+	pub MaxIssuance: |_currency_id: CurrencyId| -> Option<Balance> {
+		None
+	};
+}
+
+parameter_type_with_key! {
+	pub IssuanceHighWaterMark: |_currency_id: CurrencyId| -> Option<Balance> {
+		None
+	};
+}
+
 parameter_types! {
 	pub DustAccount: AccountId = PalletId(*b"orml/dst").into_account();
 	pub MaxLocks: u32 = 100_000;
+	pub MaxReserves: u32 = 100_000;
+	pub const MaxTransfers: u32 = 100;
+	pub const StringLimit: u32 = 50;
+	pub const ApprovalDeposit: Balance = 1;
+	pub const AllowFrozenReceiving: bool = true;
+	pub const MaintainHoldersIndex: bool = false;
 }
 
 impl orml_tokens::Config for Runtime {
@@ -87,27 +110,221 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = orml_tokens::TransferDust<Runtime, DustAccount>;
+	type OnTransfer = ();
+	type OnDeposit = ();
+	type OnWithdraw = ();
+	type OnSlash = ();
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+	type SufficientCurrencies = Nothing;
+	type ProviderCurrencies = Everything;
 	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type MaxTransfers = MaxTransfers;
+	type DepositCurrency = PalletBalances;
+	type ApprovalDeposit = ApprovalDeposit;
+	type ReserveIdentifier = [u8; 8];
 	type DustRemovalWhitelist = Nothing;
+	type DustReceiver = DustAccount;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MetadataOrigin = frame_system::EnsureRoot<AccountId>;
+	type StringLimit = StringLimit;
+	type AllowFrozenReceiving = AllowFrozenReceiving;
+	type MaintainHoldersIndex = MaintainHoldersIndex;
+	type MaxIssuance = MaxIssuance;
+	type IssuanceHighWaterMark = IssuanceHighWaterMark;
 }
 
 pub const NATIVE_CURRENCY_ID: CurrencyId = 1;
 pub const X_TOKEN_ID: CurrencyId = 2;
+pub const NO_PRICE_TOKEN_ID: CurrencyId = 3;
+pub const EXPENSIVE_TOKEN_ID: CurrencyId = 4;
+pub const FUNGIBLE_TOKEN_ID: CurrencyId = 10;
+/// A fungibles currency with a minimum balance of 10.
+pub const MIN_BALANCE_FUNGIBLE_TOKEN_ID: CurrencyId = 11;
+pub const ERC20_TOKEN_ID: CurrencyId = 20;
+pub const OUT_OF_GAS_ERC20_TOKEN_ID: CurrencyId = 21;
+pub const UNDEPLOYED_ERC20_TOKEN_ID: CurrencyId = 22;
+
+pub const ERC20_ADDRESS: H160 = H160([1u8; 20]);
+pub const OUT_OF_GAS_ERC20_ADDRESS: H160 = H160([2u8; 20]);
+pub const UNDEPLOYED_ERC20_ADDRESS: H160 = H160([3u8; 20]);
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 0;
+	pub const MetadataDepositBase: Balance = 0;
+	pub const MetadataDepositPerByte: Balance = 0;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = CurrencyId;
+	type Currency = PalletBalances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = FungiblesFreezer<Runtime>;
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+/// A mock ERC-20 executor standing in for an EVM. The contracts are deployed
+/// at `ERC20_ADDRESS`, which reverts transfers to `EVA` and those exceeding
+/// the balance, and `OUT_OF_GAS_ERC20_ADDRESS`, which runs out of gas on any
+/// call.
+pub struct MockErc20;
+
+impl MockErc20 {
+	fn ensure_deployed(contract: H160) -> Result<(), Erc20Error> {
+		match contract {
+			ERC20_ADDRESS => Ok(()),
+			OUT_OF_GAS_ERC20_ADDRESS => Err(Erc20Error::OutOfGas),
+			_ => Err(Erc20Error::ContractNotFound),
+		}
+	}
+
+	fn supply_key(contract: H160) -> Vec<u8> {
+		(b"erc20/supply", contract).encode()
+	}
+
+	fn balance_key(contract: H160, who: &AccountId) -> Vec<u8> {
+		(b"erc20/balance", contract, who).encode()
+	}
+
+	fn set_balance(contract: H160, who: &AccountId, balance: Balance) {
+		unhashed::put(&Self::balance_key(contract, who), &balance);
+	}
+}
+
+impl Erc20Adapter<AccountId, Balance> for MockErc20 {
+	fn total_supply(contract: H160) -> Result<Balance, Erc20Error> {
+		Self::ensure_deployed(contract)?;
+		Ok(unhashed::get_or_default(&Self::supply_key(contract)))
+	}
+
+	fn balance_of(contract: H160, who: &AccountId) -> Result<Balance, Erc20Error> {
+		Self::ensure_deployed(contract)?;
+		Ok(unhashed::get_or_default(&Self::balance_key(contract, who)))
+	}
+
+	fn transfer(contract: H160, from: &AccountId, to: &AccountId, amount: Balance) -> Result<(), Erc20Error> {
+		let from_balance = Self::balance_of(contract, from)?;
+		if *to == EVA || from_balance < amount {
+			return Err(Erc20Error::Reverted);
+		}
+		Self::set_balance(contract, from, from_balance - amount);
+		Self::set_balance(contract, to, Self::balance_of(contract, to)? + amount);
+		Ok(())
+	}
+
+	fn mint(contract: H160, who: &AccountId, amount: Balance) -> Result<(), Erc20Error> {
+		let supply = Self::total_supply(contract)?;
+		unhashed::put(&Self::supply_key(contract), &(supply + amount));
+		Self::set_balance(contract, who, Self::balance_of(contract, who)? + amount);
+		Ok(())
+	}
+
+	fn burn(contract: H160, who: &AccountId, amount: Balance) -> Result<(), Erc20Error> {
+		let balance = Self::balance_of(contract, who)?;
+		if balance < amount {
+			return Err(Erc20Error::Reverted);
+		}
+		let supply = Self::total_supply(contract)?;
+		unhashed::put(&Self::supply_key(contract), &(supply - amount));
+		Self::set_balance(contract, who, balance - amount);
+		Ok(())
+	}
+}
+
+parameter_type_with_key! {
+	pub CurrencyBackends: |currency_id: CurrencyId| -> CurrencyBackend {
+		match *currency_id {
+			FUNGIBLE_TOKEN_ID | MIN_BALANCE_FUNGIBLE_TOKEN_ID => CurrencyBackend::Fungibles,
+			ERC20_TOKEN_ID => CurrencyBackend::Erc20(ERC20_ADDRESS),
+			OUT_OF_GAS_ERC20_TOKEN_ID => CurrencyBackend::Erc20(OUT_OF_GAS_ERC20_ADDRESS),
+			UNDEPLOYED_ERC20_TOKEN_ID => CurrencyBackend::Erc20(UNDEPLOYED_ERC20_ADDRESS),
+			_ => CurrencyBackend::MultiCurrency,
+		}
+	};
+}
 
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
+	pub const CurrenciesPalletId: PalletId = PalletId(*b"orml/cur");
+	pub const MaxHeldCurrencies: u32 = 2;
+	pub const MaxFungiblesCurrencies: u32 = 2;
+	pub FungiblesCurrencyIds: BoundedVec<CurrencyId, MaxFungiblesCurrencies> =
+		vec![FUNGIBLE_TOKEN_ID].try_into().expect("at most `MaxFungiblesCurrencies`; qed");
 }
 
 impl Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
 	type NativeCurrency = AdaptedBasicCurrency;
+	type FungiblesCurrency = FungiblesAdapter<Runtime, Assets, FungiblesCurrencyIds>;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type CurrencyBackends = CurrencyBackends;
+	type Erc20Adapter = MockErc20;
+	type PalletId = CurrenciesPalletId;
+	type MaxLocks = MaxLocks;
+	type MaxHeldCurrencies = MaxHeldCurrencies;
+	type MaxFungiblesCurrencies = MaxFungiblesCurrencies;
 	type WeightInfo = ();
 }
 pub type NativeCurrency = NativeCurrencyOf<Runtime>;
 pub type AdaptedBasicCurrency = BasicCurrencyAdapter<Runtime, PalletBalances, i64, u64>;
 
+parameter_types! {
+	pub const GetXTokenId: CurrencyId = X_TOKEN_ID;
+}
+
+pub type AdaptedNativeCurrency = CurrencyAdapter<Runtime, GetNativeCurrencyId>;
+pub type AdaptedXToken = CurrencyAdapter<Runtime, GetXTokenId>;
+
+pub type Price = FixedU128;
+
+/// A mock oracle, the native currency is worth twice as much as `X_TOKEN_ID`,
+/// `EXPENSIVE_TOKEN_ID` is worth a thousand times as much as the native
+/// currency, and `NO_PRICE_TOKEN_ID` has no price.
+pub struct MockPriceSource;
+impl DataProvider<CurrencyId, Price> for MockPriceSource {
+	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		match *currency_id {
+			NATIVE_CURRENCY_ID => Some(Price::saturating_from_integer(1)),
+			X_TOKEN_ID => Some(Price::saturating_from_rational(1, 2)),
+			EXPENSIVE_TOKEN_ID => Some(Price::saturating_from_integer(1_000)),
+			_ => None,
+		}
+	}
+}
+
+parameter_types! {
+	pub FeeReceiver: AccountId = PalletId(*b"orml/fee").into_account();
+	pub const TransactionByteFee: Balance = 1;
+}
+
+impl pallet_transaction_payment::Config for Runtime {
+	type OnChargeTransaction =
+		MultiCurrencyFeeAdapter<Runtime, DefaultPriceProvider<CurrencyId, MockPriceSource>, Price, FeeReceiver>;
+	type TransactionByteFee = TransactionByteFee;
+	type WeightToFee = IdentityFee<Balance>;
+	type FeeMultiplierUpdate = ();
+}
+
+parameter_types! {
+	pub TreasuryAccount: AccountId = PalletId(*b"orml/trs").into_account();
+	pub const TreasuryPart: u32 = 4;
+	pub const BurnPart: u32 = 1;
+}
+
+/// Resolve 80% of a credit into the treasury, and burn the rest.
+pub type DealWithCredit =
+	SplitTwoWays<AccountId, Currencies, TreasuryPart, ResolveTo<AccountId, Currencies, TreasuryAccount>, BurnPart, ()>;
+
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
 type Block = frame_system::mocking::MockBlock<Runtime>;
 
@@ -121,6 +338,8 @@ construct_runtime!(
 		Currencies: currencies::{Pallet, Call, Event<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
 		PalletBalances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -175,12 +394,40 @@ impl ExtBuilder {
 			balances: self
 				.balances
 				.into_iter()
-				.filter(|(_, currency_id, _)| *currency_id != NATIVE_CURRENCY_ID)
+				.clone()
+				.into_iter()
+				.filter(|(_, currency_id, _)| {
+					*currency_id != NATIVE_CURRENCY_ID
+						&& CurrencyBackends::get(currency_id) == CurrencyBackend::MultiCurrency
+				})
 				.collect::<Vec<_>>(),
+			metadata: vec![],
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
 
-		t.into()
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			assert_ok!(Assets::force_create(Origin::root(), FUNGIBLE_TOKEN_ID, ALICE, true, 1));
+			assert_ok!(Assets::force_create(
+				Origin::root(),
+				MIN_BALANCE_FUNGIBLE_TOKEN_ID,
+				ALICE,
+				true,
+				10
+			));
+			for (account_id, currency_id, initial_balance) in self.balances {
+				match CurrencyBackends::get(&currency_id) {
+					CurrencyBackend::Fungibles => {
+						assert_ok!(Assets::mint_into(currency_id, &account_id, initial_balance));
+					}
+					CurrencyBackend::Erc20(contract) => {
+						assert_ok!(MockErc20::mint(contract, &account_id, initial_balance));
+					}
+					CurrencyBackend::MultiCurrency => {}
+				}
+			}
+		});
+		ext
 	}
 }
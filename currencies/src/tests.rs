@@ -3,9 +3,41 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::OnUnbalanced,
+	weights::{DispatchInfo, PostDispatchInfo},
+};
 use mock::{Event, *};
-use sp_runtime::traits::BadOrigin;
+use pallet_transaction_payment::ChargeTransactionPayment;
+use sp_runtime::{
+	traits::{AccountIdConversion, BadOrigin, SignedExtension},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	TokenError,
+};
+
+const CALL: &<Runtime as frame_system::Config>::Call =
+	&Call::Currencies(crate::Call::transfer_native_currency(BOB, 10));
+
+/// Dispatch info of weight 10, with a length of 10 the fee is 20.
+fn info() -> DispatchInfo {
+	DispatchInfo {
+		weight: 10,
+		..Default::default()
+	}
+}
+
+/// Post dispatch info of actual weight 5, the corrected fee is 15.
+fn post_info() -> PostDispatchInfo {
+	PostDispatchInfo {
+		actual_weight: Some(5),
+		pays_fee: Default::default(),
+	}
+}
+
+fn fee_extension(currency_id: Option<CurrencyId>) -> SetFeeCurrency<Runtime> {
+	SetFeeCurrency::new(currency_id, ChargeTransactionPayment::from(0))
+}
 
 #[test]
 fn multi_lockable_currency_should_work() {
@@ -20,6 +52,41 @@ fn multi_lockable_currency_should_work() {
 		});
 }
 
+#[test]
+fn multi_lockable_currency_with_reasons_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::set_lock_with_reasons(
+				ID_1,
+				X_TOKEN_ID,
+				&ALICE,
+				50,
+				WithdrawReasons::TRANSACTION_PAYMENT
+			));
+			assert_eq!(Tokens::locks(&ALICE, X_TOKEN_ID)[0].reasons, orml_tokens::Reasons::Fee);
+
+			assert_ok!(Currencies::set_lock_with_reasons(
+				ID_1,
+				NATIVE_CURRENCY_ID,
+				&ALICE,
+				50,
+				WithdrawReasons::TRANSACTION_PAYMENT
+			));
+			assert_eq!(PalletBalances::locks(&ALICE)[0].reasons, pallet_balances::Reasons::Fee);
+			assert_ok!(Currencies::extend_lock_with_reasons(
+				ID_1,
+				NATIVE_CURRENCY_ID,
+				&ALICE,
+				20,
+				WithdrawReasons::TRANSFER
+			));
+			assert_eq!(PalletBalances::locks(&ALICE)[0].amount, 50);
+			assert_eq!(PalletBalances::locks(&ALICE)[0].reasons, pallet_balances::Reasons::All);
+		});
+}
+
 #[test]
 fn multi_reservable_currency_should_work() {
 	ExtBuilder::default()
@@ -276,3 +343,815 @@ fn call_event_should_work() {
 			System::assert_last_event(Event::Currencies(crate::Event::Withdrawn(X_TOKEN_ID, ALICE, 20)));
 		});
 }
+
+#[test]
+fn pay_fee_in_native_currency_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let pre = fee_extension(None).pre_dispatch(&ALICE, CALL, &info(), 10).unwrap();
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 80);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 100);
+
+			assert_ok!(SetFeeCurrency::<Runtime>::post_dispatch(
+				pre,
+				&info(),
+				&post_info(),
+				10,
+				&Ok(())
+			));
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 85);
+			assert_eq!(NativeCurrency::free_balance(&FeeReceiver::get()), 15);
+		});
+}
+
+#[test]
+fn pay_fee_in_non_native_currency_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let pre = fee_extension(Some(X_TOKEN_ID))
+				.pre_dispatch(&ALICE, CALL, &info(), 10)
+				.unwrap();
+			assert_eq!(FeeCurrency::<Runtime>::get(), None);
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 100);
+			// the native currency is worth twice as much as `X_TOKEN_ID`
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 60);
+
+			assert_ok!(SetFeeCurrency::<Runtime>::post_dispatch(
+				pre,
+				&info(),
+				&post_info(),
+				10,
+				&Ok(())
+			));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 70);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &FeeReceiver::get()), 30);
+			assert_eq!(Currencies::total_issuance(X_TOKEN_ID), 200);
+		});
+}
+
+#[test]
+fn pay_fee_fails_if_no_price() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, NO_PRICE_TOKEN_ID, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				fee_extension(Some(NO_PRICE_TOKEN_ID))
+					.pre_dispatch(&ALICE, CALL, &info(), 10)
+					.err(),
+				Some(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+			);
+			assert_eq!(Currencies::free_balance(NO_PRICE_TOKEN_ID, &ALICE), 100);
+			assert_eq!(FeeCurrency::<Runtime>::get(), None);
+		});
+}
+
+#[test]
+fn pay_fee_fails_if_balance_too_low() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, NATIVE_CURRENCY_ID, 100), (ALICE, X_TOKEN_ID, 39)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				fee_extension(Some(X_TOKEN_ID))
+					.pre_dispatch(&ALICE, CALL, &info(), 10)
+					.err(),
+				Some(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+			);
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 100);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 39);
+		});
+}
+
+#[test]
+fn pay_fee_fails_if_converted_to_zero() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, EXPENSIVE_TOKEN_ID, 100)])
+		.build()
+		.execute_with(|| {
+			// a fee of 20 is worth 0.02 `EXPENSIVE_TOKEN_ID`
+			assert_eq!(
+				fee_extension(Some(EXPENSIVE_TOKEN_ID))
+					.pre_dispatch(&ALICE, CALL, &info(), 10)
+					.err(),
+				Some(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+			);
+			assert_eq!(Currencies::free_balance(EXPENSIVE_TOKEN_ID, &ALICE), 100);
+		});
+}
+
+#[test]
+fn pay_fee_should_keep_payer_alive() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, NATIVE_CURRENCY_ID, 20)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				fee_extension(None).pre_dispatch(&ALICE, CALL, &info(), 10).err(),
+				Some(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+			);
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 20);
+		});
+}
+
+#[test]
+fn pay_fee_should_respect_lock_reasons() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			// locks restricting other withdrawals don't restrict the fee
+			assert_ok!(Currencies::set_lock_with_reasons(
+				ID_1,
+				X_TOKEN_ID,
+				&ALICE,
+				100,
+				WithdrawReasons::TRANSFER
+			));
+			assert_ok!(fee_extension(Some(X_TOKEN_ID)).pre_dispatch(&ALICE, CALL, &info(), 10));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 60);
+
+			assert_ok!(Currencies::set_lock_with_reasons(
+				ID_1,
+				X_TOKEN_ID,
+				&BOB,
+				100,
+				WithdrawReasons::TRANSACTION_PAYMENT
+			));
+			assert_eq!(
+				fee_extension(Some(X_TOKEN_ID))
+					.pre_dispatch(&BOB, CALL, &info(), 10)
+					.err(),
+				Some(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+			);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 100);
+		});
+}
+
+#[test]
+fn set_fee_currency_should_not_leak_into_next_extrinsic() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			// the fee is checked in the fee currency
+			assert_ok!(fee_extension(Some(X_TOKEN_ID)).validate(&ALICE, CALL, &info(), 10));
+			assert_eq!(FeeCurrency::<Runtime>::get(), None);
+			assert_eq!(
+				fee_extension(Some(NO_PRICE_TOKEN_ID))
+					.validate(&ALICE, CALL, &info(), 10)
+					.err(),
+				Some(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+			);
+			assert_eq!(FeeCurrency::<Runtime>::get(), None);
+
+			assert_ok!(fee_extension(None).pre_dispatch(&BOB, CALL, &info(), 10));
+			assert_eq!(NativeCurrency::free_balance(&BOB), 80);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 100);
+		});
+}
+
+#[test]
+fn held_currencies_and_reducible_balance_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let mut held = <Currencies as MultiReducibleCurrency<AccountId>>::held_currencies(&ALICE, 10);
+			held.sort_unstable();
+			assert_eq!(held, vec![NATIVE_CURRENCY_ID, X_TOKEN_ID]);
+			assert_eq!(
+				<Currencies as MultiReducibleCurrency<AccountId>>::held_currencies(&ALICE, 1),
+				vec![X_TOKEN_ID]
+			);
+			assert_eq!(
+				<Currencies as MultiReducibleCurrency<AccountId>>::held_currencies(&EVA, 10),
+				vec![]
+			);
+
+			assert_ok!(Currencies::set_lock(ID_1, NATIVE_CURRENCY_ID, &ALICE, 10));
+			assert_ok!(Currencies::reserve(X_TOKEN_ID, &ALICE, 20));
+			assert_eq!(
+				<Currencies as MultiReducibleCurrency<AccountId>>::reducible_balance(NATIVE_CURRENCY_ID, &ALICE, false),
+				90
+			);
+			assert_eq!(
+				<Currencies as MultiReducibleCurrency<AccountId>>::reducible_balance(X_TOKEN_ID, &ALICE, false),
+				80
+			);
+			assert_eq!(NativeCurrency::reducible_balance(&BOB, true), 99);
+			assert_eq!(AdaptedBasicCurrency::reducible_balance(&BOB, false), 100);
+		});
+}
+
+#[test]
+fn transfer_all_currencies_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Currencies::set_lock(ID_1, NATIVE_CURRENCY_ID, &ALICE, 10));
+			assert_ok!(Currencies::reserve(X_TOKEN_ID, &ALICE, 20));
+
+			// the balance and the reserve of each of `MaxFungiblesCurrencies` are read
+			let fungibles_weight = <Runtime as frame_system::Config>::DbWeight::get().reads(2 * 2);
+			assert_eq!(
+				Currencies::transfer_all_currencies(Some(ALICE).into(), BOB, false).map(|info| info.actual_weight),
+				Ok(Some(<() as WeightInfo>::transfer_all_currencies(1) + fungibles_weight))
+			);
+			System::assert_last_event(Event::Currencies(crate::Event::AllCurrenciesTransferred(
+				ALICE,
+				BOB,
+				vec![],
+			)));
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 10);
+			assert_eq!(NativeCurrency::free_balance(&BOB), 190);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 0);
+			assert_eq!(Currencies::reserved_balance(X_TOKEN_ID, &ALICE), 20);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 180);
+		});
+}
+
+#[test]
+fn transfer_all_currencies_keep_alive_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::transfer_all_currencies(Some(ALICE).into(), BOB, true));
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 1);
+			assert_eq!(NativeCurrency::free_balance(&BOB), 199);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 0);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 200);
+		});
+}
+
+#[test]
+fn transfer_all_currencies_should_skip_currencies_with_nothing_to_transfer() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, X_TOKEN_ID, 100), (ALICE, NO_PRICE_TOKEN_ID, 100)])
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Currencies::reserve(NO_PRICE_TOKEN_ID, &ALICE, 100));
+			assert_ok!(Currencies::transfer_all_currencies(Some(ALICE).into(), BOB, false));
+			System::assert_last_event(Event::Currencies(crate::Event::AllCurrenciesTransferred(
+				ALICE,
+				BOB,
+				vec![NO_PRICE_TOKEN_ID, NATIVE_CURRENCY_ID],
+			)));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 100);
+			assert_eq!(Currencies::reserved_balance(NO_PRICE_TOKEN_ID, &ALICE), 100);
+		});
+}
+
+#[test]
+fn transfer_all_currencies_fails_if_too_many_currencies() {
+	ExtBuilder::default()
+		.balances(vec![
+			(ALICE, X_TOKEN_ID, 100),
+			(ALICE, NO_PRICE_TOKEN_ID, 100),
+			(ALICE, 4, 100),
+		])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Currencies::transfer_all_currencies(Some(ALICE).into(), BOB, false),
+				Error::<Runtime>::TooManyCurrencies
+			);
+		});
+}
+
+#[test]
+fn fungibles_currency_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, FUNGIBLE_TOKEN_ID, 100), (BOB, FUNGIBLE_TOKEN_ID, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Currencies::total_issuance(FUNGIBLE_TOKEN_ID), 200);
+			assert_eq!(Currencies::minimum_balance(FUNGIBLE_TOKEN_ID), 1);
+			assert_eq!(Tokens::free_balance(FUNGIBLE_TOKEN_ID, &ALICE), 0);
+
+			assert_ok!(Currencies::transfer(Some(ALICE).into(), BOB, FUNGIBLE_TOKEN_ID, 50));
+			assert_eq!(Currencies::free_balance(FUNGIBLE_TOKEN_ID, &ALICE), 50);
+			assert_eq!(Currencies::free_balance(FUNGIBLE_TOKEN_ID, &BOB), 150);
+			assert_eq!(Assets::balance(FUNGIBLE_TOKEN_ID, ALICE), 50);
+
+			assert_ok!(Currencies::deposit(FUNGIBLE_TOKEN_ID, &ALICE, 10));
+			assert_eq!(Currencies::free_balance(FUNGIBLE_TOKEN_ID, &ALICE), 60);
+			assert_eq!(Currencies::total_issuance(FUNGIBLE_TOKEN_ID), 210);
+
+			assert_ok!(Currencies::withdraw(FUNGIBLE_TOKEN_ID, &ALICE, 20));
+			assert_eq!(Currencies::free_balance(FUNGIBLE_TOKEN_ID, &ALICE), 40);
+			assert_eq!(Currencies::total_issuance(FUNGIBLE_TOKEN_ID), 190);
+
+			assert_ok!(Currencies::update_balance(FUNGIBLE_TOKEN_ID, &ALICE, -10));
+			assert_eq!(Currencies::free_balance(FUNGIBLE_TOKEN_ID, &ALICE), 30);
+			assert_ok!(Currencies::update_balance(FUNGIBLE_TOKEN_ID, &ALICE, 20));
+			assert_eq!(Currencies::free_balance(FUNGIBLE_TOKEN_ID, &ALICE), 50);
+
+			assert_noop!(
+				Currencies::withdraw(FUNGIBLE_TOKEN_ID, &ALICE, 100),
+				Error::<Runtime>::BalanceTooLow
+			);
+		});
+}
+
+#[test]
+fn fungibles_currency_reservable_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, FUNGIBLE_TOKEN_ID, 100), (BOB, FUNGIBLE_TOKEN_ID, 100)])
+		.build()
+		.execute_with(|| {
+			let reserve_account: AccountId = CurrenciesPalletId::get().into_account();
+
+			assert_ok!(Currencies::reserve(FUNGIBLE_TOKEN_ID, &ALICE, 30));
+			assert_eq!(Currencies::free_balance(FUNGIBLE_TOKEN_ID, &ALICE), 70);
+			assert_eq!(Currencies::reserved_balance(FUNGIBLE_TOKEN_ID, &ALICE), 30);
+			assert_eq!(Currencies::total_balance(FUNGIBLE_TOKEN_ID, &ALICE), 100);
+			assert_eq!(Assets::balance(FUNGIBLE_TOKEN_ID, reserve_account.clone()), 30);
+			assert_eq!(System::providers(&reserve_account), 1);
+			assert_noop!(
+				Currencies::reserve(FUNGIBLE_TOKEN_ID, &ALICE, 100),
+				Error::<Runtime>::BalanceTooLow
+			);
+			// the reserving account is kept alive
+			assert!(!Currencies::can_reserve(FUNGIBLE_TOKEN_ID, &ALICE, 70));
+			assert_noop!(
+				Currencies::reserve(FUNGIBLE_TOKEN_ID, &ALICE, 70),
+				pallet_assets::Error::<Runtime>::BalanceLow
+			);
+
+			assert_eq!(Currencies::unreserve(FUNGIBLE_TOKEN_ID, &ALICE, 10), 0);
+			assert_eq!(Currencies::free_balance(FUNGIBLE_TOKEN_ID, &ALICE), 80);
+			assert_eq!(Currencies::reserved_balance(FUNGIBLE_TOKEN_ID, &ALICE), 20);
+
+			assert_eq!(Currencies::slash_reserved(FUNGIBLE_TOKEN_ID, &ALICE, 5), 0);
+			assert_eq!(Currencies::reserved_balance(FUNGIBLE_TOKEN_ID, &ALICE), 15);
+			assert_eq!(Currencies::total_issuance(FUNGIBLE_TOKEN_ID), 195);
+
+			assert_eq!(
+				Currencies::repatriate_reserved(FUNGIBLE_TOKEN_ID, &ALICE, &BOB, 10, BalanceStatus::Free),
+				Ok(0)
+			);
+			assert_eq!(Currencies::reserved_balance(FUNGIBLE_TOKEN_ID, &ALICE), 5);
+			assert_eq!(Currencies::free_balance(FUNGIBLE_TOKEN_ID, &BOB), 110);
+
+			assert_eq!(
+				Currencies::repatriate_reserved(FUNGIBLE_TOKEN_ID, &ALICE, &BOB, 10, BalanceStatus::Reserved),
+				Ok(5)
+			);
+			assert_eq!(Currencies::reserved_balance(FUNGIBLE_TOKEN_ID, &ALICE), 0);
+			assert_eq!(Currencies::reserved_balance(FUNGIBLE_TOKEN_ID, &BOB), 5);
+			assert_eq!(Assets::balance(FUNGIBLE_TOKEN_ID, reserve_account), 5);
+		});
+}
+
+#[test]
+fn fungibles_currency_reserves_should_not_be_swept() {
+	ExtBuilder::default()
+		.balances(vec![
+			(ALICE, MIN_BALANCE_FUNGIBLE_TOKEN_ID, 100),
+			(BOB, MIN_BALANCE_FUNGIBLE_TOKEN_ID, 100),
+		])
+		.build()
+		.execute_with(|| {
+			let reserve_account: AccountId = CurrenciesPalletId::get().into_account();
+
+			assert_ok!(Currencies::reserve(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &ALICE, 30));
+			assert_ok!(Currencies::reserve(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &BOB, 5));
+			assert_eq!(
+				Assets::balance(MIN_BALANCE_FUNGIBLE_TOKEN_ID, reserve_account.clone()),
+				35
+			);
+
+			// the reserve account is kept at the minimum balance of 10 for the
+			// reserved balance of BOB
+			assert_eq!(Currencies::unreserve(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &ALICE, 30), 5);
+			assert_eq!(Currencies::free_balance(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &ALICE), 95);
+			assert_eq!(Currencies::reserved_balance(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &ALICE), 5);
+			assert_eq!(
+				Assets::balance(MIN_BALANCE_FUNGIBLE_TOKEN_ID, reserve_account.clone()),
+				10
+			);
+
+			assert_eq!(Currencies::slash_reserved(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &ALICE, 5), 5);
+			assert_eq!(
+				Currencies::repatriate_reserved(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &ALICE, &BOB, 5, BalanceStatus::Free),
+				Ok(5)
+			);
+			assert_eq!(Currencies::reserved_balance(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &ALICE), 5);
+			assert_eq!(Currencies::reserved_balance(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &BOB), 5);
+			assert_eq!(Currencies::free_balance(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &BOB), 95);
+			assert_eq!(Currencies::total_issuance(MIN_BALANCE_FUNGIBLE_TOKEN_ID), 200);
+
+			// the last reserved balance reaps the reserve account
+			assert_eq!(
+				Currencies::repatriate_reserved(
+					MIN_BALANCE_FUNGIBLE_TOKEN_ID,
+					&ALICE,
+					&BOB,
+					5,
+					BalanceStatus::Reserved
+				),
+				Ok(0)
+			);
+			assert_eq!(Currencies::unreserve(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &BOB, 10), 0);
+			assert_eq!(Currencies::free_balance(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &BOB), 105);
+			assert_eq!(Currencies::reserved_balance(MIN_BALANCE_FUNGIBLE_TOKEN_ID, &BOB), 0);
+			assert_eq!(Assets::balance(MIN_BALANCE_FUNGIBLE_TOKEN_ID, reserve_account), 0);
+			assert_eq!(Currencies::total_issuance(MIN_BALANCE_FUNGIBLE_TOKEN_ID), 200);
+		});
+}
+
+#[test]
+fn fungibles_currency_lockable_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, FUNGIBLE_TOKEN_ID, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::set_lock(ID_1, FUNGIBLE_TOKEN_ID, &ALICE, 50));
+			assert_eq!(Currencies::fungibles_locks(FUNGIBLE_TOKEN_ID, &ALICE).len(), 1);
+			assert_ok!(Currencies::extend_lock(ID_1, FUNGIBLE_TOKEN_ID, &ALICE, 40));
+			// `pallet_assets` keeps the minimum balance on top of the locks
+			assert_eq!(Currencies::reducible_balance(FUNGIBLE_TOKEN_ID, &ALICE, false), 49);
+
+			assert_noop!(
+				Currencies::transfer(Some(ALICE).into(), BOB, FUNGIBLE_TOKEN_ID, 60),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+			assert_noop!(
+				Currencies::reserve(FUNGIBLE_TOKEN_ID, &ALICE, 60),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+			assert_noop!(
+				Currencies::transfer(Some(ALICE).into(), BOB, FUNGIBLE_TOKEN_ID, 50),
+				TokenError::Frozen
+			);
+			// the locks are respected by `pallet_assets` too
+			assert_noop!(
+				Assets::transfer(Origin::signed(ALICE), FUNGIBLE_TOKEN_ID, BOB, 50),
+				pallet_assets::Error::<Runtime>::BalanceLow
+			);
+			assert_ok!(Currencies::transfer(Some(ALICE).into(), BOB, FUNGIBLE_TOKEN_ID, 49));
+
+			assert_ok!(Currencies::remove_lock(ID_1, FUNGIBLE_TOKEN_ID, &ALICE));
+			assert!(Currencies::fungibles_locks(FUNGIBLE_TOKEN_ID, &ALICE).is_empty());
+			assert_ok!(Assets::transfer(Origin::signed(ALICE), FUNGIBLE_TOKEN_ID, BOB, 51));
+			assert_eq!(Currencies::free_balance(FUNGIBLE_TOKEN_ID, &BOB), 100);
+		});
+}
+
+#[test]
+fn fungibles_currency_held_currencies_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, NATIVE_CURRENCY_ID, 100), (ALICE, FUNGIBLE_TOKEN_ID, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				<Currencies as MultiReducibleCurrency<AccountId>>::held_currencies(&ALICE, 10),
+				vec![FUNGIBLE_TOKEN_ID, NATIVE_CURRENCY_ID]
+			);
+			assert_eq!(
+				<Currencies as MultiReducibleCurrency<AccountId>>::held_currencies(&BOB, 10),
+				vec![]
+			);
+
+			assert_ok!(Currencies::transfer_all_currencies(Some(ALICE).into(), BOB, false));
+			assert_eq!(Currencies::free_balance(FUNGIBLE_TOKEN_ID, &ALICE), 0);
+			assert_eq!(Currencies::free_balance(FUNGIBLE_TOKEN_ID, &BOB), 100);
+			assert_eq!(NativeCurrency::free_balance(&BOB), 100);
+		});
+}
+
+#[test]
+fn erc20_currency_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, ERC20_TOKEN_ID, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Currencies::total_issuance(ERC20_TOKEN_ID), 100);
+			assert_eq!(Currencies::total_balance(ERC20_TOKEN_ID, &ALICE), 100);
+			assert_eq!(Tokens::free_balance(ERC20_TOKEN_ID, &ALICE), 0);
+
+			assert_ok!(Currencies::transfer(Some(ALICE).into(), BOB, ERC20_TOKEN_ID, 40));
+			assert_eq!(Currencies::free_balance(ERC20_TOKEN_ID, &ALICE), 60);
+			assert_eq!(Currencies::free_balance(ERC20_TOKEN_ID, &BOB), 40);
+			assert_eq!(MockErc20::balance_of(ERC20_ADDRESS, &BOB), Ok(40));
+
+			assert_ok!(Currencies::deposit(ERC20_TOKEN_ID, &BOB, 10));
+			assert_eq!(Currencies::free_balance(ERC20_TOKEN_ID, &BOB), 50);
+			assert_eq!(Currencies::total_issuance(ERC20_TOKEN_ID), 110);
+
+			assert_ok!(Currencies::withdraw(ERC20_TOKEN_ID, &BOB, 20));
+			assert_eq!(Currencies::free_balance(ERC20_TOKEN_ID, &BOB), 30);
+			assert_eq!(Currencies::total_issuance(ERC20_TOKEN_ID), 90);
+
+			assert_ok!(Currencies::update_balance(ERC20_TOKEN_ID, &BOB, -10));
+			assert_eq!(Currencies::free_balance(ERC20_TOKEN_ID, &BOB), 20);
+			assert_ok!(Currencies::update_balance(ERC20_TOKEN_ID, &BOB, 5));
+			assert_eq!(Currencies::free_balance(ERC20_TOKEN_ID, &BOB), 25);
+
+			assert_eq!(Currencies::slash(ERC20_TOKEN_ID, &BOB, 30), 5);
+			assert_eq!(Currencies::free_balance(ERC20_TOKEN_ID, &BOB), 0);
+		});
+}
+
+#[test]
+fn erc20_currency_failures_should_map_to_errors() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, ERC20_TOKEN_ID, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Currencies::transfer(Some(ALICE).into(), BOB, ERC20_TOKEN_ID, 101),
+				Error::<Runtime>::BalanceTooLow
+			);
+			assert_noop!(
+				Currencies::transfer(Some(ALICE).into(), EVA, ERC20_TOKEN_ID, 10),
+				Error::<Runtime>::Erc20Reverted
+			);
+			assert_noop!(
+				Currencies::transfer(Some(ALICE).into(), BOB, OUT_OF_GAS_ERC20_TOKEN_ID, 10),
+				Error::<Runtime>::Erc20OutOfGas
+			);
+			assert_noop!(
+				Currencies::deposit(UNDEPLOYED_ERC20_TOKEN_ID, &ALICE, 10),
+				Error::<Runtime>::Erc20ContractNotFound
+			);
+			assert_eq!(Currencies::free_balance(UNDEPLOYED_ERC20_TOKEN_ID, &ALICE), 0);
+		});
+}
+
+#[test]
+fn erc20_currency_should_not_support_locks_and_reserves() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, ERC20_TOKEN_ID, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Currencies::set_lock(ID_1, ERC20_TOKEN_ID, &ALICE, 50),
+				Error::<Runtime>::Erc20Unsupported
+			);
+			assert!(!Currencies::can_reserve(ERC20_TOKEN_ID, &ALICE, 50));
+			assert_noop!(
+				Currencies::reserve(ERC20_TOKEN_ID, &ALICE, 50),
+				Error::<Runtime>::Erc20Unsupported
+			);
+			assert_eq!(Currencies::reserved_balance(ERC20_TOKEN_ID, &ALICE), 0);
+			assert_eq!(Currencies::unreserve(ERC20_TOKEN_ID, &ALICE, 50), 50);
+		});
+}
+
+#[test]
+fn fungibles_balanced_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			for currency_id in [NATIVE_CURRENCY_ID, X_TOKEN_ID] {
+				let credit = <Currencies as fungibles::Balanced<_>>::withdraw(currency_id, &ALICE, 30).unwrap();
+				assert_eq!(credit.peek(), 30);
+				assert_eq!(Currencies::free_balance(currency_id, &ALICE), 70);
+				assert!(<Currencies as fungibles::Balanced<_>>::resolve(&BOB, credit).is_ok());
+				assert_eq!(Currencies::free_balance(currency_id, &BOB), 130);
+				assert_eq!(Currencies::total_issuance(currency_id), 200);
+
+				drop(<Currencies as fungibles::Balanced<_>>::withdraw(currency_id, &ALICE, 20).unwrap());
+				assert_eq!(Currencies::total_issuance(currency_id), 180);
+				drop(<Currencies as fungibles::Balanced<_>>::deposit(currency_id, &ALICE, 10).unwrap());
+				assert_eq!(Currencies::free_balance(currency_id, &ALICE), 60);
+				assert_eq!(Currencies::total_issuance(currency_id), 190);
+			}
+		});
+}
+
+#[test]
+fn fungibles_balanced_erc20_currency_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, ERC20_TOKEN_ID, 100)])
+		.build()
+		.execute_with(|| {
+			let credit = <Currencies as fungibles::Balanced<_>>::withdraw(ERC20_TOKEN_ID, &ALICE, 30).unwrap();
+			assert_eq!(Currencies::free_balance(ERC20_TOKEN_ID, &ALICE), 70);
+			assert_eq!(Currencies::total_issuance(ERC20_TOKEN_ID), 70);
+
+			assert!(<Currencies as fungibles::Balanced<_>>::resolve(&BOB, credit).is_ok());
+			assert_eq!(Currencies::free_balance(ERC20_TOKEN_ID, &BOB), 30);
+			assert_eq!(Currencies::total_issuance(ERC20_TOKEN_ID), 100);
+
+			assert!(<Currencies as fungibles::Balanced<_>>::withdraw(ERC20_TOKEN_ID, &ALICE, 71).is_err());
+			assert_noop!(
+				<Currencies as fungibles::Unbalanced<_>>::set_balance(ERC20_TOKEN_ID, &ALICE, 10),
+				Error::<Runtime>::Erc20Unsupported
+			);
+		});
+}
+
+#[test]
+fn split_two_ways_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let credit = <Currencies as fungibles::Balanced<_>>::withdraw(X_TOKEN_ID, &ALICE, 50).unwrap();
+			DealWithCredit::on_unbalanced(credit);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 50);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &TreasuryAccount::get()), 40);
+			assert_eq!(Currencies::total_issuance(X_TOKEN_ID), 190);
+		});
+}
+
+#[test]
+fn currency_adapter_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_eq!(AdaptedXToken::total_issuance(), 200);
+			assert_eq!(AdaptedXToken::free_balance(&ALICE), 100);
+
+			assert_ok!(AdaptedXToken::transfer(
+				&ALICE,
+				&BOB,
+				50,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_eq!(AdaptedXToken::free_balance(&ALICE), 50);
+			assert_eq!(AdaptedXToken::free_balance(&BOB), 150);
+
+			let imbalance =
+				AdaptedXToken::withdraw(&ALICE, 20, WithdrawReasons::all(), ExistenceRequirement::AllowDeath).unwrap();
+			assert_eq!(imbalance.peek(), 20);
+			assert_eq!(AdaptedXToken::free_balance(&ALICE), 30);
+			assert_eq!(AdaptedXToken::total_issuance(), 180);
+
+			let imbalance = AdaptedXToken::deposit_creating(&EVA, 20);
+			assert_eq!(imbalance.peek(), 20);
+			assert_eq!(AdaptedXToken::free_balance(&EVA), 20);
+			assert_eq!(AdaptedXToken::total_issuance(), 200);
+
+			let (imbalance, remaining) = AdaptedXToken::slash(&ALICE, 40);
+			assert_eq!((imbalance.peek(), remaining), (30, 10));
+			assert_eq!(AdaptedXToken::free_balance(&ALICE), 0);
+			assert_eq!(AdaptedXToken::total_issuance(), 170);
+
+			assert_noop!(
+				AdaptedXToken::deposit_into_existing(&ALICE, 10),
+				Error::<Runtime>::DeadAccount
+			);
+			assert_eq!(AdaptedXToken::deposit_into_existing(&BOB, 10).unwrap().peek(), 10);
+			assert_eq!(AdaptedXToken::free_balance(&BOB), 160);
+		});
+}
+
+#[test]
+fn currency_adapter_keep_alive_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				AdaptedNativeCurrency::transfer(&ALICE, &BOB, 100, ExistenceRequirement::KeepAlive),
+				Error::<Runtime>::KeepAlive
+			);
+			assert_noop!(
+				AdaptedNativeCurrency::withdraw(&ALICE, 100, WithdrawReasons::all(), ExistenceRequirement::KeepAlive),
+				Error::<Runtime>::KeepAlive
+			);
+			assert_ok!(AdaptedNativeCurrency::transfer(
+				&ALICE,
+				&BOB,
+				99,
+				ExistenceRequirement::KeepAlive
+			));
+			assert_eq!(AdaptedNativeCurrency::free_balance(&ALICE), 1);
+			assert_eq!(AdaptedNativeCurrency::free_balance(&BOB), 199);
+		});
+}
+
+#[test]
+fn currency_adapter_make_free_balance_be_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(AdaptedXToken::reserve(&ALICE, 30));
+			assert!(matches!(
+				AdaptedXToken::make_free_balance_be(&ALICE, 100),
+				SignedImbalance::Positive(imbalance) if imbalance.peek() == 30
+			));
+			assert_eq!(AdaptedXToken::free_balance(&ALICE), 100);
+			assert_eq!(AdaptedXToken::reserved_balance(&ALICE), 30);
+			assert_eq!(AdaptedXToken::total_issuance(), 230);
+
+			assert!(matches!(
+				AdaptedXToken::make_free_balance_be(&ALICE, 40),
+				SignedImbalance::Negative(imbalance) if imbalance.peek() == 60
+			));
+			assert_eq!(AdaptedXToken::free_balance(&ALICE), 40);
+			assert_eq!(AdaptedXToken::reserved_balance(&ALICE), 30);
+			assert_eq!(AdaptedXToken::total_issuance(), 170);
+		});
+}
+
+#[test]
+fn currency_adapter_reservable_and_lockable_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert!(AdaptedXToken::can_reserve(&ALICE, 100));
+			assert_ok!(AdaptedXToken::reserve(&ALICE, 50));
+			assert_eq!(AdaptedXToken::reserved_balance(&ALICE), 50);
+			assert_eq!(AdaptedXToken::unreserve(&ALICE, 10), 0);
+
+			let (imbalance, remaining) = AdaptedXToken::slash_reserved(&ALICE, 50);
+			assert_eq!((imbalance.peek(), remaining), (40, 10));
+			assert_eq!(AdaptedXToken::reserved_balance(&ALICE), 0);
+			assert_eq!(AdaptedXToken::total_issuance(), 160);
+
+			AdaptedXToken::set_lock(ID_1, &BOB, 50, WithdrawReasons::TRANSFER);
+			assert_eq!(Tokens::locks(&BOB, X_TOKEN_ID)[0].amount, 50);
+			assert_noop!(
+				AdaptedXToken::transfer(&BOB, &ALICE, 60, ExistenceRequirement::AllowDeath),
+				orml_tokens::Error::<Runtime>::LiquidityRestrictions
+			);
+			AdaptedXToken::extend_lock(ID_1, &BOB, 60, WithdrawReasons::TRANSFER);
+			assert_eq!(Tokens::locks(&BOB, X_TOKEN_ID)[0].amount, 60);
+			AdaptedXToken::remove_lock(ID_1, &BOB);
+			assert_eq!(Tokens::locks(&BOB, X_TOKEN_ID).len(), 0);
+
+			// a lock for transaction payment doesn't restrict transfers
+			AdaptedXToken::set_lock(ID_1, &BOB, 100, WithdrawReasons::TRANSACTION_PAYMENT);
+			assert_ok!(AdaptedXToken::transfer(
+				&BOB,
+				&ALICE,
+				60,
+				ExistenceRequirement::AllowDeath
+			));
+		});
+}
+
+#[test]
+fn currency_adapter_withdraw_should_respect_lock_reasons() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			AdaptedXToken::set_lock(ID_1, &BOB, 50, WithdrawReasons::TRANSFER);
+			assert_noop!(
+				AdaptedXToken::ensure_can_withdraw(&BOB, 60, WithdrawReasons::TRANSFER, 40),
+				orml_tokens::Error::<Runtime>::LiquidityRestrictions
+			);
+			assert_eq!(
+				AdaptedXToken::withdraw(&BOB, 60, WithdrawReasons::TRANSFER, ExistenceRequirement::AllowDeath)
+					.map(|imbalance| imbalance.peek()),
+				Err(orml_tokens::Error::<Runtime>::LiquidityRestrictions.into())
+			);
+			assert_ok!(AdaptedXToken::ensure_can_withdraw(
+				&BOB,
+				60,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				40
+			));
+			let imbalance = AdaptedXToken::withdraw(
+				&BOB,
+				60,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				ExistenceRequirement::AllowDeath,
+			)
+			.unwrap();
+			assert_eq!(imbalance.peek(), 60);
+			assert_eq!(AdaptedXToken::free_balance(&BOB), 40);
+
+			// the native currency is routed to the reasons of `pallet_balances` locks
+			AdaptedNativeCurrency::set_lock(ID_1, &BOB, 50, WithdrawReasons::TRANSFER);
+			assert_noop!(
+				AdaptedNativeCurrency::ensure_can_withdraw(&BOB, 60, WithdrawReasons::TRANSFER, 40),
+				pallet_balances::Error::<Runtime>::LiquidityRestrictions
+			);
+			assert_ok!(AdaptedNativeCurrency::ensure_can_withdraw(
+				&BOB,
+				60,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				40
+			));
+			let imbalance = AdaptedNativeCurrency::withdraw(
+				&BOB,
+				60,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				ExistenceRequirement::AllowDeath,
+			)
+			.unwrap();
+			assert_eq!(imbalance.peek(), 60);
+			assert_eq!(AdaptedNativeCurrency::free_balance(&BOB), 40);
+		});
+}
@@ -35,6 +35,7 @@ pub trait WeightInfo {
 	fn update_balance_non_native_currency() -> Weight;
 	fn update_balance_native_currency_creating() -> Weight;
 	fn update_balance_native_currency_killing() -> Weight;
+	fn transfer_all_currencies(c: u32) -> Weight;
 }
 
 /// Default weights.
@@ -64,4 +65,14 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `transfer_all_currencies` of `benchmarking.rs` is run.
+	fn transfer_all_currencies(c: u32) -> Weight {
+		(68_000_000 as Weight)
+			.saturating_add((64_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)))
+	}
 }
@@ -0,0 +1,31 @@
+//! Runtime API definition for tokens module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::Vec;
+
+pub use orml_traits::tokens::{BalanceLock, CurrencyMetadata, Reasons};
+
+sp_api::decl_runtime_apis! {
+	pub trait TokensApi<CurrencyId, AccountId, Balance> where
+		CurrencyId: Codec,
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		fn holders_count(currency_id: CurrencyId) -> u32;
+		fn holders(currency_id: CurrencyId, start_after: Option<AccountId>, limit: u32) -> Option<Vec<AccountId>>;
+		fn free_balance(currency_id: CurrencyId, who: AccountId) -> Balance;
+		fn reserved_balance(currency_id: CurrencyId, who: AccountId) -> Balance;
+		fn frozen(currency_id: CurrencyId, who: AccountId, reasons: Reasons) -> Balance;
+		fn locks(currency_id: CurrencyId, who: AccountId) -> Vec<BalanceLock<Balance>>;
+		fn reducible_balance(currency_id: CurrencyId, who: AccountId, keep_alive: bool) -> Balance;
+		fn total_issuance(currency_id: CurrencyId) -> Balance;
+		fn existential_deposit(currency_id: CurrencyId) -> Balance;
+		fn metadata(currency_id: CurrencyId) -> CurrencyMetadata<Vec<u8>>;
+	}
+}
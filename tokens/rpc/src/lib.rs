@@ -0,0 +1,277 @@
+use std::{fmt::Display, sync::Arc};
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use self::gen_client::Client as TokensClient;
+pub use orml_tokens_rpc_runtime_api::{BalanceLock, CurrencyMetadata, Reasons, TokensApi as TokensRuntimeApi};
+
+/// A number encoded as a decimal string, so that big numbers are not
+/// truncated by JSON parsers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct NumberString(pub String);
+
+impl NumberString {
+	pub fn new(number: impl Display) -> Self {
+		NumberString(number.to_string())
+	}
+}
+
+/// A balance lock, with the amount encoded as `NumberString`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BalanceLockInfo {
+	pub id: [u8; 8],
+	pub amount: NumberString,
+	pub reasons: Reasons,
+}
+
+impl<Balance: Display> From<BalanceLock<Balance>> for BalanceLockInfo {
+	fn from(lock: BalanceLock<Balance>) -> Self {
+		BalanceLockInfo {
+			id: lock.id,
+			amount: NumberString::new(lock.amount),
+			reasons: lock.reasons,
+		}
+	}
+}
+
+/// The metadata of a currency, with the name and symbol decoded as UTF-8.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CurrencyMetadataInfo {
+	pub name: String,
+	pub symbol: String,
+	pub decimals: u8,
+}
+
+impl From<CurrencyMetadata<Vec<u8>>> for CurrencyMetadataInfo {
+	fn from(metadata: CurrencyMetadata<Vec<u8>>) -> Self {
+		CurrencyMetadataInfo {
+			name: String::from_utf8_lossy(&metadata.name).into_owned(),
+			symbol: String::from_utf8_lossy(&metadata.symbol).into_owned(),
+			decimals: metadata.decimals,
+		}
+	}
+}
+
+#[rpc]
+pub trait TokensApi<BlockHash, CurrencyId, AccountId, Balance> {
+	#[rpc(name = "tokens_holdersCount")]
+	fn holders_count(&self, currency_id: CurrencyId, at: Option<BlockHash>) -> Result<u32>;
+	#[rpc(name = "tokens_holders")]
+	fn holders(
+		&self,
+		currency_id: CurrencyId,
+		start_after: Option<AccountId>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Option<Vec<AccountId>>>;
+	#[rpc(name = "tokens_freeBalance")]
+	fn free_balance(&self, currency_id: CurrencyId, who: AccountId, at: Option<BlockHash>) -> Result<NumberString>;
+	#[rpc(name = "tokens_reservedBalance")]
+	fn reserved_balance(&self, currency_id: CurrencyId, who: AccountId, at: Option<BlockHash>) -> Result<NumberString>;
+	#[rpc(name = "tokens_frozen")]
+	fn frozen(
+		&self,
+		currency_id: CurrencyId,
+		who: AccountId,
+		reasons: Reasons,
+		at: Option<BlockHash>,
+	) -> Result<NumberString>;
+	#[rpc(name = "tokens_locks")]
+	fn locks(&self, currency_id: CurrencyId, who: AccountId, at: Option<BlockHash>) -> Result<Vec<BalanceLockInfo>>;
+	#[rpc(name = "tokens_reducibleBalance")]
+	fn reducible_balance(
+		&self,
+		currency_id: CurrencyId,
+		who: AccountId,
+		keep_alive: bool,
+		at: Option<BlockHash>,
+	) -> Result<NumberString>;
+	#[rpc(name = "tokens_totalIssuance")]
+	fn total_issuance(&self, currency_id: CurrencyId, at: Option<BlockHash>) -> Result<NumberString>;
+	#[rpc(name = "tokens_existentialDeposit")]
+	fn existential_deposit(&self, currency_id: CurrencyId, at: Option<BlockHash>) -> Result<NumberString>;
+	#[rpc(name = "tokens_metadata")]
+	fn metadata(&self, currency_id: CurrencyId, at: Option<BlockHash>) -> Result<CurrencyMetadataInfo>;
+}
+
+/// A struct that implements the [`TokensApi`].
+pub struct Tokens<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Tokens<C, B> {
+	/// Create new `Tokens` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Tokens {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<C, Block> Tokens<C, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	fn block_id(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		))
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(message: &str, e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: message.into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, CurrencyId, AccountId, Balance> TokensApi<<Block as BlockT>::Hash, CurrencyId, AccountId, Balance>
+	for Tokens<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: TokensRuntimeApi<Block, CurrencyId, AccountId, Balance>,
+	CurrencyId: Codec,
+	AccountId: Codec,
+	Balance: Codec + Display,
+{
+	fn holders_count(&self, currency_id: CurrencyId, at: Option<<Block as BlockT>::Hash>) -> Result<u32> {
+		self.client
+			.runtime_api()
+			.holders_count(&self.block_id(at), currency_id)
+			.map_err(|e| runtime_error("Unable to get holders count.", e))
+	}
+
+	fn holders(
+		&self,
+		currency_id: CurrencyId,
+		start_after: Option<AccountId>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Vec<AccountId>>> {
+		self.client
+			.runtime_api()
+			.holders(&self.block_id(at), currency_id, start_after, limit)
+			.map_err(|e| runtime_error("Unable to get holders.", e))
+	}
+
+	fn free_balance(
+		&self,
+		currency_id: CurrencyId,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<NumberString> {
+		self.client
+			.runtime_api()
+			.free_balance(&self.block_id(at), currency_id, who)
+			.map(NumberString::new)
+			.map_err(|e| runtime_error("Unable to get free balance.", e))
+	}
+
+	fn reserved_balance(
+		&self,
+		currency_id: CurrencyId,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<NumberString> {
+		self.client
+			.runtime_api()
+			.reserved_balance(&self.block_id(at), currency_id, who)
+			.map(NumberString::new)
+			.map_err(|e| runtime_error("Unable to get reserved balance.", e))
+	}
+
+	fn frozen(
+		&self,
+		currency_id: CurrencyId,
+		who: AccountId,
+		reasons: Reasons,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<NumberString> {
+		self.client
+			.runtime_api()
+			.frozen(&self.block_id(at), currency_id, who, reasons)
+			.map(NumberString::new)
+			.map_err(|e| runtime_error("Unable to get frozen balance.", e))
+	}
+
+	fn locks(
+		&self,
+		currency_id: CurrencyId,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<BalanceLockInfo>> {
+		self.client
+			.runtime_api()
+			.locks(&self.block_id(at), currency_id, who)
+			.map(|locks| locks.into_iter().map(Into::into).collect())
+			.map_err(|e| runtime_error("Unable to get locks.", e))
+	}
+
+	fn reducible_balance(
+		&self,
+		currency_id: CurrencyId,
+		who: AccountId,
+		keep_alive: bool,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<NumberString> {
+		self.client
+			.runtime_api()
+			.reducible_balance(&self.block_id(at), currency_id, who, keep_alive)
+			.map(NumberString::new)
+			.map_err(|e| runtime_error("Unable to get reducible balance.", e))
+	}
+
+	fn total_issuance(&self, currency_id: CurrencyId, at: Option<<Block as BlockT>::Hash>) -> Result<NumberString> {
+		self.client
+			.runtime_api()
+			.total_issuance(&self.block_id(at), currency_id)
+			.map(NumberString::new)
+			.map_err(|e| runtime_error("Unable to get total issuance.", e))
+	}
+
+	fn existential_deposit(
+		&self,
+		currency_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<NumberString> {
+		self.client
+			.runtime_api()
+			.existential_deposit(&self.block_id(at), currency_id)
+			.map(NumberString::new)
+			.map_err(|e| runtime_error("Unable to get existential deposit.", e))
+	}
+
+	fn metadata(&self, currency_id: CurrencyId, at: Option<<Block as BlockT>::Hash>) -> Result<CurrencyMetadataInfo> {
+		self.client
+			.runtime_api()
+			.metadata(&self.block_id(at), currency_id)
+			.map(Into::into)
+			.map_err(|e| runtime_error("Unable to get metadata.", e))
+	}
+}
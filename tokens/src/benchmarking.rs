@@ -0,0 +1,165 @@
+//! Benchmarks for the tokens module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::One;
+
+const SEED: u32 = 0;
+
+benchmarks! {
+	where_clause { where T::CurrencyId: Default }
+
+	transfer_multiple {
+		let c in 1 .. T::MaxTransfers::get();
+
+		let currency_id = T::CurrencyId::default();
+		let amount = T::ExistentialDeposits::get(&currency_id)
+			.max(One::one())
+			.saturating_mul(1_000u32.into());
+		let from: T::AccountId = whitelisted_caller();
+		<Pallet<T> as MultiCurrency<_>>::deposit(currency_id, &from, amount.saturating_mul(c.into()))?;
+
+		// every transfer creates a new account
+		let transfers = (0..c)
+			.map(|i| (currency_id, T::Lookup::unlookup(account("to", i, SEED)), amount))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("at most `MaxTransfers`; qed");
+	}: _(RawOrigin::Signed(from), transfers)
+	verify {
+		let to: T::AccountId = account("to", c - 1, SEED);
+		assert_eq!(Pallet::<T>::free_balance(currency_id, &to), amount);
+	}
+
+	freeze_account {
+		let currency_id = T::CurrencyId::default();
+		let who: T::AccountId = account("who", 0, SEED);
+	}: _(T::FreezeOrigin::successful_origin(), currency_id, T::Lookup::unlookup(who.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::frozen_accounts(currency_id, &who), Some(()));
+	}
+
+	thaw_account {
+		let currency_id = T::CurrencyId::default();
+		let who: T::AccountId = account("who", 0, SEED);
+		FrozenAccounts::<T>::insert(currency_id, &who, ());
+	}: _(T::FreezeOrigin::successful_origin(), currency_id, T::Lookup::unlookup(who.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::frozen_accounts(currency_id, &who), None);
+	}
+
+	freeze_currency {
+		let currency_id = T::CurrencyId::default();
+	}: _(T::FreezeOrigin::successful_origin(), currency_id)
+	verify {
+		assert_eq!(Pallet::<T>::frozen_currencies(currency_id), Some(()));
+	}
+
+	thaw_currency {
+		let currency_id = T::CurrencyId::default();
+		FrozenCurrencies::<T>::insert(currency_id, ());
+	}: _(T::FreezeOrigin::successful_origin(), currency_id)
+	verify {
+		assert_eq!(Pallet::<T>::frozen_currencies(currency_id), None);
+	}
+
+	// the name and symbol are `n` bytes in total
+	set_metadata {
+		let n in 0 .. 2 * T::StringLimit::get();
+
+		let currency_id = T::CurrencyId::default();
+		let name = sp_std::vec![b'a'; (n / 2) as usize];
+		let symbol = sp_std::vec![b'a'; (n - n / 2) as usize];
+	}: _(T::MetadataOrigin::successful_origin(), currency_id, name.clone(), symbol.clone(), 12)
+	verify {
+		let metadata = Pallet::<T>::metadata(currency_id);
+		assert_eq!(metadata.name.into_inner(), name);
+		assert_eq!(metadata.symbol.into_inner(), symbol);
+	}
+
+	// a new approval, which reserves the deposit
+	approve_transfer {
+		let currency_id = T::CurrencyId::default();
+		let amount = T::ExistentialDeposits::get(&currency_id)
+			.max(One::one())
+			.saturating_mul(1_000u32.into());
+		let owner: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let deposit = T::ApprovalDeposit::get();
+		T::DepositCurrency::make_free_balance_be(&owner, T::DepositCurrency::minimum_balance().saturating_add(deposit));
+	}: _(RawOrigin::Signed(owner.clone()), T::Lookup::unlookup(delegate.clone()), currency_id, amount)
+	verify {
+		assert_eq!(Pallet::<T>::approvals((currency_id, &owner, &delegate)).map(|approval| approval.amount), Some(amount));
+		assert_eq!(T::DepositCurrency::reserved_balance(&owner), deposit);
+	}
+
+	cancel_approval {
+		let currency_id = T::CurrencyId::default();
+		let amount = T::ExistentialDeposits::get(&currency_id)
+			.max(One::one())
+			.saturating_mul(1_000u32.into());
+		let owner: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		T::DepositCurrency::make_free_balance_be(
+			&owner,
+			T::DepositCurrency::minimum_balance().saturating_add(T::ApprovalDeposit::get()),
+		);
+		<Pallet<T> as orml_traits::Approvals<_>>::approve(currency_id, &owner, &delegate, amount)?;
+	}: _(RawOrigin::Signed(owner.clone()), T::Lookup::unlookup(delegate.clone()), currency_id)
+	verify {
+		assert!(Pallet::<T>::approvals((currency_id, &owner, &delegate)).is_none());
+		assert!(T::DepositCurrency::reserved_balance(&owner).is_zero());
+	}
+
+	// the transfer uses up the allowance, which unreserves the deposit, reaps
+	// the owner and creates the recipient
+	transfer_approved {
+		let currency_id = T::CurrencyId::default();
+		let amount = T::ExistentialDeposits::get(&currency_id)
+			.max(One::one())
+			.saturating_mul(1_000u32.into());
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let delegate: T::AccountId = whitelisted_caller();
+		let to: T::AccountId = account("to", 0, SEED);
+		T::DepositCurrency::make_free_balance_be(
+			&owner,
+			T::DepositCurrency::minimum_balance().saturating_add(T::ApprovalDeposit::get()),
+		);
+		<Pallet<T> as MultiCurrency<_>>::deposit(currency_id, &owner, amount)?;
+		<Pallet<T> as orml_traits::Approvals<_>>::approve(currency_id, &owner, &delegate, amount)?;
+	}: _(
+		RawOrigin::Signed(delegate.clone()),
+		T::Lookup::unlookup(owner.clone()),
+		T::Lookup::unlookup(to.clone()),
+		currency_id,
+		amount
+	)
+	verify {
+		assert_eq!(Pallet::<T>::free_balance(currency_id, &to), amount);
+		assert!(Pallet::<T>::approvals((currency_id, &owner, &delegate)).is_none());
+		assert!(T::DepositCurrency::reserved_balance(&owner).is_zero());
+	}
+
+	// the flush of one currency, which creates the account of the dust receiver
+	flush_dust {
+		let currency_id = T::CurrencyId::default();
+		let amount = T::ExistentialDeposits::get(&currency_id)
+			.max(One::one())
+			.saturating_mul(1_000u32.into());
+		// accumulated dust is still part of the total issuance
+		AccumulatedDust::<T>::insert(currency_id, amount);
+		TotalIssuance::<T>::mutate(currency_id, |total_issuance| *total_issuance = total_issuance.saturating_add(amount));
+		let remaining_weight = T::WeightInfo::flush_dust().saturating_add(T::DbWeight::get().reads(1));
+	}: {
+		Pallet::<T>::flush_dust(remaining_weight);
+	}
+	verify {
+		assert!(Pallet::<T>::accumulated_dust(currency_id).is_zero());
+		assert_eq!(Pallet::<T>::free_balance(currency_id, &T::DustReceiver::get()), amount);
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime,);
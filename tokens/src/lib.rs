@@ -12,6 +12,7 @@
 //! - Balance transfer between accounts.
 //! - Depositing and withdrawing balance.
 //! - Slashing an account balance.
+//! - Counting, and optionally indexing, the holders of each currency.
 //!
 //! ### Implementations
 //!
@@ -21,6 +22,10 @@
 //! - `MultiCurrencyExtended` - Extended `MultiCurrency` with additional helper
 //!   types and methods, like updating balance
 //! by a given signed integer amount.
+//! - `fungibles::metadata::Inspect` / `fungibles::metadata::Mutate` - The name,
+//!   symbol and decimals of each currency.
+//! - `fungibles::Unbalanced` / `fungibles::Balanced` - Move balances by
+//!   multi-currency credits and debts, see `CreditOf` and `DebtOf`.
 //!
 //! ## Interface
 //!
@@ -28,29 +33,48 @@
 //!
 //! - `transfer` - Transfer some balance to another account.
 //! - `transfer_all` - Transfer all balance to another account.
+//! - `transfer_multiple` - Transfer some balance to multiple accounts, all or
+//!   nothing.
+//! - `approve_transfer` / `cancel_approval` - Approve or stop a delegate
+//!   transferring from the caller's balance.
+//! - `transfer_approved` - Transfer some balance of another account, within an
+//!   approved allowance.
+//! - `freeze_account` / `thaw_account` - Freeze or thaw an account under a
+//!   given currency, privileged origin required.
+//! - `freeze_currency` / `thaw_currency` - Freeze or thaw all accounts of a
+//!   given currency, privileged origin required.
+//! - `set_metadata` - Set the name, symbol and decimals of a currency,
+//!   privileged origin required.
 //!
 //! ### Genesis Config
 //!
-//! The tokens module depends on the `GenesisConfig`. Endowed accounts could be
-//! configured in genesis configs.
+//! The tokens module depends on the `GenesisConfig`. Endowed accounts, and the
+//! metadata of currencies, could be configured in genesis configs.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 #![allow(clippy::comparison_chain)]
 
 pub use crate::imbalances::{NegativeImbalance, PositiveImbalance};
+pub use orml_traits::tokens::{BalanceLock, CurrencyMetadata, Reasons};
 
 use codec::MaxEncodedLen;
+
 use frame_support::{
 	ensure, log,
 	pallet_prelude::*,
+	storage::{KeyPrefixIterator, StoragePrefixedMap},
 	traits::{
-		tokens::{fungible, fungibles, DepositConsequence, WithdrawConsequence},
-		BalanceStatus as Status, Contains, Currency as PalletCurrency, ExistenceRequirement, Get, Imbalance,
-		LockableCurrency as PalletLockableCurrency, ReservableCurrency as PalletReservableCurrency, SignedImbalance,
-		WithdrawReasons,
+		tokens::{
+			fungible,
+			fungibles::{self, metadata},
+			DepositConsequence, WithdrawConsequence,
+		},
+		BalanceStatus as Status, Contains, Currency as PalletCurrency, EnsureOrigin, ExistenceRequirement, Get,
+		Imbalance, LockableCurrency as PalletLockableCurrency, ReservableCurrency as PalletReservableCurrency,
+		SignedImbalance, StorageVersion, WithdrawReasons,
 	},
-	transactional, BoundedVec,
+	transactional, BoundedVec, ReversibleStorageHasher, StorageHasher,
 };
 use frame_system::{ensure_signed, pallet_prelude::*};
 use sp_runtime::{
@@ -58,9 +82,10 @@ use sp_runtime::{
 		AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, Saturating,
 		StaticLookup, Zero,
 	},
-	ArithmeticError, DispatchError, DispatchResult, RuntimeDebug,
+	ArithmeticError, DispatchError, DispatchResult, RuntimeDebug, SaturatedConversion, TokenError,
 };
 use sp_std::{
+	cmp,
 	convert::{Infallible, TryFrom, TryInto},
 	marker,
 	prelude::*,
@@ -71,16 +96,22 @@ use orml_traits::{
 	arithmetic::{self, Signed},
 	currency::TransferAll,
 	BalanceStatus, GetByKey, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
-	MultiReservableCurrency, OnDust,
+	MultiReducibleCurrency, MultiReservableCurrency, NamedMultiReservableCurrency, OnDeposit, OnDust,
+	OnKilledTokenAccount, OnNewTokenAccount, OnSlash, OnTransfer, OnWithdraw,
 };
 
+mod benchmarking;
 mod imbalances;
+pub mod migrations;
 mod mock;
 mod tests;
 mod weights;
 
 pub use weights::WeightInfo;
 
+/// The max number of accounts returned by a page of `Pallet::holders_of`.
+pub const MAX_HOLDERS_PAGE: u32 = 1_000;
+
 pub struct TransferDust<T, GetAccountId>(marker::PhantomData<(T, GetAccountId)>);
 impl<T, GetAccountId> OnDust<T::AccountId, T::CurrencyId, T::Balance> for TransferDust<T, GetAccountId>
 where
@@ -100,6 +131,19 @@ where
 	}
 }
 
+/// Accumulate dust per currency in `AccumulatedDust` instead of moving it
+/// right away, to be deposited to `T::DustReceiver` in batches in `on_idle`.
+pub struct AccumulateDust<T>(marker::PhantomData<T>);
+impl<T: Config> OnDust<T::AccountId, T::CurrencyId, T::Balance> for AccumulateDust<T> {
+	fn on_dust(who: &T::AccountId, currency_id: T::CurrencyId, amount: T::Balance) {
+		// withdraw the dust but keep it in total issuance, ignore the result,
+		// if failed will leave some dust which still could be recycled.
+		if Pallet::<T>::do_withdraw(currency_id, who, amount, ExistenceRequirement::AllowDeath, false).is_ok() {
+			AccumulatedDust::<T>::mutate(currency_id, |dust| *dust = dust.saturating_add(amount));
+		}
+	}
+}
+
 pub struct BurnDust<T>(marker::PhantomData<T>);
 impl<T: Config> OnDust<T::AccountId, T::CurrencyId, T::Balance> for BurnDust<T> {
 	fn on_dust(who: &T::AccountId, currency_id: T::CurrencyId, amount: T::Balance) {
@@ -109,16 +153,36 @@ impl<T: Config> OnDust<T::AccountId, T::CurrencyId, T::Balance> for BurnDust<T>
 	}
 }
 
-/// A single lock on a balance. There can be many of these on an account and
-/// they "overlap", so the same balance is frozen by multiple locks.
+/// Store named reserved balance.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug)]
-pub struct BalanceLock<Balance> {
-	/// An identifier for this lock. Only one lock may be in existence for
-	/// each identifier.
-	pub id: LockIdentifier,
-	/// The amount which the free balance may not drop below when this lock
-	/// is in effect.
+pub struct ReserveData<ReserveIdentifier, Balance> {
+	/// The identifier for the named reserve.
+	pub id: ReserveIdentifier,
+	/// The amount of the named reserve.
+	pub amount: Balance,
+}
+
+pub type DepositBalanceOf<T> =
+	<<T as Config>::DepositCurrency as PalletCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The reference a token account adds to its account in `frame_system`.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum AccountReference {
+	/// A provider reference, of the `ProviderCurrencies`.
+	Provider,
+	/// A sufficient reference, of the `SufficientCurrencies`.
+	Sufficient,
+	/// A consumer reference, which needs an existing provider.
+	Consumer,
+}
+
+/// An approval for a delegate to transfer some balance of an owner.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug)]
+pub struct Approval<Balance, DepositBalance> {
+	/// The amount the delegate may still transfer.
 	pub amount: Balance,
+	/// The deposit reserved from the owner for the approval.
+	pub deposit: DepositBalance,
 }
 
 /// balance information for an account.
@@ -139,15 +203,23 @@ pub struct AccountData<Balance> {
 	/// order to set aside tokens that are still 'owned' by the account
 	/// holder, but which are suspendable.
 	pub reserved: Balance,
-	/// The amount that `free` may not drop below when withdrawing.
-	pub frozen: Balance,
+	/// The amount that `free` may not drop below when withdrawing for
+	/// *anything except transaction fee payment*.
+	pub misc_frozen: Balance,
+	/// The amount that `free` may not drop below when withdrawing
+	/// specifically for transaction fee payment.
+	pub fee_frozen: Balance,
 }
 
 impl<Balance: Saturating + Copy + Ord> AccountData<Balance> {
 	/// The amount that this account's free balance may not be reduced
-	/// beyond.
-	pub(crate) fn frozen(&self) -> Balance {
-		self.frozen
+	/// beyond for the given `reasons`.
+	pub fn frozen(&self, reasons: Reasons) -> Balance {
+		match reasons {
+			Reasons::All => self.misc_frozen.max(self.fee_frozen),
+			Reasons::Misc => self.misc_frozen,
+			Reasons::Fee => self.fee_frozen,
+		}
 	}
 	/// The total balance in this account including any that is reserved and
 	/// ignoring any frozen.
@@ -202,12 +274,107 @@ pub mod module {
 		/// Handler to burn or transfer account's dust
 		type OnDust: OnDust<Self::AccountId, Self::CurrencyId, Self::Balance>;
 
+		/// Hooks called on transfers, including repatriation of reserved
+		/// balance. `pre_transfer` can veto the transfer.
+		type OnTransfer: OnTransfer<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
+		/// Hooks called on deposits. `pre_deposit` can veto the deposit.
+		type OnDeposit: OnDeposit<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
+		/// Hooks called on withdrawals. `pre_withdraw` can veto the
+		/// withdrawal.
+		type OnWithdraw: OnWithdraw<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
+		/// Hook called on slashes of free or reserved balance.
+		type OnSlash: OnSlash<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
+		/// Handler for when a token account is created.
+		type OnNewTokenAccount: OnNewTokenAccount<Self::AccountId, Self::CurrencyId>;
+
+		/// Handler for when a token account is reaped.
+		type OnKilledTokenAccount: OnKilledTokenAccount<Self::AccountId, Self::CurrencyId>;
+
+		/// The sufficient currencies. Like a sufficient asset of
+		/// `pallet_assets`, a token account of a sufficient currency adds a
+		/// sufficient reference to the account in `frame_system`, and keeps it
+		/// alive on its own.
+		///
+		/// Like a non-sufficient asset of `pallet_assets`, a token account of
+		/// any other currency, which is not one of the `ProviderCurrencies`,
+		/// adds a consumer reference and needs an existing provider.
+		///
+		/// NOTE: Changing whether a currency is sufficient while it has
+		/// holders requires a migration of their references.
+		type SufficientCurrencies: Contains<Self::CurrencyId>;
+
+		/// The currencies a token account of which adds a provider reference
+		/// to the account in `frame_system`, like the native currency. Use
+		/// `Everything` (and no `SufficientCurrencies`) to keep every token
+		/// account providing for its account, or `Nothing` for the semantics
+		/// of `pallet_assets`.
+		///
+		/// NOTE: Changing whether a currency is a provider while it has
+		/// holders requires a migration of their references.
+		type ProviderCurrencies: Contains<Self::CurrencyId>;
+
 		#[pallet::constant]
 		type MaxLocks: Get<u32>;
 
-		// The whitelist of accounts that will not be reaped even if its total
-		// is zero or below ED.
-		type DustRemovalWhitelist: Contains<Self::AccountId>;
+		/// The maximum number of named reserves that can exist on an account.
+		#[pallet::constant]
+		type MaxReserves: Get<u32>;
+
+		/// The maximum number of transfers in a `transfer_multiple` call.
+		#[pallet::constant]
+		type MaxTransfers: Get<u32>;
+
+		/// The currency in which approval deposits are reserved.
+		type DepositCurrency: PalletReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved from the owner for each approval.
+		#[pallet::constant]
+		type ApprovalDeposit: Get<DepositBalanceOf<Self>>;
+
+		/// The id type for named reserves.
+		type ReserveIdentifier: Parameter + Member + MaxEncodedLen + Ord + Copy;
+
+		// The whitelist of accounts, per currency, that will not be reaped
+		// even if its total is zero or below ED.
+		type DustRemovalWhitelist: Contains<(Self::AccountId, Self::CurrencyId)>;
+
+		/// The account that dust accumulated by `AccumulateDust` is deposited
+		/// to in `on_idle`.
+		type DustReceiver: Get<Self::AccountId>;
+
+		/// The origin which may freeze or thaw accounts and currencies.
+		type FreezeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may set the metadata of currencies. It is also
+		/// checked for the account setting metadata through
+		/// `fungibles::metadata::Mutate`, as a signed origin.
+		type MetadataOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum length of the name and symbol of a currency.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
+		/// Whether frozen accounts, and accounts of frozen currencies, are
+		/// still allowed to receive funds.
+		type AllowFrozenReceiving: Get<bool>;
+
+		/// Whether to maintain the `Holders` index of accounts per currency.
+		///
+		/// NOTE: Enabling it on a live chain requires a migration to populate
+		/// the index.
+		#[pallet::constant]
+		type MaintainHoldersIndex: Get<bool>;
+
+		/// The maximum total issuance of a currency, `None` for no limit.
+		type MaxIssuance: GetByKey<Self::CurrencyId, Option<Self::Balance>>;
+
+		/// The total issuance of a currency at which
+		/// `IssuanceHighWaterMarkReached` is emitted, `None` for no event.
+		type IssuanceHighWaterMark: GetByKey<Self::CurrencyId, Option<Self::Balance>>;
 	}
 
 	#[pallet::error]
@@ -226,6 +393,20 @@ pub mod module {
 		ExistentialDeposit,
 		/// Beneficiary account must pre-exist
 		DeadAccount,
+		// Number of named reserves exceed `T::MaxReserves`
+		TooManyReserves,
+		/// The account is frozen under this currency
+		AccountFrozen,
+		/// The currency is frozen
+		CurrencyFrozen,
+		/// The total issuance would exceed `T::MaxIssuance`
+		MaxIssuanceExceeded,
+		/// No approval exists, or the allowance is too low
+		Unapproved,
+		/// The name or symbol is longer than `T::StringLimit`
+		BadMetadata,
+		/// No transfers were given
+		NoTransfers,
 	}
 
 	#[pallet::event]
@@ -249,6 +430,33 @@ pub mod module {
 		Unreserved(T::CurrencyId, T::AccountId, T::Balance),
 		/// A balance was set by root. \[who, free, reserved\]
 		BalanceSet(T::CurrencyId, T::AccountId, T::Balance, T::Balance),
+		/// An account was frozen under a currency. \[currency_id, who\]
+		AccountFrozen(T::CurrencyId, T::AccountId),
+		/// An account was thawed under a currency. \[currency_id, who\]
+		AccountThawed(T::CurrencyId, T::AccountId),
+		/// A currency was frozen. \[currency_id\]
+		CurrencyFrozen(T::CurrencyId),
+		/// A currency was thawed. \[currency_id\]
+		CurrencyThawed(T::CurrencyId),
+		/// The total issuance of a currency reached its high-water mark.
+		/// \[currency_id, total_issuance\]
+		IssuanceHighWaterMarkReached(T::CurrencyId, T::Balance),
+		/// Multiple transfers succeeded. \[from, count\]
+		TransferredMultiple(T::AccountId, u32),
+		/// A delegate was approved to transfer from an owner.
+		/// \[currency_id, owner, delegate, amount\]
+		ApprovedTransfer(T::CurrencyId, T::AccountId, T::AccountId, T::Balance),
+		/// An approval was cancelled. \[currency_id, owner, delegate\]
+		ApprovalCancelled(T::CurrencyId, T::AccountId, T::AccountId),
+		/// A delegate transferred from an owner within its allowance.
+		/// \[currency_id, owner, delegate, to, amount\]
+		TransferredApproved(T::CurrencyId, T::AccountId, T::AccountId, T::AccountId, T::Balance),
+		/// Accumulated dust was deposited to the dust receiver.
+		/// \[currency_id, receiver, amount\]
+		DustFlushed(T::CurrencyId, T::AccountId, T::Balance),
+		/// The metadata of a currency was set. \[currency_id, name, symbol,
+		/// decimals\]
+		MetadataSet(T::CurrencyId, Vec<u8>, Vec<u8>, u8),
 	}
 
 	/// The total issuance of a token type.
@@ -270,6 +478,80 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// Named reserves on some account balances.
+	#[pallet::storage]
+	#[pallet::getter(fn reserves)]
+	pub type Reserves<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::CurrencyId,
+		BoundedVec<ReserveData<T::ReserveIdentifier, T::Balance>, T::MaxReserves>,
+		ValueQuery,
+	>;
+
+	/// The accounts frozen under a token type.
+	#[pallet::storage]
+	#[pallet::getter(fn frozen_accounts)]
+	pub type FrozenAccounts<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::CurrencyId, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// The frozen token types.
+	#[pallet::storage]
+	#[pallet::getter(fn frozen_currencies)]
+	pub type FrozenCurrencies<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, (), OptionQuery>;
+
+	/// The number of accounts holding a token type.
+	#[pallet::storage]
+	#[pallet::getter(fn holders_count)]
+	pub type HoldersCount<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, u32, ValueQuery>;
+
+	/// The accounts holding a token type.
+	///
+	/// NOTE: Only maintained if `T::MaintainHoldersIndex` is true.
+	#[pallet::storage]
+	#[pallet::getter(fn holders)]
+	pub type Holders<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::CurrencyId, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// The dust accumulated by `AccumulateDust` of a token type, not yet
+	/// deposited to `T::DustReceiver`.
+	///
+	/// NOTE: Accumulated dust is still accounted in `TotalIssuance`.
+	#[pallet::storage]
+	#[pallet::getter(fn accumulated_dust)]
+	pub type AccumulatedDust<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, T::Balance, ValueQuery>;
+
+	/// The accumulated dust of a token type which failed to be deposited to
+	/// `T::DustReceiver`, retried after all of `AccumulatedDust` is flushed.
+	///
+	/// NOTE: Deferred dust is still accounted in `TotalIssuance`.
+	#[pallet::storage]
+	#[pallet::getter(fn deferred_dust)]
+	pub type DeferredDust<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, T::Balance, ValueQuery>;
+
+	/// The metadata of a token type.
+	#[pallet::storage]
+	#[pallet::getter(fn metadata)]
+	pub type Metadata<T: Config> =
+		StorageMap<_, Twox64Concat, T::CurrencyId, CurrencyMetadata<BoundedVec<u8, T::StringLimit>>, ValueQuery>;
+
+	/// The approvals of delegates to transfer a token type from an owner,
+	/// keyed by `(currency_id, owner, delegate)`.
+	#[pallet::storage]
+	#[pallet::getter(fn approvals)]
+	pub type Approvals<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, T::CurrencyId>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+		),
+		Approval<T::Balance, DepositBalanceOf<T>>,
+		OptionQuery,
+	>;
+
 	/// The balance of a token type under an account.
 	///
 	/// NOTE: If the total is ever zero, decrease account ref account.
@@ -291,12 +573,18 @@ pub mod module {
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub balances: Vec<(T::AccountId, T::CurrencyId, T::Balance)>,
+		/// The metadata of currencies. \[currency_id, name, symbol,
+		/// decimals\]
+		pub metadata: Vec<(T::CurrencyId, Vec<u8>, Vec<u8>, u8)>,
 	}
 
 	#[cfg(feature = "std")]
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			GenesisConfig { balances: vec![] }
+			GenesisConfig {
+				balances: vec![],
+				metadata: vec![],
+			}
 		}
 	}
 
@@ -321,6 +609,10 @@ pub mod module {
 						*initial_balance >= T::ExistentialDeposits::get(currency_id),
 						"the balance of any account should always be more than existential deposit.",
 					);
+					assert!(
+						Pallet::<T>::ensure_can_create_account(*currency_id, account_id).is_ok(),
+						"the accounts of currencies which are neither sufficient nor providers need a provider.",
+					);
 					Pallet::<T>::mutate_account(account_id, *currency_id, |account_data, _| {
 						account_data.free = *initial_balance
 					});
@@ -330,14 +622,42 @@ pub mod module {
 							.expect("total issuance cannot overflow when building genesis")
 					});
 				});
+
+			self.metadata.iter().for_each(|(currency_id, name, symbol, decimals)| {
+				let metadata = Pallet::<T>::bounded_metadata(name.clone(), symbol.clone(), *decimals)
+					.expect("the name and symbol of currencies should fit in `StringLimit`.");
+				Metadata::<T>::insert(currency_id, metadata);
+			});
 		}
 	}
 
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::flush_dust(remaining_weight)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migrations::pre_migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migrations::post_migrate::<T>()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -369,6 +689,35 @@ pub mod module {
 			Ok(())
 		}
 
+		/// Transfer some liquid free balance to multiple accounts, in one or
+		/// more currencies. Either all transfers succeed or none of them.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `transfers`: the currency type, recipient and free balance
+		///   amount of each transfer, at least one and at most
+		///   `T::MaxTransfers`.
+		#[pallet::weight(T::WeightInfo::transfer_multiple(transfers.len() as u32))]
+		#[transactional]
+		pub fn transfer_multiple(
+			origin: OriginFor<T>,
+			transfers: BoundedVec<(T::CurrencyId, <T::Lookup as StaticLookup>::Source, T::Balance), T::MaxTransfers>,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			ensure!(!transfers.is_empty(), Error::<T>::NoTransfers);
+
+			let count = transfers.len() as u32;
+			for (currency_id, dest, amount) in transfers.into_inner() {
+				let to = T::Lookup::lookup(dest)?;
+				Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::AllowDeath)?;
+				Self::deposit_event(Event::Transfer(currency_id, from.clone(), to, amount));
+			}
+
+			Self::deposit_event(Event::TransferredMultiple(from, count));
+			Ok(())
+		}
+
 		/// Transfer all remaining balance to the given account.
 		///
 		/// NOTE: This function only attempts to transfer _transferable_
@@ -476,7 +825,7 @@ pub mod module {
 			ensure_root(origin)?;
 			let who = T::Lookup::lookup(who)?;
 
-			Self::try_mutate_account(&who, currency_id, |account, _| -> DispatchResult {
+			Self::try_mutate_account(&who, currency_id, |account, existed| -> DispatchResult {
 				let mut new_total = new_free.checked_add(&new_reserved).ok_or(ArithmeticError::Overflow)?;
 				let (new_free, new_reserved) = if new_free + new_reserved < T::ExistentialDeposits::get(&currency_id) {
 					new_total = Zero::zero();
@@ -484,18 +833,20 @@ pub mod module {
 				} else {
 					(new_free, new_reserved)
 				};
+				if !existed && !new_total.is_zero() {
+					Self::ensure_can_create_account(currency_id, &who)?;
+				}
 				let old_total = account.total();
 
 				account.free = new_free;
 				account.reserved = new_reserved;
 
 				if new_total > old_total {
-					TotalIssuance::<T>::try_mutate(currency_id, |t| -> DispatchResult {
-						*t = t
-							.checked_add(&(new_total - old_total))
-							.ok_or(ArithmeticError::Overflow)?;
-						Ok(())
-					})?;
+					let new_total_issuance = Self::total_issuance(currency_id)
+						.checked_add(&(new_total - old_total))
+						.ok_or(ArithmeticError::Overflow)?;
+					Self::ensure_max_issuance(currency_id, new_total_issuance)?;
+					Self::update_total_issuance(currency_id, new_total_issuance);
 				} else if new_total < old_total {
 					TotalIssuance::<T>::try_mutate(currency_id, |t| -> DispatchResult {
 						*t = t
@@ -509,12 +860,298 @@ pub mod module {
 				Ok(())
 			})
 		}
+
+		/// Freeze the account `who` under `currency_id`. A frozen account
+		/// cannot transfer, withdraw or reserve under this currency.
+		///
+		/// The dispatch origin for this call must be `T::FreezeOrigin`.
+		#[pallet::weight(T::WeightInfo::freeze_account())]
+		pub fn freeze_account(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			FrozenAccounts::<T>::insert(currency_id, &who, ());
+			Self::deposit_event(Event::AccountFrozen(currency_id, who));
+			Ok(())
+		}
+
+		/// Thaw the account `who` under `currency_id`.
+		///
+		/// The dispatch origin for this call must be `T::FreezeOrigin`.
+		#[pallet::weight(T::WeightInfo::thaw_account())]
+		pub fn thaw_account(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			FrozenAccounts::<T>::remove(currency_id, &who);
+			Self::deposit_event(Event::AccountThawed(currency_id, who));
+			Ok(())
+		}
+
+		/// Freeze all accounts under `currency_id`.
+		///
+		/// The dispatch origin for this call must be `T::FreezeOrigin`.
+		#[pallet::weight(T::WeightInfo::freeze_currency())]
+		pub fn freeze_currency(origin: OriginFor<T>, currency_id: T::CurrencyId) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+
+			FrozenCurrencies::<T>::insert(currency_id, ());
+			Self::deposit_event(Event::CurrencyFrozen(currency_id));
+			Ok(())
+		}
+
+		/// Thaw `currency_id`.
+		///
+		/// The dispatch origin for this call must be `T::FreezeOrigin`.
+		#[pallet::weight(T::WeightInfo::thaw_currency())]
+		pub fn thaw_currency(origin: OriginFor<T>, currency_id: T::CurrencyId) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+
+			FrozenCurrencies::<T>::remove(currency_id);
+			Self::deposit_event(Event::CurrencyThawed(currency_id));
+			Ok(())
+		}
+
+		/// Set the name, symbol and decimals of `currency_id`.
+		///
+		/// The dispatch origin for this call must be `T::MetadataOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `name`: the user friendly name, limited to `T::StringLimit` bytes.
+		/// - `symbol`: the ticker symbol, limited to `T::StringLimit` bytes.
+		/// - `decimals`: the number of decimals of one unit.
+		#[pallet::weight(T::WeightInfo::set_metadata(name.len().saturating_add(symbol.len()) as u32))]
+		pub fn set_metadata(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+		) -> DispatchResult {
+			T::MetadataOrigin::ensure_origin(origin)?;
+			Self::do_set_metadata(currency_id, name, symbol, decimals)
+		}
+
+		/// Approve `delegate` to transfer up to `amount` of `currency_id` from
+		/// the caller's free balance, replacing any existing approval. A zero
+		/// `amount` cancels the approval.
+		///
+		/// `T::ApprovalDeposit` is reserved from the caller for a new
+		/// approval, and unreserved once it is cancelled or used up.
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner.
+		///
+		/// - `delegate`: the account allowed to transfer.
+		/// - `currency_id`: currency type.
+		/// - `amount`: the allowance.
+		#[pallet::weight(T::WeightInfo::approve_transfer())]
+		pub fn approve_transfer(
+			origin: OriginFor<T>,
+			delegate: <T::Lookup as StaticLookup>::Source,
+			currency_id: T::CurrencyId,
+			#[pallet::compact] amount: T::Balance,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			<Self as orml_traits::Approvals<_>>::approve(currency_id, &owner, &delegate, amount)
+		}
+
+		/// Cancel the approval of `delegate` to transfer `currency_id` from
+		/// the caller, and unreserve its deposit.
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner.
+		///
+		/// - `delegate`: the approved account.
+		/// - `currency_id`: currency type.
+		#[pallet::weight(T::WeightInfo::cancel_approval())]
+		pub fn cancel_approval(
+			origin: OriginFor<T>,
+			delegate: <T::Lookup as StaticLookup>::Source,
+			currency_id: T::CurrencyId,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			<Self as orml_traits::Approvals<_>>::cancel_approval(currency_id, &owner, &delegate)
+		}
+
+		/// Transfer some free balance of `owner` to `dest`, within the
+		/// allowance `owner` approved for the caller.
+		///
+		/// The dispatch origin for this call must be `Signed` by the delegate.
+		///
+		/// - `owner`: the account to transfer from.
+		/// - `dest`: the recipient of the transfer.
+		/// - `currency_id`: currency type.
+		/// - `amount`: free balance amount to transfer.
+		#[pallet::weight(T::WeightInfo::transfer_approved())]
+		pub fn transfer_approved(
+			origin: OriginFor<T>,
+			owner: <T::Lookup as StaticLookup>::Source,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: T::CurrencyId,
+			#[pallet::compact] amount: T::Balance,
+		) -> DispatchResult {
+			let delegate = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let to = T::Lookup::lookup(dest)?;
+			<Self as orml_traits::Approvals<_>>::transfer_approved(currency_id, &owner, &delegate, &to, amount)
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// Bound `name` and `symbol` by `T::StringLimit`.
+	pub(crate) fn bounded_metadata(
+		name: Vec<u8>,
+		symbol: Vec<u8>,
+		decimals: u8,
+	) -> Result<CurrencyMetadata<BoundedVec<u8, T::StringLimit>>, DispatchError> {
+		Ok(CurrencyMetadata {
+			name: name.try_into().map_err(|_| Error::<T>::BadMetadata)?,
+			symbol: symbol.try_into().map_err(|_| Error::<T>::BadMetadata)?,
+			decimals,
+		})
+	}
+
+	pub(crate) fn do_set_metadata(
+		currency_id: T::CurrencyId,
+		name: Vec<u8>,
+		symbol: Vec<u8>,
+		decimals: u8,
+	) -> DispatchResult {
+		let metadata = Self::bounded_metadata(name.clone(), symbol.clone(), decimals)?;
+		Metadata::<T>::insert(currency_id, metadata);
+
+		Self::deposit_event(Event::MetadataSet(currency_id, name, symbol, decimals));
+		Ok(())
+	}
+
+	/// Whether `who` under `currency_id` is exempt from dust removal.
+	pub(crate) fn is_dust_removal_whitelisted(who: &T::AccountId, currency_id: T::CurrencyId) -> bool {
+		T::DustRemovalWhitelist::contains(&(who.clone(), currency_id))
+	}
+
+	/// Deposit the accumulated dust of as many currencies as fit in
+	/// `remaining_weight` to `T::DustReceiver`.
+	///
+	/// Dust that can't be deposited is moved to `DeferredDust`, which is
+	/// retried once all of `AccumulatedDust` is flushed, so that it does not
+	/// hold back the flush of other currencies.
+	pub(crate) fn flush_dust(remaining_weight: Weight) -> Weight {
+		let per_currency = T::WeightInfo::flush_dust();
+		// reading the keys of a map costs a read even if it is empty
+		let per_map = T::DbWeight::get().reads(1);
+		let max_currencies = |used_weight: Weight| {
+			remaining_weight
+				.saturating_sub(used_weight)
+				.saturating_sub(per_map)
+				.checked_div(per_currency)
+				.unwrap_or(Weight::MAX)
+				.saturated_into::<usize>()
+		};
+
+		let max_accumulated = max_currencies(0);
+		if max_accumulated.is_zero() {
+			return 0;
+		}
+
+		let receiver = T::DustReceiver::get();
+		let accumulated = AccumulatedDust::<T>::iter_keys()
+			.take(max_accumulated)
+			.collect::<Vec<_>>();
+		for currency_id in accumulated.iter() {
+			let amount = AccumulatedDust::<T>::take(currency_id);
+			if !Self::deposit_dust(*currency_id, &receiver, amount) {
+				DeferredDust::<T>::mutate(currency_id, |dust| *dust = dust.saturating_add(amount));
+			}
+		}
+		let mut weight = per_map.saturating_add(per_currency.saturating_mul(accumulated.len() as Weight));
+
+		let max_deferred = max_currencies(weight);
+		if accumulated.len() < max_accumulated && !max_deferred.is_zero() {
+			let deferred = DeferredDust::<T>::iter_keys().take(max_deferred).collect::<Vec<_>>();
+			for currency_id in deferred.iter() {
+				let amount = DeferredDust::<T>::take(currency_id);
+				if !Self::deposit_dust(*currency_id, &receiver, amount) {
+					DeferredDust::<T>::insert(currency_id, amount);
+				}
+			}
+			weight = weight
+				.saturating_add(per_map)
+				.saturating_add(per_currency.saturating_mul(deferred.len() as Weight));
+		}
+
+		weight
+	}
+
+	/// Deposit the flushed dust of `currency_id` to `receiver`, return
+	/// whether it succeeded.
+	fn deposit_dust(currency_id: T::CurrencyId, receiver: &T::AccountId, amount: T::Balance) -> bool {
+		let deposited = Self::do_deposit(currency_id, receiver, amount, false, false).is_ok();
+		if deposited {
+			Self::deposit_event(Event::DustFlushed(currency_id, receiver.clone(), amount));
+		}
+		deposited
+	}
+
+	/// Ensure neither `currency_id` nor `who` under `currency_id` is frozen.
+	pub(crate) fn ensure_not_frozen(currency_id: T::CurrencyId, who: &T::AccountId) -> DispatchResult {
+		ensure!(
+			!FrozenCurrencies::<T>::contains_key(currency_id),
+			Error::<T>::CurrencyFrozen
+		);
+		ensure!(
+			!FrozenAccounts::<T>::contains_key(currency_id, who),
+			Error::<T>::AccountFrozen
+		);
+		Ok(())
+	}
+
+	/// Ensure `who` can receive funds under `currency_id`.
+	pub(crate) fn ensure_can_receive(currency_id: T::CurrencyId, who: &T::AccountId) -> DispatchResult {
+		Self::ensure_can_create_account(currency_id, who)?;
+		if T::AllowFrozenReceiving::get() {
+			return Ok(());
+		}
+		Self::ensure_not_frozen(currency_id, who)
+	}
+
+	/// The reference a token account of `currency_id` adds to its account.
+	fn account_reference(currency_id: T::CurrencyId) -> AccountReference {
+		if T::SufficientCurrencies::contains(&currency_id) {
+			AccountReference::Sufficient
+		} else if T::ProviderCurrencies::contains(&currency_id) {
+			AccountReference::Provider
+		} else {
+			AccountReference::Consumer
+		}
+	}
+
+	/// Ensure the token account of `who` under `currency_id` exists or could
+	/// be created: the consumer reference of a currency which is neither
+	/// sufficient nor a provider needs an existing provider.
+	pub(crate) fn ensure_can_create_account(currency_id: T::CurrencyId, who: &T::AccountId) -> DispatchResult {
+		if Self::account_reference(currency_id) == AccountReference::Consumer
+			&& !Accounts::<T>::contains_key(who, currency_id)
+		{
+			ensure!(
+				!frame_system::Pallet::<T>::providers(who).is_zero(),
+				TokenError::CannotCreate
+			);
+		}
+		Ok(())
+	}
+
 	pub(crate) fn deposit_consequence(
-		_who: &T::AccountId,
+		who: &T::AccountId,
 		currency_id: T::CurrencyId,
 		amount: T::Balance,
 		account: &AccountData<T::Balance>,
@@ -523,8 +1160,9 @@ impl<T: Config> Pallet<T> {
 			return DepositConsequence::Success;
 		}
 
-		if TotalIssuance::<T>::get(currency_id).checked_add(&amount).is_none() {
-			return DepositConsequence::Overflow;
+		match TotalIssuance::<T>::get(currency_id).checked_add(&amount) {
+			Some(new_total_issuance) if Self::ensure_max_issuance(currency_id, new_total_issuance).is_ok() => {}
+			_ => return DepositConsequence::Overflow,
 		}
 
 		let new_total_balance = match account.total().checked_add(&amount) {
@@ -536,8 +1174,9 @@ impl<T: Config> Pallet<T> {
 			return DepositConsequence::BelowMinimum;
 		}
 
-		// NOTE: We assume that we are a provider, so don't need to do any checks in the
-		// case of account creation.
+		if account.total().is_zero() && Self::ensure_can_create_account(currency_id, who).is_err() {
+			return DepositConsequence::CannotCreate;
+		}
 
 		DepositConsequence::Success
 	}
@@ -552,6 +1191,10 @@ impl<T: Config> Pallet<T> {
 			return WithdrawConsequence::Success;
 		}
 
+		if Self::ensure_not_frozen(currency_id, who).is_err() {
+			return WithdrawConsequence::Frozen;
+		}
+
 		if TotalIssuance::<T>::get(currency_id).checked_sub(&amount).is_none() {
 			return WithdrawConsequence::Underflow;
 		}
@@ -563,11 +1206,9 @@ impl<T: Config> Pallet<T> {
 
 		// Provider restriction - total account balance cannot be reduced to zero if it
 		// cannot sustain the loss of a provider reference.
-		// NOTE: This assumes that the pallet is a provider (which is true). Is this
-		// ever changes, then this will need to adapt accordingly.
 		let ed = T::ExistentialDeposits::get(&currency_id);
 		let success = if new_total_balance < ed {
-			if frame_system::Pallet::<T>::can_dec_provider(who) {
+			if Self::can_dec_account_reference(currency_id, who) {
 				WithdrawConsequence::ReducedToZero(new_total_balance)
 			} else {
 				return WithdrawConsequence::WouldDie;
@@ -583,7 +1224,7 @@ impl<T: Config> Pallet<T> {
 		};
 
 		// Eventual free funds must be no less than the frozen balance.
-		if new_free_balance < account.frozen() {
+		if new_free_balance < account.frozen(Reasons::All) {
 			return WithdrawConsequence::Frozen;
 		}
 
@@ -597,21 +1238,114 @@ impl<T: Config> Pallet<T> {
 		currency_id: T::CurrencyId,
 		who: &T::AccountId,
 		amount: T::Balance,
+	) -> DispatchResult {
+		Self::ensure_can_withdraw_with_reasons(currency_id, who, amount, WithdrawReasons::all())
+	}
+
+	// Ensure that an account can withdraw from their free balance for the given
+	// `reasons`, only the locks which restrict any of `reasons` are taken into
+	// account.
+	// Is a no-op if amount to be withdrawn is zero.
+	pub(crate) fn ensure_can_withdraw_with_reasons(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
 	) -> DispatchResult {
 		if amount.is_zero() {
 			return Ok(());
 		}
 
+		Self::ensure_not_frozen(currency_id, who)?;
+
 		let new_balance = Self::free_balance(currency_id, who)
 			.checked_sub(&amount)
 			.ok_or(Error::<T>::BalanceTooLow)?;
 		ensure!(
-			new_balance >= Self::accounts(who, currency_id).frozen(),
+			new_balance >= Self::accounts(who, currency_id).frozen(reasons.into()),
 			Error::<T>::LiquidityRestrictions
 		);
 		Ok(())
 	}
 
+	/// Ensure `new_total_issuance` does not exceed the `T::MaxIssuance` of
+	/// `currency_id`.
+	pub(crate) fn ensure_max_issuance(currency_id: T::CurrencyId, new_total_issuance: T::Balance) -> DispatchResult {
+		if let Some(max_issuance) = T::MaxIssuance::get(&currency_id) {
+			ensure!(new_total_issuance <= max_issuance, Error::<T>::MaxIssuanceExceeded);
+		}
+		Ok(())
+	}
+
+	/// Set the total issuance of `currency_id`, and emit
+	/// `IssuanceHighWaterMarkReached` if it crosses the high-water mark.
+	pub(crate) fn update_total_issuance(currency_id: T::CurrencyId, new_total_issuance: T::Balance) {
+		let old_total_issuance =
+			TotalIssuance::<T>::mutate(currency_id, |v| sp_std::mem::replace(v, new_total_issuance));
+		if let Some(mark) = T::IssuanceHighWaterMark::get(&currency_id) {
+			if old_total_issuance < mark && new_total_issuance >= mark {
+				Self::deposit_event(Event::IssuanceHighWaterMarkReached(currency_id, new_total_issuance));
+			}
+		}
+	}
+
+	/// At most `limit` accounts holding `currency_id`, capped at
+	/// `MAX_HOLDERS_PAGE`, following `start_after` in storage order, or `None`
+	/// if the `Holders` index is not maintained.
+	///
+	/// Pass the last account of a page as `start_after` to get the next page,
+	/// see `HoldersCount` for the total number of holders.
+	pub fn holders_of(
+		currency_id: T::CurrencyId,
+		start_after: Option<T::AccountId>,
+		limit: u32,
+	) -> Option<Vec<T::AccountId>> {
+		if !T::MaintainHoldersIndex::get() {
+			return None;
+		}
+
+		let mut prefix = Holders::<T>::final_prefix().to_vec();
+		prefix.extend(Twox64Concat::hash(&currency_id.encode()));
+		let previous_key = match start_after {
+			Some(who) => Holders::<T>::hashed_key_for(currency_id, who),
+			None => prefix.clone(),
+		};
+		let holders = KeyPrefixIterator::new(prefix, previous_key, |raw_key_without_prefix| {
+			T::AccountId::decode(&mut Blake2_128Concat::reverse(raw_key_without_prefix))
+		});
+		Some(holders.take(limit.min(MAX_HOLDERS_PAGE) as usize).collect())
+	}
+
+	fn add_holder(currency_id: T::CurrencyId, who: &T::AccountId) {
+		HoldersCount::<T>::mutate(currency_id, |count| *count = count.saturating_add(1));
+		if T::MaintainHoldersIndex::get() {
+			Holders::<T>::insert(currency_id, who, ());
+		}
+	}
+
+	fn remove_holder(currency_id: T::CurrencyId, who: &T::AccountId) {
+		HoldersCount::<T>::mutate(currency_id, |count| *count = count.saturating_sub(1));
+		if T::MaintainHoldersIndex::get() {
+			Holders::<T>::remove(currency_id, who);
+		}
+	}
+
+	/// Whether the account can lose the reference added by its token account
+	/// of `currency_id`.
+	///
+	/// Like a provider reference, a sufficient reference cannot be removed if
+	/// it is the last reference keeping an account with consumers alive.
+	fn can_dec_account_reference(currency_id: T::CurrencyId, who: &T::AccountId) -> bool {
+		match Self::account_reference(currency_id) {
+			AccountReference::Provider => frame_system::Pallet::<T>::can_dec_provider(who),
+			AccountReference::Sufficient => {
+				let account = frame_system::Account::<T>::get(who);
+				account.consumers.is_zero() || account.providers > 0 || account.sufficients > 1
+			}
+			AccountReference::Consumer => true,
+		}
+	}
+
 	pub(crate) fn try_mutate_account<R, E>(
 		who: &T::AccountId,
 		currency_id: T::CurrencyId,
@@ -629,7 +1363,7 @@ impl<T: Config> Pallet<T> {
 					if total.is_zero() {
 						None
 					} else {
-						if !T::DustRemovalWhitelist::contains(who) {
+						if !Self::is_dust_removal_whitelisted(who, currency_id) {
 							maybe_dust = Some(total);
 						}
 						Some(account)
@@ -644,13 +1378,40 @@ impl<T: Config> Pallet<T> {
 		})
 		.map(|(maybe_endowed, existed, exists, maybe_dust, result)| {
 			if existed && !exists {
-				// If existed before, decrease account provider.
+				// If existed before, remove the reference added by the token account.
 				// Ignore the result, because if it failed then there are remaining consumers,
 				// and the account storage in frame_system shouldn't be reaped.
-				let _ = frame_system::Pallet::<T>::dec_providers(who);
+				match Self::account_reference(currency_id) {
+					AccountReference::Provider => {
+						let _ = frame_system::Pallet::<T>::dec_providers(who);
+					}
+					AccountReference::Sufficient => frame_system::Pallet::<T>::dec_sufficients(who),
+					AccountReference::Consumer => frame_system::Pallet::<T>::dec_consumers(who),
+				}
+				Self::remove_holder(currency_id, who);
+				T::OnKilledTokenAccount::on_killed_token_account(who, currency_id);
 			} else if !existed && exists {
-				// if new, increase account provider
-				frame_system::Pallet::<T>::inc_providers(who);
+				// if new, add the reference of the token account
+				match Self::account_reference(currency_id) {
+					AccountReference::Provider => {
+						frame_system::Pallet::<T>::inc_providers(who);
+					}
+					AccountReference::Sufficient => {
+						frame_system::Pallet::<T>::inc_sufficients(who);
+					}
+					AccountReference::Consumer => {
+						if frame_system::Pallet::<T>::inc_consumers(who).is_err() {
+							// No provider, which is checked by `ensure_can_create_account` before
+							// funds are received.
+							log::warn!(
+								"Warning: Attempt to introduce token account consumer reference, yet no providers. \
+								This is unexpected but should be safe."
+							);
+						}
+					}
+				}
+				Self::add_holder(currency_id, who);
+				T::OnNewTokenAccount::on_new_token_account(who, currency_id);
 			}
 
 			if let Some(endowed) = maybe_endowed {
@@ -705,43 +1466,63 @@ impl<T: Config> Pallet<T> {
 
 	/// Update the account entry for `who` under `currency_id`, given the
 	/// locks.
+	///
+	/// The first lock adds a consumer reference to the account, which needs a
+	/// provider reference: locks are refused on accounts only kept alive by
+	/// sufficient references.
 	pub(crate) fn update_locks(
 		currency_id: T::CurrencyId,
 		who: &T::AccountId,
 		locks: &[BalanceLock<T::Balance>],
 	) -> DispatchResult {
+		let maybe_bounded_locks: Option<BoundedVec<BalanceLock<T::Balance>, T::MaxLocks>> = if locks.is_empty() {
+			None
+		} else {
+			Some(locks.to_vec().try_into().map_err(|_| Error::<T>::MaxLocksExceeded)?)
+		};
+
+		let existed = Locks::<T>::contains_key(who, currency_id);
+		if !existed && maybe_bounded_locks.is_some() {
+			// increase account ref count when initialize lock
+			if frame_system::Pallet::<T>::inc_consumers(who).is_err() {
+				// A sufficient reference cannot back the consumer reference of the locks.
+				ensure!(
+					frame_system::Pallet::<T>::sufficients(who).is_zero(),
+					DispatchError::NoProviders
+				);
+				// No providers for the locks. This is impossible under normal circumstances
+				// since the funds that are under the lock will themselves be stored in the
+				// account and therefore will need a reference.
+				log::warn!(
+					"Warning: Attempt to introduce lock consumer reference, yet no providers. \
+					This is unexpected but should be safe."
+				);
+			}
+		}
+
 		// update account data
 		Self::mutate_account(who, currency_id, |account, _| {
-			account.frozen = Zero::zero();
+			account.misc_frozen = Zero::zero();
+			account.fee_frozen = Zero::zero();
 			for lock in locks.iter() {
-				account.frozen = account.frozen.max(lock.amount);
+				if lock.reasons == Reasons::All || lock.reasons == Reasons::Misc {
+					account.misc_frozen = account.misc_frozen.max(lock.amount);
+				}
+				if lock.reasons == Reasons::All || lock.reasons == Reasons::Fee {
+					account.fee_frozen = account.fee_frozen.max(lock.amount);
+				}
 			}
 		});
 
 		// update locks
-		let existed = Locks::<T>::contains_key(who, currency_id);
-		if locks.is_empty() {
+		if let Some(bounded_locks) = maybe_bounded_locks {
+			Locks::<T>::insert(who, currency_id, bounded_locks);
+		} else {
 			Locks::<T>::remove(who, currency_id);
 			if existed {
 				// decrease account ref count when destruct lock
 				frame_system::Pallet::<T>::dec_consumers(who);
 			}
-		} else {
-			let bounded_locks: BoundedVec<BalanceLock<T::Balance>, T::MaxLocks> =
-				locks.to_vec().try_into().map_err(|_| Error::<T>::MaxLocksExceeded)?;
-			Locks::<T>::insert(who, currency_id, bounded_locks);
-			if !existed {
-				// increase account ref count when initialize lock
-				if frame_system::Pallet::<T>::inc_consumers(who).is_err() {
-					// No providers for the locks. This is impossible under normal circumstances
-					// since the funds that are under the lock will themselves be stored in the
-					// account and therefore will need a reference.
-					log::warn!(
-						"Warning: Attempt to introduce lock consumer reference, yet no providers. \
-						This is unexpected but should be safe."
-					);
-				}
-			}
 		}
 
 		Ok(())
@@ -764,6 +1545,8 @@ impl<T: Config> Pallet<T> {
 			return Ok(());
 		}
 
+		T::OnTransfer::pre_transfer(currency_id, from, to, amount)?;
+
 		Self::try_mutate_account(to, currency_id, |to_account, _existed| -> DispatchResult {
 			Self::try_mutate_account(from, currency_id, |from_account, _existed| -> DispatchResult {
 				from_account.free = from_account
@@ -772,26 +1555,28 @@ impl<T: Config> Pallet<T> {
 					.ok_or(Error::<T>::BalanceTooLow)?;
 				to_account.free = to_account.free.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
 
+				Self::ensure_can_receive(currency_id, to)?;
+
 				let ed = T::ExistentialDeposits::get(&currency_id);
 				// if the total of `to_account` is below existential deposit, would return an
 				// error.
 				// Note: if `to_account` is in `T::DustRemovalWhitelist`, can bypass this check.
 				ensure!(
-					to_account.total() >= ed || T::DustRemovalWhitelist::contains(to),
+					to_account.total() >= ed || Self::is_dust_removal_whitelisted(to, currency_id),
 					Error::<T>::ExistentialDeposit
 				);
 
-				Self::ensure_can_withdraw(currency_id, from, amount)?;
+				Self::ensure_can_withdraw_with_reasons(currency_id, from, amount, WithdrawReasons::TRANSFER)?;
 
 				let allow_death = existence_requirement == ExistenceRequirement::AllowDeath;
-				let allow_death = allow_death && frame_system::Pallet::<T>::can_dec_provider(from);
+				let allow_death = allow_death && Self::can_dec_account_reference(currency_id, from);
 				let would_be_dead = if from_account.total() < ed {
 					if from_account.total().is_zero() {
 						true
 					} else {
 						// Note: if account is not in `T::DustRemovalWhitelist`, account will eventually
 						// be reaped due to the dust removal.
-						!T::DustRemovalWhitelist::contains(from)
+						!Self::is_dust_removal_whitelisted(from, currency_id)
 					}
 				} else {
 					false
@@ -802,7 +1587,10 @@ impl<T: Config> Pallet<T> {
 				Ok(())
 			})?;
 			Ok(())
-		})
+		})?;
+
+		T::OnTransfer::post_transfer(currency_id, from, to, amount);
+		Ok(())
 	}
 
 	/// Withdraw some free balance from an account, respecting existence
@@ -819,13 +1607,35 @@ impl<T: Config> Pallet<T> {
 		amount: T::Balance,
 		existence_requirement: ExistenceRequirement,
 		change_total_issuance: bool,
+	) -> DispatchResult {
+		Self::do_withdraw_with_reasons(
+			currency_id,
+			who,
+			amount,
+			WithdrawReasons::all(),
+			existence_requirement,
+			change_total_issuance,
+		)
+	}
+
+	/// Same as `do_withdraw`, but only the locks which restrict any of
+	/// `reasons` are taken into account.
+	pub(crate) fn do_withdraw_with_reasons(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+		existence_requirement: ExistenceRequirement,
+		change_total_issuance: bool,
 	) -> DispatchResult {
 		if amount.is_zero() {
 			return Ok(());
 		}
 
+		T::OnWithdraw::pre_withdraw(currency_id, who, amount)?;
+
 		Self::try_mutate_account(who, currency_id, |account, _existed| -> DispatchResult {
-			Self::ensure_can_withdraw(currency_id, who, amount)?;
+			Self::ensure_can_withdraw_with_reasons(currency_id, who, amount, reasons)?;
 			let previous_total = account.total();
 			account.free -= amount;
 
@@ -836,7 +1646,7 @@ impl<T: Config> Pallet<T> {
 				} else {
 					// Note: if account is not in `T::DustRemovalWhitelist`, account will eventually
 					// be reaped due to the dust removal.
-					!T::DustRemovalWhitelist::contains(who)
+					!Self::is_dust_removal_whitelisted(who, currency_id)
 				}
 			} else {
 				false
@@ -852,7 +1662,10 @@ impl<T: Config> Pallet<T> {
 			}
 
 			Ok(())
-		})
+		})?;
+
+		T::OnWithdraw::post_withdraw(currency_id, who, amount);
+		Ok(())
 	}
 
 	/// Deposit some `value` into the free balance of `who`.
@@ -877,7 +1690,11 @@ impl<T: Config> Pallet<T> {
 			return Ok(());
 		}
 
+		T::OnDeposit::pre_deposit(currency_id, who, amount)?;
+
 		Self::try_mutate_account(who, currency_id, |account, existed| -> DispatchResult {
+			Self::ensure_can_receive(currency_id, who)?;
+
 			if require_existed {
 				ensure!(existed, Error::<T>::DeadAccount);
 			} else {
@@ -885,7 +1702,7 @@ impl<T: Config> Pallet<T> {
 				// Note: if who is in dust removal whitelist, allow to deposit the amount that
 				// below ED to it.
 				ensure!(
-					amount >= ed || existed || T::DustRemovalWhitelist::contains(who),
+					amount >= ed || existed || Self::is_dust_removal_whitelisted(who, currency_id),
 					Error::<T>::ExistentialDeposit
 				);
 			}
@@ -894,12 +1711,16 @@ impl<T: Config> Pallet<T> {
 				.checked_add(&amount)
 				.ok_or(ArithmeticError::Overflow)?;
 			if change_total_issuance {
-				TotalIssuance::<T>::mutate(currency_id, |v| *v = new_total_issuance);
+				Self::ensure_max_issuance(currency_id, new_total_issuance)?;
+				Self::update_total_issuance(currency_id, new_total_issuance);
 			}
 			account.free += amount;
 
 			Ok(())
-		})
+		})?;
+
+		T::OnDeposit::post_deposit(currency_id, who, amount);
+		Ok(())
 	}
 }
 
@@ -989,7 +1810,11 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 
 		// Cannot underflow because the slashed value cannot be greater than total
 		// issuance
-		TotalIssuance::<T>::mutate(currency_id, |v| *v -= amount - remaining_slash);
+		let slashed = amount - remaining_slash;
+		TotalIssuance::<T>::mutate(currency_id, |v| *v -= slashed);
+		if !slashed.is_zero() {
+			T::OnSlash::on_slash(currency_id, who, slashed);
+		}
 		remaining_slash
 	}
 }
@@ -1030,11 +1855,49 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		currency_id: Self::CurrencyId,
 		who: &T::AccountId,
 		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::set_lock_with_reasons(lock_id, currency_id, who, amount, WithdrawReasons::all())
+	}
+
+	// Extend a lock on the balance of `who` under `currency_id`.
+	// Is a no-op if lock amount is zero
+	fn extend_lock(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::extend_lock_with_reasons(lock_id, currency_id, who, amount, WithdrawReasons::all())
+	}
+
+	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
+		let mut locks = Self::locks(who, currency_id);
+		locks.retain(|lock| lock.id != lock_id);
+		let locks_vec = locks.to_vec();
+		Self::update_locks(currency_id, who, &locks_vec[..])
+	}
+
+	// Set a lock on the balance of `who` under `currency_id` for `reasons`.
+	// Is a no-op if lock amount is zero, and removes the lock if `reasons` is
+	// empty.
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
 	) -> DispatchResult {
 		if amount.is_zero() {
 			return Ok(());
 		}
-		let mut new_lock = Some(BalanceLock { id: lock_id, amount });
+		if reasons.is_empty() {
+			return Self::remove_lock(lock_id, currency_id, who);
+		}
+		let mut new_lock = Some(BalanceLock {
+			id: lock_id,
+			amount,
+			reasons: reasons.into(),
+		});
 		let mut locks = Self::locks(who, currency_id)
 			.into_iter()
 			.filter_map(|lock| {
@@ -1051,18 +1914,24 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		Self::update_locks(currency_id, who, &locks[..])
 	}
 
-	// Extend a lock on the balance of `who` under `currency_id`.
-	// Is a no-op if lock amount is zero
-	fn extend_lock(
+	// Extend a lock on the balance of `who` under `currency_id` for
+	// `reasons`.
+	// Is a no-op if lock amount is zero or `reasons` is empty.
+	fn extend_lock_with_reasons(
 		lock_id: LockIdentifier,
 		currency_id: Self::CurrencyId,
 		who: &T::AccountId,
 		amount: Self::Balance,
+		reasons: WithdrawReasons,
 	) -> DispatchResult {
-		if amount.is_zero() {
+		if amount.is_zero() || reasons.is_empty() {
 			return Ok(());
 		}
-		let mut new_lock = Some(BalanceLock { id: lock_id, amount });
+		let mut new_lock = Some(BalanceLock {
+			id: lock_id,
+			amount,
+			reasons: reasons.into(),
+		});
 		let mut locks = Self::locks(who, currency_id)
 			.into_iter()
 			.filter_map(|lock| {
@@ -1070,6 +1939,7 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 					new_lock.take().map(|nl| BalanceLock {
 						id: lock.id,
 						amount: lock.amount.max(nl.amount),
+						reasons: lock.reasons | nl.reasons,
 					})
 				} else {
 					Some(lock)
@@ -1082,11 +1952,30 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		Self::update_locks(currency_id, who, &locks[..])
 	}
 
-	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
-		let mut locks = Self::locks(who, currency_id);
-		locks.retain(|lock| lock.id != lock_id);
-		let locks_vec = locks.to_vec();
-		Self::update_locks(currency_id, who, &locks_vec[..])
+	fn ensure_can_withdraw_with_reasons(
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::ensure_can_withdraw_with_reasons(currency_id, who, amount, reasons)
+	}
+
+	fn withdraw_with_reasons(
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		// allow death
+		Self::do_withdraw_with_reasons(
+			currency_id,
+			who,
+			amount,
+			reasons,
+			ExistenceRequirement::AllowDeath,
+			true,
+		)
 	}
 }
 
@@ -1114,6 +2003,9 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		let actual = reserved_balance.min(value);
 		Self::set_reserved_balance(currency_id, who, reserved_balance - actual);
 		TotalIssuance::<T>::mutate(currency_id, |v| *v -= actual);
+		if !actual.is_zero() {
+			T::OnSlash::on_slash(currency_id, who, actual);
+		}
 		value - actual
 	}
 
@@ -1129,7 +2021,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		if value.is_zero() {
 			return Ok(());
 		}
-		Self::ensure_can_withdraw(currency_id, who, value)?;
+		Self::ensure_can_withdraw_with_reasons(currency_id, who, value, WithdrawReasons::RESERVE)?;
 
 		let account = Self::accounts(who, currency_id);
 		Self::set_free_balance(currency_id, who, account.free - value);
@@ -1184,9 +2076,12 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 			};
 		}
 
+		Self::ensure_can_receive(currency_id, beneficiary)?;
+
 		let from_account = Self::accounts(slashed, currency_id);
 		let to_account = Self::accounts(beneficiary, currency_id);
 		let actual = from_account.reserved.min(value);
+		T::OnTransfer::pre_transfer(currency_id, slashed, beneficiary, actual)?;
 		match status {
 			BalanceStatus::Free => {
 				Self::set_free_balance(currency_id, beneficiary, to_account.free + actual);
@@ -1196,10 +2091,258 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 			}
 		}
 		Self::set_reserved_balance(currency_id, slashed, from_account.reserved - actual);
+		T::OnTransfer::post_transfer(currency_id, slashed, beneficiary, actual);
 		Ok(value - actual)
 	}
 }
 
+impl<T: Config> MultiReducibleCurrency<T::AccountId> for Pallet<T> {
+	fn held_currencies(who: &T::AccountId, limit: u32) -> Vec<Self::CurrencyId> {
+		Accounts::<T>::iter_key_prefix(who).take(limit as usize).collect()
+	}
+
+	fn reducible_balance(currency_id: Self::CurrencyId, who: &T::AccountId, keep_alive: bool) -> Self::Balance {
+		<Self as fungibles::Inspect<_>>::reducible_balance(currency_id, who, keep_alive)
+	}
+}
+
+impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
+	type ReserveIdentifier = T::ReserveIdentifier;
+
+	fn reserved_balance_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+	) -> Self::Balance {
+		let reserves = Self::reserves(who, currency_id);
+		reserves
+			.binary_search_by_key(id, |data| data.id)
+			.map(|index| reserves[index].amount)
+			.unwrap_or_default()
+	}
+
+	/// Move `value` from the free balance from `who` to a named reserve
+	/// balance.
+	///
+	/// Is a no-op if value to be reserved is zero.
+	fn reserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> DispatchResult {
+		if value.is_zero() {
+			return Ok(());
+		}
+
+		Reserves::<T>::try_mutate(who, currency_id, |reserves| -> DispatchResult {
+			match reserves.binary_search_by_key(id, |data| data.id) {
+				Ok(index) => {
+					// this add can't overflow but just to be defensive.
+					reserves[index].amount = reserves[index].amount.saturating_add(value);
+				}
+				Err(index) => {
+					reserves
+						.try_insert(index, ReserveData { id: *id, amount: value })
+						.map_err(|_| Error::<T>::TooManyReserves)?;
+				}
+			};
+			<Self as MultiReservableCurrency<_>>::reserve(currency_id, who, value)
+		})
+	}
+
+	/// Unreserve some funds, returning any amount that was unable to be
+	/// unreserved.
+	///
+	/// Is a no-op if the value to be unreserved is zero.
+	fn unreserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Self::Balance {
+		if value.is_zero() {
+			return Zero::zero();
+		}
+
+		Reserves::<T>::mutate_exists(who, currency_id, |maybe_reserves| -> Self::Balance {
+			if let Some(reserves) = maybe_reserves.as_mut() {
+				match reserves.binary_search_by_key(id, |data| data.id) {
+					Ok(index) => {
+						let to_change = cmp::min(reserves[index].amount, value);
+
+						let remain = <Self as MultiReservableCurrency<_>>::unreserve(currency_id, who, to_change);
+
+						// remain should always be zero but just to be defensive here
+						let actual = to_change.saturating_sub(remain);
+
+						// `actual <= to_change` and `to_change <= amount`; qed;
+						reserves[index].amount -= actual;
+
+						if reserves[index].amount.is_zero() {
+							if reserves.len() == 1 {
+								// no more named reserves
+								*maybe_reserves = None;
+							} else {
+								// remove this named reserve
+								reserves.remove(index);
+							}
+						}
+						value - actual
+					}
+					Err(_) => value,
+				}
+			} else {
+				value
+			}
+		})
+	}
+
+	/// Slash from reserved balance, returning the amount that was unable to be
+	/// slashed.
+	///
+	/// Is a no-op if the value to be slashed is zero.
+	fn slash_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Self::Balance {
+		if value.is_zero() {
+			return Zero::zero();
+		}
+
+		Reserves::<T>::mutate(who, currency_id, |reserves| -> Self::Balance {
+			match reserves.binary_search_by_key(id, |data| data.id) {
+				Ok(index) => {
+					let to_change = cmp::min(reserves[index].amount, value);
+
+					let remain = <Self as MultiReservableCurrency<_>>::slash_reserved(currency_id, who, to_change);
+
+					// remain should always be zero but just to be defensive here
+					let actual = to_change.saturating_sub(remain);
+
+					// `actual <= to_change` and `to_change <= amount`; qed;
+					reserves[index].amount -= actual;
+
+					value - actual
+				}
+				Err(_) => value,
+			}
+		})
+	}
+
+	/// Move the reserved balance of one account into the balance of another,
+	/// according to `status`. If `status` is `Reserved`, the balance will be
+	/// reserved with given `id`.
+	///
+	/// Is a no-op if:
+	/// - the value to be moved is zero; or
+	/// - the `slashed` id equal to `beneficiary` and the `status` is
+	///   `Reserved`.
+	fn repatriate_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: Status,
+	) -> Result<Self::Balance, DispatchError> {
+		if value.is_zero() {
+			return Ok(Zero::zero());
+		}
+
+		if slashed == beneficiary {
+			return match status {
+				Status::Free => Ok(Self::unreserve_named(id, currency_id, slashed, value)),
+				Status::Reserved => Ok(value.saturating_sub(Self::reserved_balance_named(id, currency_id, slashed))),
+			};
+		}
+
+		Reserves::<T>::try_mutate(
+			slashed,
+			currency_id,
+			|reserves| -> Result<Self::Balance, DispatchError> {
+				match reserves.binary_search_by_key(id, |data| data.id) {
+					Ok(index) => {
+						let to_change = cmp::min(reserves[index].amount, value);
+
+						let actual = if status == Status::Reserved {
+							// make it the reserved under same identifier
+							Reserves::<T>::try_mutate(
+								beneficiary,
+								currency_id,
+								|reserves| -> Result<T::Balance, DispatchError> {
+									match reserves.binary_search_by_key(id, |data| data.id) {
+										Ok(index) => {
+											let remain = <Self as MultiReservableCurrency<_>>::repatriate_reserved(
+												currency_id,
+												slashed,
+												beneficiary,
+												to_change,
+												status,
+											)?;
+
+											// remain should always be zero but just to be defensive here
+											let actual = to_change.saturating_sub(remain);
+
+											// this add can't overflow but just to be defensive.
+											reserves[index].amount = reserves[index].amount.saturating_add(actual);
+
+											Ok(actual)
+										}
+										Err(index) => {
+											let remain = <Self as MultiReservableCurrency<_>>::repatriate_reserved(
+												currency_id,
+												slashed,
+												beneficiary,
+												to_change,
+												status,
+											)?;
+
+											// remain should always be zero but just to be defensive here
+											let actual = to_change.saturating_sub(remain);
+
+											reserves
+												.try_insert(
+													index,
+													ReserveData {
+														id: *id,
+														amount: actual,
+													},
+												)
+												.map_err(|_| Error::<T>::TooManyReserves)?;
+
+											Ok(actual)
+										}
+									}
+								},
+							)?
+						} else {
+							let remain = <Self as MultiReservableCurrency<_>>::repatriate_reserved(
+								currency_id,
+								slashed,
+								beneficiary,
+								to_change,
+								status,
+							)?;
+
+							// remain should always be zero but just to be defensive here
+							to_change.saturating_sub(remain)
+						};
+
+						// `actual <= to_change` and `to_change <= amount`; qed;
+						reserves[index].amount -= actual;
+
+						Ok(value - actual)
+					}
+					Err(_) => Ok(value),
+				}
+			},
+		)
+	}
+}
+
 impl<T: Config> fungibles::Inspect<T::AccountId> for Pallet<T> {
 	type AssetId = T::CurrencyId;
 	type Balance = T::Balance;
@@ -1217,10 +2360,14 @@ impl<T: Config> fungibles::Inspect<T::AccountId> for Pallet<T> {
 	}
 
 	fn reducible_balance(asset_id: Self::AssetId, who: &T::AccountId, keep_alive: bool) -> Self::Balance {
+		if Self::ensure_not_frozen(asset_id, who).is_err() {
+			return Zero::zero();
+		}
+
 		let a = Self::accounts(who, asset_id);
 		// Liquid balance is what is neither reserved nor locked/frozen.
-		let liquid = a.free.saturating_sub(a.frozen);
-		if frame_system::Pallet::<T>::can_dec_provider(who) && !keep_alive {
+		let liquid = a.free.saturating_sub(a.frozen(Reasons::All));
+		if Self::can_dec_account_reference(asset_id, who) && !keep_alive {
 			liquid
 		} else {
 			// `must_remain_to_exist` is the part of liquid balance which must remain to
@@ -1245,6 +2392,7 @@ impl<T: Config> fungibles::Inspect<T::AccountId> for Pallet<T> {
 
 impl<T: Config> fungibles::Mutate<T::AccountId> for Pallet<T> {
 	fn mint_into(asset_id: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		Self::ensure_max_issuance(asset_id, Self::total_issuance(asset_id).saturating_add(amount))?;
 		Self::deposit_consequence(who, asset_id, amount, &Self::accounts(who, asset_id)).into_result()?;
 		// do not require existing
 		Self::do_deposit(asset_id, who, amount, false, true)
@@ -1279,16 +2427,45 @@ impl<T: Config> fungibles::Transfer<T::AccountId> for Pallet<T> {
 	}
 }
 
+/// The credit of a currency, e.g. withdrawn by `fungibles::Balanced`, which
+/// decreases the total issuance if dropped.
+pub type CreditOf<T> = fungibles::CreditOf<<T as frame_system::Config>::AccountId, Pallet<T>>;
+
+/// The debt of a currency, e.g. deposited by `fungibles::Balanced`, which
+/// increases the total issuance if dropped.
+pub type DebtOf<T> = fungibles::DebtOf<<T as frame_system::Config>::AccountId, Pallet<T>>;
+
 impl<T: Config> fungibles::Unbalanced<T::AccountId> for Pallet<T> {
 	fn set_balance(asset_id: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
-		// Balance is the same type and will not overflow
-		Self::mutate_account(who, asset_id, |account, _| account.free = amount);
+		// `fungibles::Inspect::balance` is the total balance, the reserved balance is
+		// kept and the free balance makes up the rest.
+		let account = Self::accounts(who, asset_id);
+		let new_free = amount
+			.checked_sub(&account.reserved)
+			.ok_or(ArithmeticError::Underflow)?;
+
+		// the change of the free balance is a withdrawal or a deposit, with the same
+		// checks and hooks
+		if new_free < account.free {
+			let withdrawn = account.free.saturating_sub(new_free);
+			Self::ensure_not_frozen(asset_id, who)?;
+			T::OnWithdraw::pre_withdraw(asset_id, who, withdrawn)?;
+			Self::set_free_balance(asset_id, who, new_free);
+			T::OnWithdraw::post_withdraw(asset_id, who, withdrawn);
+		} else if new_free > account.free {
+			let deposited = new_free.saturating_sub(account.free);
+			Self::ensure_can_receive(asset_id, who)?;
+			Self::ensure_max_issuance(asset_id, Self::total_issuance(asset_id).saturating_add(deposited))?;
+			T::OnDeposit::pre_deposit(asset_id, who, deposited)?;
+			Self::set_free_balance(asset_id, who, new_free);
+			T::OnDeposit::post_deposit(asset_id, who, deposited);
+		}
 		Ok(())
 	}
 
+	// the max issuance is checked by the deposits of `set_balance`
 	fn set_total_issuance(asset_id: Self::AssetId, amount: Self::Balance) {
-		// Balance is the same type and will not overflow
-		TotalIssuance::<T>::mutate(asset_id, |t| *t = amount);
+		Self::update_total_issuance(asset_id, amount);
 	}
 }
 
@@ -1299,7 +2476,7 @@ impl<T: Config> fungibles::InspectHold<T::AccountId> for Pallet<T> {
 
 	fn can_hold(asset_id: Self::AssetId, who: &T::AccountId, amount: T::Balance) -> bool {
 		let a = Self::accounts(who, asset_id);
-		let min_balance = T::ExistentialDeposits::get(&asset_id).max(a.frozen);
+		let min_balance = T::ExistentialDeposits::get(&asset_id).max(a.frozen(Reasons::All));
 		if a.reserved.checked_add(&amount).is_none() {
 			return false;
 		}
@@ -1365,6 +2542,27 @@ impl<T: Config> fungibles::MutateHold<T::AccountId> for Pallet<T> {
 
 pub struct CurrencyAdapter<T, GetCurrencyId>(marker::PhantomData<(T, GetCurrencyId)>);
 
+impl<T: Config> metadata::Inspect<T::AccountId> for Pallet<T> {
+	fn name(asset: &Self::AssetId) -> Vec<u8> {
+		Self::metadata(asset).name.into_inner()
+	}
+
+	fn symbol(asset: &Self::AssetId) -> Vec<u8> {
+		Self::metadata(asset).symbol.into_inner()
+	}
+
+	fn decimals(asset: &Self::AssetId) -> u8 {
+		Self::metadata(asset).decimals
+	}
+}
+
+impl<T: Config> metadata::Mutate<T::AccountId> for Pallet<T> {
+	fn set(asset: Self::AssetId, from: &T::AccountId, name: Vec<u8>, symbol: Vec<u8>, decimals: u8) -> DispatchResult {
+		T::MetadataOrigin::ensure_origin(frame_system::RawOrigin::Signed(from.clone()).into())?;
+		Self::do_set_metadata(asset, name, symbol, decimals)
+	}
+}
+
 impl<T, GetCurrencyId> PalletCurrency<T::AccountId> for CurrencyAdapter<T, GetCurrencyId>
 where
 	T: Config,
@@ -1407,12 +2605,18 @@ where
 		if amount.is_zero() {
 			return NegativeImbalance::zero();
 		}
-		TotalIssuance::<T>::mutate(GetCurrencyId::get(), |issued| {
-			*issued = issued.checked_add(&amount).unwrap_or_else(|| {
-				amount = Self::Balance::max_value() - *issued;
-				Self::Balance::max_value()
-			})
-		});
+		let currency_id = GetCurrencyId::get();
+		// issue at most up to the max issuance
+		let max_issuance = T::MaxIssuance::get(&currency_id).unwrap_or_else(Self::Balance::max_value);
+		let issued = TotalIssuance::<T>::get(currency_id);
+		let new_total_issuance = issued
+			.checked_add(&amount)
+			.filter(|new_total_issuance| *new_total_issuance <= max_issuance)
+			.unwrap_or_else(|| {
+				amount = max_issuance.saturating_sub(issued);
+				issued.max(max_issuance)
+			});
+		Pallet::<T>::update_total_issuance(currency_id, new_total_issuance);
 		NegativeImbalance::new(amount)
 	}
 
@@ -1423,10 +2627,10 @@ where
 	fn ensure_can_withdraw(
 		who: &T::AccountId,
 		amount: Self::Balance,
-		_reasons: WithdrawReasons,
+		reasons: WithdrawReasons,
 		_new_balance: Self::Balance,
 	) -> DispatchResult {
-		<Pallet<T> as MultiCurrency<_>>::ensure_can_withdraw(GetCurrencyId::get(), who, amount)
+		Pallet::<T>::ensure_can_withdraw_with_reasons(GetCurrencyId::get(), who, amount, reasons)
 	}
 
 	fn transfer(
@@ -1458,11 +2662,13 @@ where
 			let reserved_slashed_amount = account.reserved.min(remaining_slash);
 			remaining_slash -= reserved_slashed_amount;
 			Pallet::<T>::set_reserved_balance(currency_id, who, account.reserved - reserved_slashed_amount);
-			(
-				Self::NegativeImbalance::new(free_slashed_amount + reserved_slashed_amount),
-				remaining_slash,
-			)
+			let slashed = free_slashed_amount + reserved_slashed_amount;
+			if !slashed.is_zero() {
+				T::OnSlash::on_slash(currency_id, who, slashed);
+			}
+			(Self::NegativeImbalance::new(slashed), remaining_slash)
 		} else {
+			T::OnSlash::on_slash(currency_id, who, value);
 			(Self::NegativeImbalance::new(value), remaining_slash)
 		}
 	}
@@ -1488,11 +2694,11 @@ where
 	fn withdraw(
 		who: &T::AccountId,
 		value: Self::Balance,
-		_reasons: WithdrawReasons,
+		reasons: WithdrawReasons,
 		liveness: ExistenceRequirement,
 	) -> sp_std::result::Result<Self::NegativeImbalance, DispatchError> {
 		// do not change total issuance
-		Pallet::<T>::do_withdraw(GetCurrencyId::get(), who, value, liveness, false)
+		Pallet::<T>::do_withdraw_with_reasons(GetCurrencyId::get(), who, value, reasons, liveness, false)
 			.map(|_| Self::NegativeImbalance::new(value))
 	}
 
@@ -1514,6 +2720,10 @@ where
 				// underflow the issuance and our arithmetic will be off.
 				let ed = T::ExistentialDeposits::get(&currency_id);
 				ensure!(value.saturating_add(account.reserved) >= ed || existed, ());
+				ensure!(
+					existed || value.is_zero() || Pallet::<T>::ensure_can_create_account(currency_id, who).is_ok(),
+					()
+				);
 
 				let imbalance = if account.free <= value {
 					SignedImbalance::Positive(PositiveImbalance::new(value - account.free))
@@ -1578,12 +2788,24 @@ where
 	type Moment = T::BlockNumber;
 	type MaxLocks = ();
 
-	fn set_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, _reasons: WithdrawReasons) {
-		let _ = <Pallet<T> as MultiLockableCurrency<_>>::set_lock(id, GetCurrencyId::get(), who, amount);
+	fn set_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) {
+		let _ = <Pallet<T> as MultiLockableCurrency<_>>::set_lock_with_reasons(
+			id,
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		);
 	}
 
-	fn extend_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, _reasons: WithdrawReasons) {
-		let _ = <Pallet<T> as MultiLockableCurrency<_>>::extend_lock(id, GetCurrencyId::get(), who, amount);
+	fn extend_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) {
+		let _ = <Pallet<T> as MultiLockableCurrency<_>>::extend_lock_with_reasons(
+			id,
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		);
 	}
 
 	fn remove_lock(id: LockIdentifier, who: &T::AccountId) {
@@ -1591,6 +2813,96 @@ where
 	}
 }
 
+impl<T: Config> orml_traits::Approvals<T::AccountId> for Pallet<T> {
+	fn allowance(currency_id: Self::CurrencyId, owner: &T::AccountId, delegate: &T::AccountId) -> Self::Balance {
+		Self::approvals((currency_id, owner, delegate))
+			.map(|approval| approval.amount)
+			.unwrap_or_default()
+	}
+
+	fn approve(
+		currency_id: Self::CurrencyId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			if Approvals::<T>::contains_key((currency_id, owner, delegate)) {
+				return <Self as orml_traits::Approvals<_>>::cancel_approval(currency_id, owner, delegate);
+			}
+			return Ok(());
+		}
+
+		Approvals::<T>::try_mutate((currency_id, owner, delegate), |maybe_approval| -> DispatchResult {
+			match maybe_approval {
+				Some(approval) => approval.amount = amount,
+				None => {
+					let deposit = T::ApprovalDeposit::get();
+					T::DepositCurrency::reserve(owner, deposit)?;
+					*maybe_approval = Some(Approval { amount, deposit });
+				}
+			}
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::ApprovedTransfer(
+			currency_id,
+			owner.clone(),
+			delegate.clone(),
+			amount,
+		));
+		Ok(())
+	}
+
+	fn cancel_approval(currency_id: Self::CurrencyId, owner: &T::AccountId, delegate: &T::AccountId) -> DispatchResult {
+		let approval = Approvals::<T>::take((currency_id, owner, delegate)).ok_or(Error::<T>::Unapproved)?;
+		T::DepositCurrency::unreserve(owner, approval.deposit);
+
+		Self::deposit_event(Event::ApprovalCancelled(currency_id, owner.clone(), delegate.clone()));
+		Ok(())
+	}
+
+	fn transfer_approved(
+		currency_id: Self::CurrencyId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+		to: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		// the deposit to unreserve if the allowance is used up
+		let used_up_deposit = Approvals::<T>::try_mutate_exists(
+			(currency_id, owner, delegate),
+			|maybe_approval| -> Result<Option<DepositBalanceOf<T>>, DispatchError> {
+				let approval = maybe_approval.as_mut().ok_or(Error::<T>::Unapproved)?;
+				approval.amount = approval.amount.checked_sub(&amount).ok_or(Error::<T>::Unapproved)?;
+
+				Self::do_transfer(currency_id, owner, to, amount, ExistenceRequirement::AllowDeath)?;
+
+				if approval.amount.is_zero() {
+					let deposit = approval.deposit;
+					*maybe_approval = None;
+					Ok(Some(deposit))
+				} else {
+					Ok(None)
+				}
+			},
+		)?;
+
+		if let Some(deposit) = used_up_deposit {
+			T::DepositCurrency::unreserve(owner, deposit);
+		}
+
+		Self::deposit_event(Event::TransferredApproved(
+			currency_id,
+			owner.clone(),
+			delegate.clone(),
+			to.clone(),
+			amount,
+		));
+		Ok(())
+	}
+}
+
 impl<T: Config> TransferAll<T::AccountId> for Pallet<T> {
 	#[transactional]
 	fn transfer_all(source: &T::AccountId, dest: &T::AccountId) -> DispatchResult {
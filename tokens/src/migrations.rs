@@ -0,0 +1,218 @@
+//! Storage migrations for the tokens module.
+//!
+//! Each storage change bumps `STORAGE_VERSION` and ships a `vN` module
+//! holding the migration from the previous version. Replaced layouts are kept
+//! in `vN-1` so that they can be decoded in migrations and in the
+//! `try-runtime` checks.
+
+use super::*;
+use sp_std::collections::btree_map::BTreeMap;
+
+/// Storage layouts before `StorageVersion` 1.
+pub mod v0 {
+	use super::*;
+
+	/// A single lock on a balance, without withdraw reasons.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct BalanceLock<Balance> {
+		pub id: LockIdentifier,
+		pub amount: Balance,
+	}
+
+	/// Balance information for an account, with a single frozen balance.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+	pub struct AccountData<Balance> {
+		pub free: Balance,
+		pub reserved: Balance,
+		pub frozen: Balance,
+	}
+}
+
+/// Migrate storage to the current `STORAGE_VERSION`.
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	if StorageVersion::get::<Pallet<T>>() < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+		StorageVersion::new(1).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	if StorageVersion::get::<Pallet<T>>() < 2 {
+		weight = weight.saturating_add(v2::migrate::<T>());
+		StorageVersion::new(2).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	weight
+}
+
+/// Checks the on-chain state before `migrate` is applied.
+#[cfg(any(feature = "try-runtime", test))]
+pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+	ensure!(
+		StorageVersion::get::<Pallet<T>>() <= STORAGE_VERSION,
+		"tokens: unknown on-chain storage version"
+	);
+	ensure_total_issuance::<T>()
+}
+
+/// Checks the on-chain state after `migrate` is applied.
+#[cfg(any(feature = "try-runtime", test))]
+pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+	ensure!(
+		StorageVersion::get::<Pallet<T>>() == STORAGE_VERSION,
+		"tokens: storage version not updated"
+	);
+	ensure_total_issuance::<T>()?;
+	ensure_holders::<T>()
+}
+
+/// Ensure `HoldersCount`, and `Holders` if maintained, match `Accounts`.
+#[cfg(any(feature = "try-runtime", test))]
+pub fn ensure_holders<T: Config>() -> Result<(), &'static str> {
+	let mut counts: BTreeMap<T::CurrencyId, u32> = BTreeMap::new();
+	for (who, currency_id) in Accounts::<T>::iter_keys() {
+		*counts.entry(currency_id).or_default() += 1;
+		ensure!(
+			!T::MaintainHoldersIndex::get() || Holders::<T>::contains_key(currency_id, &who),
+			"tokens: account missing from holders index"
+		);
+	}
+
+	for (currency_id, count) in HoldersCount::<T>::iter() {
+		ensure!(
+			counts.remove(&currency_id).unwrap_or_default() == count,
+			"tokens: holders count mismatch"
+		);
+	}
+	ensure!(counts.is_empty(), "tokens: holders without holders count");
+
+	if T::MaintainHoldersIndex::get() {
+		for (currency_id, who, _) in Holders::<T>::iter() {
+			ensure!(
+				Accounts::<T>::contains_key(&who, currency_id),
+				"tokens: stale holders index entry"
+			);
+		}
+	}
+
+	Ok(())
+}
+
+/// Ensure `TotalIssuance` of every currency equals the sum of the free and
+/// reserved balances in `Accounts`, decoded with the on-chain layout, and of
+/// the `AccumulatedDust` and `DeferredDust`.
+#[cfg(any(feature = "try-runtime", test))]
+pub fn ensure_total_issuance<T: Config>() -> Result<(), &'static str> {
+	let version = StorageVersion::get::<Pallet<T>>();
+	let mut totals: BTreeMap<T::CurrencyId, T::Balance> = BTreeMap::new();
+
+	for (who, currency_id) in Accounts::<T>::iter_keys() {
+		let key = Accounts::<T>::hashed_key_for(&who, currency_id);
+		let total = if version < 1 {
+			frame_support::storage::unhashed::get::<v0::AccountData<T::Balance>>(&key)
+				.map(|account| account.free.checked_add(&account.reserved))
+		} else {
+			frame_support::storage::unhashed::get::<AccountData<T::Balance>>(&key)
+				.map(|account| account.free.checked_add(&account.reserved))
+		}
+		.ok_or("tokens: undecodable account data")?
+		.ok_or("tokens: account balance overflow")?;
+
+		let sum = totals.entry(currency_id).or_default();
+		*sum = sum.checked_add(&total).ok_or("tokens: total balance overflow")?;
+	}
+
+	for (currency_id, dust) in AccumulatedDust::<T>::iter().chain(DeferredDust::<T>::iter()) {
+		let sum = totals.entry(currency_id).or_default();
+		*sum = sum.checked_add(&dust).ok_or("tokens: total balance overflow")?;
+	}
+
+	for (currency_id, issuance) in TotalIssuance::<T>::iter() {
+		ensure!(
+			totals.remove(&currency_id).unwrap_or_default() == issuance,
+			"tokens: total issuance mismatch"
+		);
+	}
+	ensure!(
+		totals.values().all(|total| total.is_zero()),
+		"tokens: balance without total issuance"
+	);
+
+	Ok(())
+}
+
+/// Migrate to `StorageVersion` 1: locks carry withdraw reasons, and the
+/// frozen balance of accounts is split into `misc_frozen` and `fee_frozen`.
+///
+/// Existing locks restrict withdrawals for all reasons, as before.
+pub mod v1 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut translated: Weight = 0;
+
+		Accounts::<T>::translate::<v0::AccountData<T::Balance>, _>(|_, _, old| {
+			translated += 1;
+			Some(AccountData {
+				free: old.free,
+				reserved: old.reserved,
+				misc_frozen: old.frozen,
+				fee_frozen: old.frozen,
+			})
+		});
+
+		Locks::<T>::translate::<Vec<v0::BalanceLock<T::Balance>>, _>(|_, _, old| {
+			translated += 1;
+			old.into_iter()
+				.map(|lock| BalanceLock {
+					id: lock.id,
+					amount: lock.amount,
+					reasons: Reasons::All,
+				})
+				.collect::<Vec<_>>()
+				// the number of locks is bounded by the same `MaxLocks`
+				.try_into()
+				.ok()
+		});
+
+		T::DbWeight::get().reads_writes(translated, translated)
+	}
+}
+
+/// Migrate to `StorageVersion` 2: count the holders of each currency, and
+/// build the `Holders` index if it is maintained.
+///
+/// NOTE: The migration is not paged, it reads every `Accounts` entry and
+/// writes one `Holders` entry per account, plus one `HoldersCount` entry per
+/// currency, in the upgrade block. The returned weight accounts for all of
+/// them, so runtimes must check with `try-runtime` that it fits in a block.
+/// With `RocksDbWeight` and a 2 second block, that is about 16,000 accounts
+/// if the `Holders` index is maintained, and 80,000 otherwise. Larger chains
+/// need to build the index over several blocks instead.
+pub mod v2 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut reads: Weight = 0;
+		let mut writes: Weight = 0;
+		let mut counts: BTreeMap<T::CurrencyId, u32> = BTreeMap::new();
+
+		for (who, currency_id) in Accounts::<T>::iter_keys() {
+			reads += 1;
+			*counts.entry(currency_id).or_default() += 1;
+			if T::MaintainHoldersIndex::get() {
+				Holders::<T>::insert(currency_id, &who, ());
+				writes += 1;
+			}
+		}
+
+		for (currency_id, count) in counts {
+			HoldersCount::<T>::insert(currency_id, count);
+			writes += 1;
+		}
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
@@ -5,9 +5,14 @@
 use super::*;
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{ChangeMembers, ContainsLengthBound, Everything, GenesisBuild, SaturatingCurrencyToVote, SortedMembers},
+	traits::{
+		ChangeMembers, ContainsLengthBound, Everything, GenesisBuild, OnGenesis, SaturatingCurrencyToVote,
+		SortedMembers,
+	},
+	weights::constants::RocksDbWeight,
 	PalletId,
 };
+use frame_system::{EnsureOneOf, EnsureRoot, EnsureSignedBy};
 use orml_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{
@@ -20,18 +25,25 @@ use sp_std::cell::RefCell;
 pub type AccountId = AccountId32;
 pub type CurrencyId = u32;
 pub type Balance = u64;
+pub type ReserveIdentifier = [u8; 8];
 
 pub const DOT: CurrencyId = 1;
 pub const BTC: CurrencyId = 2;
 pub const ETH: CurrencyId = 3;
+pub const KSM: CurrencyId = 4;
+pub const DAI: CurrencyId = 5;
+pub const USDC: CurrencyId = 6;
 pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId = AccountId32::new([1u8; 32]);
 pub const CHARLIE: AccountId = AccountId32::new([2u8; 32]);
 pub const DAVE: AccountId = AccountId32::new([3u8; 32]);
 pub const TREASURY_ACCOUNT: AccountId = AccountId32::new([4u8; 32]);
+pub const EVE: AccountId = AccountId32::new([5u8; 32]);
 pub const ID_1: LockIdentifier = *b"1       ";
 pub const ID_2: LockIdentifier = *b"2       ";
 pub const ID_3: LockIdentifier = *b"3       ";
+pub const RID_1: ReserveIdentifier = [1u8; 8];
+pub const RID_2: ReserveIdentifier = [2u8; 8];
 
 use crate as tokens;
 
@@ -58,7 +70,7 @@ impl frame_system::Config for Runtime {
 	type AccountData = ();
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
-	type DbWeight = ();
+	type DbWeight = RocksDbWeight;
 	type BaseCallFilter = Everything;
 	type SystemWeightInfo = ();
 	type SS58Prefix = ();
@@ -75,6 +87,13 @@ thread_local! {
 	]);
 }
 
+pub struct MetadataAdmin;
+impl SortedMembers<AccountId> for MetadataAdmin {
+	fn sorted_members() -> Vec<AccountId> {
+		vec![DAVE]
+	}
+}
+
 pub struct TenToFourteen;
 impl SortedMembers<AccountId> for TenToFourteen {
 	fn sorted_members() -> Vec<AccountId> {
@@ -204,9 +223,9 @@ impl pallet_elections_phragmen::Config for Runtime {
 }
 
 pub struct MockDustRemovalWhitelist;
-impl Contains<AccountId> for MockDustRemovalWhitelist {
-	fn contains(a: &AccountId) -> bool {
-		*a == DAVE || *a == DustReceiver::get()
+impl Contains<(AccountId, CurrencyId)> for MockDustRemovalWhitelist {
+	fn contains((a, currency_id): &(AccountId, CurrencyId)) -> bool {
+		*a == DAVE || *a == DustReceiver::get() || (*a == EVE && *currency_id == DOT)
 	}
 }
 
@@ -216,14 +235,149 @@ parameter_type_with_key! {
 		match currency_id {
 			&BTC => 1,
 			&DOT => 2,
+			&DAI => 1,
+			&USDC => 1,
 			_ => 0,
 		}
 	};
 }
 
+parameter_type_with_key! {
+	pub MaxIssuance: |currency_id: CurrencyId| -> Option<Balance> {
+		#[allow(clippy::match_ref_pats)] // false positive
+		match currency_id {
+			&KSM => Some(1000),
+			_ => None,
+		}
+	};
+}
+
+parameter_type_with_key! {
+	pub IssuanceHighWaterMark: |currency_id: CurrencyId| -> Option<Balance> {
+		#[allow(clippy::match_ref_pats)] // false positive
+		match currency_id {
+			&KSM => Some(800),
+			_ => None,
+		}
+	};
+}
+
+thread_local! {
+	pub static ALLOW_FROZEN_RECEIVING: RefCell<bool> = RefCell::new(true);
+}
+
+pub struct MockAllowFrozenReceiving;
+impl MockAllowFrozenReceiving {
+	pub fn set(allow: bool) {
+		ALLOW_FROZEN_RECEIVING.with(|v| *v.borrow_mut() = allow);
+	}
+}
+impl Get<bool> for MockAllowFrozenReceiving {
+	fn get() -> bool {
+		ALLOW_FROZEN_RECEIVING.with(|v| *v.borrow())
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HookCall {
+	Transfer(CurrencyId, AccountId, AccountId, Balance),
+	Deposit(CurrencyId, AccountId, Balance),
+	Withdraw(CurrencyId, AccountId, Balance),
+	Slash(CurrencyId, AccountId, Balance),
+	NewTokenAccount(CurrencyId, AccountId),
+	KilledTokenAccount(CurrencyId, AccountId),
+}
+
+thread_local! {
+	pub static HOOK_CALLS: RefCell<Vec<HookCall>> = RefCell::new(vec![]);
+	pub static HOOKS_VETO: RefCell<bool> = RefCell::new(false);
+}
+
+/// Records the calls of post hooks, and vetoes in pre hooks if set.
+pub struct MockHooks;
+impl MockHooks {
+	pub fn set_veto(veto: bool) {
+		HOOKS_VETO.with(|v| *v.borrow_mut() = veto);
+	}
+
+	pub fn take_calls() -> Vec<HookCall> {
+		HOOK_CALLS.with(|v| v.borrow_mut().drain(..).collect())
+	}
+
+	fn pre_hook() -> DispatchResult {
+		if HOOKS_VETO.with(|v| *v.borrow()) {
+			Err(DispatchError::Other("vetoed"))
+		} else {
+			Ok(())
+		}
+	}
+
+	fn post_hook(call: HookCall) {
+		HOOK_CALLS.with(|v| v.borrow_mut().push(call));
+	}
+}
+impl OnTransfer<AccountId, CurrencyId, Balance> for MockHooks {
+	fn pre_transfer(_: CurrencyId, _: &AccountId, _: &AccountId, _: Balance) -> DispatchResult {
+		Self::pre_hook()
+	}
+	fn post_transfer(currency_id: CurrencyId, from: &AccountId, to: &AccountId, amount: Balance) {
+		Self::post_hook(HookCall::Transfer(currency_id, from.clone(), to.clone(), amount));
+	}
+}
+impl OnDeposit<AccountId, CurrencyId, Balance> for MockHooks {
+	fn pre_deposit(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Self::pre_hook()
+	}
+	fn post_deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+		Self::post_hook(HookCall::Deposit(currency_id, who.clone(), amount));
+	}
+}
+impl OnWithdraw<AccountId, CurrencyId, Balance> for MockHooks {
+	fn pre_withdraw(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Self::pre_hook()
+	}
+	fn post_withdraw(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+		Self::post_hook(HookCall::Withdraw(currency_id, who.clone(), amount));
+	}
+}
+impl OnSlash<AccountId, CurrencyId, Balance> for MockHooks {
+	fn on_slash(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+		Self::post_hook(HookCall::Slash(currency_id, who.clone(), amount));
+	}
+}
+impl OnNewTokenAccount<AccountId, CurrencyId> for MockHooks {
+	fn on_new_token_account(who: &AccountId, currency_id: CurrencyId) {
+		Self::post_hook(HookCall::NewTokenAccount(currency_id, who.clone()));
+	}
+}
+impl OnKilledTokenAccount<AccountId, CurrencyId> for MockHooks {
+	fn on_killed_token_account(who: &AccountId, currency_id: CurrencyId) {
+		Self::post_hook(HookCall::KilledTokenAccount(currency_id, who.clone()));
+	}
+}
+
+pub struct MockSufficientCurrencies;
+impl Contains<CurrencyId> for MockSufficientCurrencies {
+	fn contains(currency_id: &CurrencyId) -> bool {
+		*currency_id == DAI
+	}
+}
+
+pub struct MockProviderCurrencies;
+impl Contains<CurrencyId> for MockProviderCurrencies {
+	fn contains(currency_id: &CurrencyId) -> bool {
+		*currency_id != USDC
+	}
+}
+
 parameter_types! {
 	pub DustReceiver: AccountId = PalletId(*b"orml/dst").into_account();
 	pub MaxLocks: u32 = 2;
+	pub MaxReserves: u32 = 2;
+	pub const MaxTransfers: u32 = 3;
+	pub const ApprovalDeposit: Balance = 1;
+	pub const StringLimit: u32 = 8;
+	pub const MaintainHoldersIndex: bool = true;
 }
 
 impl Config for Runtime {
@@ -234,11 +388,37 @@ impl Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = TransferDust<Runtime, DustReceiver>;
+	type OnTransfer = MockHooks;
+	type OnDeposit = MockHooks;
+	type OnWithdraw = MockHooks;
+	type OnSlash = MockHooks;
+	type OnNewTokenAccount = MockHooks;
+	type OnKilledTokenAccount = MockHooks;
+	type SufficientCurrencies = MockSufficientCurrencies;
+	type ProviderCurrencies = MockProviderCurrencies;
 	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type MaxTransfers = MaxTransfers;
+	type DepositCurrency = TreasuryCurrencyAdapter;
+	type ApprovalDeposit = ApprovalDeposit;
+	type ReserveIdentifier = ReserveIdentifier;
 	type DustRemovalWhitelist = MockDustRemovalWhitelist;
+	type DustReceiver = DustReceiver;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MetadataOrigin = EnsureOneOf<AccountId, EnsureRoot<AccountId>, EnsureSignedBy<MetadataAdmin, AccountId>>;
+	type StringLimit = StringLimit;
+	type AllowFrozenReceiving = MockAllowFrozenReceiving;
+	type MaintainHoldersIndex = MaintainHoldersIndex;
+	type MaxIssuance = MaxIssuance;
+	type IssuanceHighWaterMark = IssuanceHighWaterMark;
 }
 pub type TreasuryCurrencyAdapter = <Runtime as pallet_treasury::Config>::Currency;
 
+parameter_types! {
+	pub const GetKsmCurrencyId: CurrencyId = KSM;
+}
+pub type KsmCurrencyAdapter = CurrencyAdapter<Runtime, GetKsmCurrencyId>;
+
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
 type Block = frame_system::mocking::MockBlock<Runtime>;
 
@@ -257,6 +437,7 @@ construct_runtime!(
 
 pub struct ExtBuilder {
 	balances: Vec<(AccountId, CurrencyId, Balance)>,
+	metadata: Vec<(CurrencyId, Vec<u8>, Vec<u8>, u8)>,
 	treasury_genesis: bool,
 }
 
@@ -264,6 +445,7 @@ impl Default for ExtBuilder {
 	fn default() -> Self {
 		Self {
 			balances: vec![],
+			metadata: vec![],
 			treasury_genesis: false,
 		}
 	}
@@ -275,6 +457,11 @@ impl ExtBuilder {
 		self
 	}
 
+	pub fn metadata(mut self, mut metadata: Vec<(CurrencyId, Vec<u8>, Vec<u8>, u8)>) -> Self {
+		self.metadata.append(&mut metadata);
+		self
+	}
+
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
@@ -282,6 +469,7 @@ impl ExtBuilder {
 
 		tokens::GenesisConfig::<Runtime> {
 			balances: self.balances,
+			metadata: self.metadata,
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -297,7 +485,12 @@ impl ExtBuilder {
 		}
 
 		let mut ext = sp_io::TestExternalities::new(t);
-		ext.execute_with(|| System::set_block_number(1));
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			Tokens::on_genesis();
+		});
+		// ignore the hooks called by genesis
+		MockHooks::take_calls();
 		ext
 	}
 }
@@ -95,7 +95,7 @@ fn transfer_all_keep_alive_should_work() {
 			assert_eq!(Tokens::free_balance(DOT, &ALICE), 2);
 
 			assert_ok!(Tokens::set_lock(ID_1, DOT, &BOB, 50));
-			assert_eq!(Tokens::accounts(&BOB, DOT).frozen, 50);
+			assert_eq!(Tokens::accounts(&BOB, DOT).frozen(Reasons::All), 50);
 			assert_eq!(Tokens::free_balance(DOT, &BOB), 100);
 			assert_ok!(Tokens::transfer_all(Some(BOB).into(), CHARLIE, DOT, true));
 			System::assert_has_event(Event::Tokens(crate::Event::Transfer(DOT, BOB, CHARLIE, 50)));
@@ -116,13 +116,64 @@ fn transfer_all_allow_death_should_work() {
 			assert_eq!(Tokens::free_balance(DOT, &ALICE), 0);
 
 			assert_ok!(Tokens::set_lock(ID_1, DOT, &BOB, 50));
-			assert_eq!(Tokens::accounts(&BOB, DOT).frozen, 50);
+			assert_eq!(Tokens::accounts(&BOB, DOT).frozen(Reasons::All), 50);
 			assert_eq!(Tokens::free_balance(DOT, &BOB), 100);
 			assert_ok!(Tokens::transfer_all(Some(BOB).into(), CHARLIE, DOT, false));
 			System::assert_last_event(Event::Tokens(crate::Event::Transfer(DOT, BOB, CHARLIE, 50)));
 		});
 }
 
+#[test]
+fn transfer_multiple_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::transfer_multiple(
+				Some(ALICE).into(),
+				vec![(DOT, BOB, 10), (BTC, BOB, 20), (DOT, CHARLIE, 30)]
+					.try_into()
+					.unwrap()
+			));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 60);
+			assert_eq!(Tokens::free_balance(BTC, &ALICE), 80);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 10);
+			assert_eq!(Tokens::free_balance(BTC, &BOB), 20);
+			assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 30);
+			System::assert_has_event(Event::Tokens(crate::Event::Transfer(DOT, ALICE, BOB, 10)));
+			System::assert_has_event(Event::Tokens(crate::Event::Transfer(BTC, ALICE, BOB, 20)));
+			System::assert_has_event(Event::Tokens(crate::Event::Transfer(DOT, ALICE, CHARLIE, 30)));
+			System::assert_last_event(Event::Tokens(crate::Event::TransferredMultiple(ALICE, 3)));
+		});
+}
+
+#[test]
+fn transfer_multiple_should_be_all_or_nothing() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Tokens::transfer_multiple(
+					Some(ALICE).into(),
+					vec![(DOT, BOB, 10), (BTC, BOB, 20), (DOT, CHARLIE, 100)]
+						.try_into()
+						.unwrap()
+				),
+				Error::<Runtime>::BalanceTooLow
+			);
+
+			assert_noop!(
+				Tokens::transfer_multiple(Some(ALICE).into(), vec![].try_into().unwrap()),
+				Error::<Runtime>::NoTransfers
+			);
+
+			// more than `MaxTransfers` transfers cannot be decoded
+			let transfers = vec![(DOT, BOB, 10u64); 4].encode();
+			assert!(BoundedVec::<(CurrencyId, AccountId, Balance), MaxTransfers>::decode(&mut &transfers[..]).is_err());
+		});
+}
+
 #[test]
 fn force_transfer_should_work() {
 	ExtBuilder::default()
@@ -210,6 +261,112 @@ fn set_balance_should_work() {
 		});
 }
 
+#[test]
+fn freeze_account_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(Tokens::freeze_account(Some(ALICE).into(), DOT, BOB), BadOrigin);
+
+			assert_ok!(Tokens::freeze_account(RawOrigin::Root.into(), DOT, ALICE));
+			System::assert_last_event(Event::Tokens(crate::Event::AccountFrozen(DOT, ALICE)));
+			assert_eq!(Tokens::frozen_accounts(DOT, ALICE), Some(()));
+
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(Tokens::withdraw(DOT, &ALICE, 10), Error::<Runtime>::AccountFrozen);
+			assert_noop!(Tokens::reserve(DOT, &ALICE, 10), Error::<Runtime>::AccountFrozen);
+			assert_noop!(
+				<Tokens as fungibles::Transfer<_>>::transfer(DOT, &ALICE, &BOB, 10, false),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				TreasuryCurrencyAdapter::transfer(&ALICE, &BOB, 10, ExistenceRequirement::AllowDeath),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_withdraw(DOT, &ALICE, 10),
+				WithdrawConsequence::Frozen
+			);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::reducible_balance(DOT, &ALICE, false),
+				0
+			);
+
+			// other currencies are not affected
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, BTC, 10));
+
+			// frozen account can still receive
+			assert_ok!(Tokens::transfer(Some(BOB).into(), ALICE, DOT, 10));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 110);
+
+			assert_ok!(Tokens::thaw_account(RawOrigin::Root.into(), DOT, ALICE));
+			System::assert_last_event(Event::Tokens(crate::Event::AccountThawed(DOT, ALICE)));
+			assert_eq!(Tokens::frozen_accounts(DOT, ALICE), None);
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+		});
+}
+
+#[test]
+fn freeze_currency_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(Tokens::freeze_currency(Some(ALICE).into(), DOT), BadOrigin);
+
+			assert_ok!(Tokens::freeze_currency(RawOrigin::Root.into(), DOT));
+			System::assert_last_event(Event::Tokens(crate::Event::CurrencyFrozen(DOT)));
+			assert_eq!(Tokens::frozen_currencies(DOT), Some(()));
+
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10),
+				Error::<Runtime>::CurrencyFrozen
+			);
+			assert_noop!(
+				Tokens::transfer(Some(BOB).into(), ALICE, DOT, 10),
+				Error::<Runtime>::CurrencyFrozen
+			);
+			assert_noop!(Tokens::withdraw(DOT, &BOB, 10), Error::<Runtime>::CurrencyFrozen);
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, BTC, 10));
+
+			assert_ok!(Tokens::thaw_currency(RawOrigin::Root.into(), DOT));
+			System::assert_last_event(Event::Tokens(crate::Event::CurrencyThawed(DOT)));
+			assert_eq!(Tokens::frozen_currencies(DOT), None);
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10));
+		});
+}
+
+#[test]
+fn frozen_account_receiving_is_configurable() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::freeze_account(RawOrigin::Root.into(), DOT, ALICE));
+			MockAllowFrozenReceiving::set(false);
+
+			assert_noop!(
+				Tokens::transfer(Some(BOB).into(), ALICE, DOT, 10),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(Tokens::deposit(DOT, &ALICE, 10), Error::<Runtime>::AccountFrozen);
+			assert_ok!(Tokens::reserve(DOT, &BOB, 10));
+			assert_noop!(
+				Tokens::repatriate_reserved(DOT, &BOB, &ALICE, 10, BalanceStatus::Free),
+				Error::<Runtime>::AccountFrozen
+			);
+
+			MockAllowFrozenReceiving::set(true);
+			assert_ok!(Tokens::deposit(DOT, &ALICE, 10));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 110);
+		});
+}
+
 // *************************************************
 // tests for inline impl
 // *************************************************
@@ -225,7 +382,8 @@ fn deposit_consequence_should_work() {
 				&AccountData {
 					free: 1,
 					reserved: 0,
-					frozen: 0
+					misc_frozen: 0,
+					fee_frozen: 0
 				}
 			)
 			.into_result(),
@@ -241,7 +399,8 @@ fn deposit_consequence_should_work() {
 				&AccountData {
 					free: 1,
 					reserved: 0,
-					frozen: 0
+					misc_frozen: 0,
+					fee_frozen: 0
 				}
 			)
 			.into_result(),
@@ -257,7 +416,8 @@ fn deposit_consequence_should_work() {
 				&AccountData {
 					free: Balance::max_value(),
 					reserved: 0,
-					frozen: 0
+					misc_frozen: 0,
+					fee_frozen: 0
 				}
 			)
 			.into_result(),
@@ -273,7 +433,8 @@ fn deposit_consequence_should_work() {
 				&AccountData {
 					free: 0,
 					reserved: 0,
-					frozen: 0
+					misc_frozen: 0,
+					fee_frozen: 0
 				}
 			)
 			.into_result(),
@@ -288,7 +449,8 @@ fn deposit_consequence_should_work() {
 				&AccountData {
 					free: 1,
 					reserved: 0,
-					frozen: 0
+					misc_frozen: 0,
+					fee_frozen: 0
 				}
 			)
 			.into_result(),
@@ -308,7 +470,8 @@ fn withdraw_consequence_should_work() {
 				&AccountData {
 					free: 1,
 					reserved: 0,
-					frozen: 0
+					misc_frozen: 0,
+					fee_frozen: 0
 				}
 			)
 			.into_result(),
@@ -326,7 +489,8 @@ fn withdraw_consequence_should_work() {
 				&AccountData {
 					free: 1,
 					reserved: 0,
-					frozen: 0
+					misc_frozen: 0,
+					fee_frozen: 0
 				}
 			)
 			.into_result(),
@@ -342,7 +506,8 @@ fn withdraw_consequence_should_work() {
 				&AccountData {
 					free: 1,
 					reserved: 0,
-					frozen: 0
+					misc_frozen: 0,
+					fee_frozen: 0
 				}
 			)
 			.into_result(),
@@ -362,7 +527,8 @@ fn withdraw_consequence_should_work() {
 				&AccountData {
 					free: 2,
 					reserved: 0,
-					frozen: 0
+					misc_frozen: 0,
+					fee_frozen: 0
 				}
 			)
 			.into_result(),
@@ -380,7 +546,8 @@ fn withdraw_consequence_should_work() {
 				&AccountData {
 					free: 2,
 					reserved: 0,
-					frozen: 0
+					misc_frozen: 0,
+					fee_frozen: 0
 				}
 			)
 			.into_result(),
@@ -396,7 +563,8 @@ fn withdraw_consequence_should_work() {
 				&AccountData {
 					free: 1,
 					reserved: 1,
-					frozen: 0
+					misc_frozen: 0,
+					fee_frozen: 0
 				}
 			)
 			.into_result(),
@@ -412,7 +580,8 @@ fn withdraw_consequence_should_work() {
 				&AccountData {
 					free: 2,
 					reserved: 0,
-					frozen: 2
+					misc_frozen: 2,
+					fee_frozen: 2
 				}
 			)
 			.into_result(),
@@ -1064,6 +1233,121 @@ fn account_survive_due_to_dust_transfer_failure() {
 	});
 }
 
+#[test]
+fn dust_removal_whitelist_should_be_per_currency() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			// eve is in dust removal whitelist for DOT only
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), CHARLIE, DOT, 1),
+				Error::<Runtime>::ExistentialDeposit
+			);
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), EVE, DOT, 1));
+			assert_eq!(Tokens::free_balance(DOT, &EVE), 1);
+			assert!(Accounts::<Runtime>::contains_key(EVE, DOT));
+			assert!(!MockDustRemovalWhitelist::contains(&(EVE, BTC)));
+		});
+}
+
+#[test]
+fn accumulate_dust_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			AccumulateDust::<Runtime>::on_dust(&ALICE, DOT, 1);
+			AccumulateDust::<Runtime>::on_dust(&BOB, DOT, 2);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 99);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 98);
+			assert_eq!(Tokens::accumulated_dust(DOT), 3);
+			assert_eq!(Tokens::total_issuance(DOT), 200);
+			assert_eq!(Tokens::free_balance(DOT, &DustReceiver::get()), 0);
+			assert_ok!(migrations::ensure_total_issuance::<Runtime>());
+
+			// not enough weight to flush
+			assert_eq!(Tokens::on_idle(1, 0), 0);
+			assert_eq!(Tokens::accumulated_dust(DOT), 3);
+
+			// not enough weight to read the keys of `AccumulatedDust`
+			let per_currency = <() as WeightInfo>::flush_dust();
+			assert_eq!(Tokens::on_idle(1, per_currency), 0);
+			assert_eq!(Tokens::accumulated_dust(DOT), 3);
+
+			// both `AccumulatedDust` and `DeferredDust` are read
+			let per_map = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+			assert_eq!(Tokens::on_idle(1, Weight::MAX), 2 * per_map + per_currency);
+			System::assert_last_event(Event::Tokens(crate::Event::DustFlushed(DOT, DustReceiver::get(), 3)));
+			assert_eq!(Tokens::accumulated_dust(DOT), 0);
+			assert_eq!(Tokens::free_balance(DOT, &DustReceiver::get()), 3);
+			assert_eq!(Tokens::total_issuance(DOT), 200);
+			assert_ok!(migrations::ensure_total_issuance::<Runtime>());
+		});
+}
+
+#[test]
+fn flush_dust_should_respect_remaining_weight() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			AccumulateDust::<Runtime>::on_dust(&ALICE, DOT, 1);
+			AccumulateDust::<Runtime>::on_dust(&ALICE, BTC, 1);
+
+			let per_currency = <() as WeightInfo>::flush_dust();
+			let per_map = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+			assert_eq!(Tokens::on_idle(1, per_map + per_currency), per_map + per_currency);
+			assert_eq!(Tokens::accumulated_dust(DOT) + Tokens::accumulated_dust(BTC), 1);
+
+			assert_eq!(Tokens::on_idle(2, per_map + per_currency), per_map + per_currency);
+			assert_eq!(Tokens::accumulated_dust(DOT), 0);
+			assert_eq!(Tokens::accumulated_dust(BTC), 0);
+			assert_eq!(Tokens::free_balance(DOT, &DustReceiver::get()), 1);
+			assert_eq!(Tokens::free_balance(BTC, &DustReceiver::get()), 1);
+		});
+}
+
+#[test]
+fn flush_dust_should_defer_failed_currencies() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			AccumulateDust::<Runtime>::on_dust(&ALICE, DOT, 1);
+			AccumulateDust::<Runtime>::on_dust(&ALICE, BTC, 1);
+
+			// the dust receiver cannot receive DOT
+			MockAllowFrozenReceiving::set(false);
+			assert_ok!(Tokens::freeze_currency(RawOrigin::Root.into(), DOT));
+
+			let per_currency = <() as WeightInfo>::flush_dust();
+			let per_map = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+			assert_eq!(
+				Tokens::on_idle(1, per_map + 2 * per_currency),
+				per_map + 2 * per_currency
+			);
+			assert_eq!(Tokens::accumulated_dust(DOT), 0);
+			assert_eq!(Tokens::deferred_dust(DOT), 1);
+			assert_eq!(Tokens::free_balance(BTC, &DustReceiver::get()), 1);
+			assert_ok!(migrations::ensure_total_issuance::<Runtime>());
+
+			// accumulated dust is flushed before deferred dust
+			AccumulateDust::<Runtime>::on_dust(&ALICE, BTC, 1);
+			assert_eq!(Tokens::on_idle(2, per_map + per_currency), per_map + per_currency);
+			assert_eq!(Tokens::free_balance(BTC, &DustReceiver::get()), 2);
+			assert_eq!(Tokens::deferred_dust(DOT), 1);
+
+			assert_ok!(Tokens::thaw_currency(RawOrigin::Root.into(), DOT));
+			MockAllowFrozenReceiving::set(true);
+			assert_eq!(Tokens::on_idle(3, Weight::MAX), 2 * per_map + per_currency);
+			System::assert_last_event(Event::Tokens(crate::Event::DustFlushed(DOT, DustReceiver::get(), 1)));
+			assert_eq!(Tokens::deferred_dust(DOT), 0);
+			assert_eq!(Tokens::free_balance(DOT, &DustReceiver::get()), 1);
+			assert_ok!(migrations::ensure_total_issuance::<Runtime>());
+		});
+}
+
 // *************************************************
 // tests for MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
 // MultiReservableCurrency traits **********************************************
@@ -1170,14 +1454,14 @@ fn multi_lockable_currency_set_lock_work() {
 		.build()
 		.execute_with(|| {
 			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 10));
-			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 10);
-			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(), 10);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::All), 10);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::All), 10);
 			assert_eq!(Tokens::locks(ALICE, DOT).len(), 1);
 			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 50));
-			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 50);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::All), 50);
 			assert_eq!(Tokens::locks(ALICE, DOT).len(), 1);
 			assert_ok!(Tokens::set_lock(ID_2, DOT, &ALICE, 60));
-			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 60);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::All), 60);
 			assert_eq!(Tokens::locks(ALICE, DOT).len(), 2);
 		});
 }
@@ -1190,16 +1474,54 @@ fn multi_lockable_currency_extend_lock_work() {
 		.execute_with(|| {
 			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 10));
 			assert_eq!(Tokens::locks(ALICE, DOT).len(), 1);
-			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 10);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::All), 10);
 			assert_ok!(Tokens::extend_lock(ID_1, DOT, &ALICE, 20));
 			assert_eq!(Tokens::locks(ALICE, DOT).len(), 1);
-			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 20);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::All), 20);
 			assert_ok!(Tokens::extend_lock(ID_2, DOT, &ALICE, 10));
 			assert_ok!(Tokens::extend_lock(ID_1, DOT, &ALICE, 20));
 			assert_eq!(Tokens::locks(ALICE, DOT).len(), 2);
 		});
 }
 
+#[test]
+fn multi_lockable_currency_lock_with_reasons_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock_with_reasons(
+				ID_1,
+				DOT,
+				&ALICE,
+				10,
+				WithdrawReasons::TRANSACTION_PAYMENT
+			));
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::Fee), 10);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::Misc), 0);
+			assert_ok!(Tokens::extend_lock_with_reasons(
+				ID_2,
+				DOT,
+				&ALICE,
+				20,
+				WithdrawReasons::TRANSFER
+			));
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::Fee), 10);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::Misc), 20);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::All), 20);
+
+			assert_ok!(Tokens::set_lock_with_reasons(
+				ID_2,
+				DOT,
+				&ALICE,
+				20,
+				WithdrawReasons::empty()
+			));
+			assert_eq!(Tokens::locks(ALICE, DOT).len(), 1);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::Misc), 0);
+		});
+}
+
 #[test]
 fn multi_lockable_currency_remove_lock_work() {
 	ExtBuilder::default()
@@ -1343,6 +1665,132 @@ fn multi_reservable_currency_repatriate_reserved_work() {
 		});
 }
 
+#[test]
+fn named_multi_reservable_currency_reserve_named_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Tokens::reserve_named(&RID_1, DOT, &ALICE, 101),
+				Error::<Runtime>::BalanceTooLow
+			);
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 0));
+			assert_eq!(Tokens::reserves(ALICE, DOT).len(), 0);
+
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 30));
+			assert_ok!(Tokens::reserve_named(&RID_2, DOT, &ALICE, 20));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 10));
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 40);
+			assert_eq!(Tokens::reserved_balance_named(&RID_2, DOT, &ALICE), 20);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 60);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 40);
+			assert_eq!(Tokens::reserves(ALICE, DOT).len(), 2);
+
+			assert_noop!(
+				Tokens::reserve_named(&[3u8; 8], DOT, &ALICE, 10),
+				Error::<Runtime>::TooManyReserves
+			);
+		});
+}
+
+#[test]
+fn named_multi_reservable_currency_unreserve_named_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 30));
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 20));
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 50);
+
+			// unreserve named reserve will not touch unnamed reserved balance
+			assert_eq!(Tokens::unreserve_named(&RID_2, DOT, &ALICE, 10), 10);
+			assert_eq!(Tokens::unreserve_named(&RID_1, DOT, &ALICE, 10), 0);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 20);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 40);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 60);
+
+			assert_eq!(Tokens::unreserve_named(&RID_1, DOT, &ALICE, 30), 10);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 0);
+			assert!(!Reserves::<Runtime>::contains_key(ALICE, DOT));
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 20);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 80);
+		});
+}
+
+#[test]
+fn named_multi_reservable_currency_slash_reserved_named_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 30));
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 20));
+			assert_eq!(Tokens::slash_reserved_named(&RID_1, DOT, &ALICE, 0), 0);
+			assert_eq!(Tokens::slash_reserved_named(&RID_1, DOT, &ALICE, 40), 10);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 0);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 20);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::total_issuance(DOT), 70);
+		});
+}
+
+#[test]
+fn named_multi_reservable_currency_repatriate_reserved_named_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 50));
+
+			assert_eq!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &ALICE, &ALICE, 60, BalanceStatus::Reserved),
+				Ok(10)
+			);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 50);
+
+			assert_eq!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &ALICE, &BOB, 20, BalanceStatus::Reserved),
+				Ok(0)
+			);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 30);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 20);
+			assert_eq!(Tokens::reserved_balance(DOT, &BOB), 20);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 100);
+
+			assert_eq!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &ALICE, &BOB, 40, BalanceStatus::Free),
+				Ok(10)
+			);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 0);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 20);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 130);
+		});
+}
+
+#[test]
+fn named_multi_reservable_currency_ensure_reserved_named_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::ensure_reserved_named(&RID_1, DOT, &ALICE, 30));
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 30);
+			assert_ok!(Tokens::ensure_reserved_named(&RID_1, DOT, &ALICE, 10));
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 10);
+			assert_ok!(Tokens::ensure_reserved_named(&RID_1, DOT, &ALICE, 10));
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 10);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 10);
+
+			assert_eq!(Tokens::unreserve_all_named(&RID_1, DOT, &ALICE), 10);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+		});
+}
+
 #[test]
 fn slash_draw_reserved_correct() {
 	ExtBuilder::default()
@@ -1405,6 +1853,41 @@ fn transfer_all_trait_should_work() {
 		});
 }
 
+#[test]
+fn multi_reducible_currency_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 200)])
+		.build()
+		.execute_with(|| {
+			let mut held = <Tokens as MultiReducibleCurrency<AccountId>>::held_currencies(&ALICE, 10);
+			held.sort_unstable();
+			assert_eq!(held, vec![DOT, BTC]);
+			assert_eq!(
+				<Tokens as MultiReducibleCurrency<AccountId>>::held_currencies(&ALICE, 1).len(),
+				1
+			);
+			assert_eq!(
+				<Tokens as MultiReducibleCurrency<AccountId>>::held_currencies(&BOB, 10),
+				vec![]
+			);
+
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 10));
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 20));
+			assert_eq!(
+				<Tokens as MultiReducibleCurrency<AccountId>>::reducible_balance(DOT, &ALICE, false),
+				70
+			);
+			assert_eq!(
+				<Tokens as MultiReducibleCurrency<AccountId>>::reducible_balance(BTC, &ALICE, false),
+				200
+			);
+			assert_eq!(
+				<Tokens as MultiReducibleCurrency<AccountId>>::reducible_balance(BTC, &ALICE, true),
+				199
+			);
+		});
+}
+
 // *************************************************
 // tests for CurrencyAdapter
 // *************************************************
@@ -1715,13 +2198,16 @@ fn currency_adapter_combination_locking_should_work() {
 		.balances(vec![(TREASURY_ACCOUNT, DOT, 100)])
 		.build()
 		.execute_with(|| {
-			// withdrawReasons not work
+			// lock with empty withdraw reasons will not be set
 			TreasuryCurrencyAdapter::set_lock(ID_1, &TREASURY_ACCOUNT, u64::max_value(), WithdrawReasons::empty());
 			TreasuryCurrencyAdapter::set_lock(ID_2, &TREASURY_ACCOUNT, 0, WithdrawReasons::all());
-			assert_noop!(
-				TreasuryCurrencyAdapter::transfer(&TREASURY_ACCOUNT, &ALICE, 2, ExistenceRequirement::AllowDeath),
-				Error::<Runtime>::LiquidityRestrictions
-			);
+			assert_eq!(Tokens::locks(TREASURY_ACCOUNT, DOT).len(), 0);
+			assert_ok!(TreasuryCurrencyAdapter::transfer(
+				&TREASURY_ACCOUNT,
+				&ALICE,
+				2,
+				ExistenceRequirement::AllowDeath
+			));
 		});
 }
 
@@ -1799,23 +2285,86 @@ fn currency_adapter_lock_reasons_extension_should_work() {
 }
 
 #[test]
-fn currency_adapter_deposit_creating_should_work() {
+fn currency_adapter_lock_reasons_should_work() {
 	ExtBuilder::default()
-		.balances(vec![(ALICE, DOT, 100)])
+		.balances(vec![(TREASURY_ACCOUNT, DOT, 100)])
 		.build()
 		.execute_with(|| {
-			assert_eq!(TreasuryCurrencyAdapter::total_issuance(), 100);
-			assert_eq!(TreasuryCurrencyAdapter::total_balance(&TREASURY_ACCOUNT), 0);
-			let _ = TreasuryCurrencyAdapter::deposit_creating(&TREASURY_ACCOUNT, 2);
-			assert_eq!(TreasuryCurrencyAdapter::total_issuance(), 102);
-			assert_eq!(TreasuryCurrencyAdapter::total_balance(&TREASURY_ACCOUNT), 2);
-
-			assert_eq!(TreasuryCurrencyAdapter::total_balance(&ALICE), 100);
-			let _ = TreasuryCurrencyAdapter::deposit_creating(&ALICE, 1);
-			assert_eq!(TreasuryCurrencyAdapter::total_issuance(), 103);
-			assert_eq!(TreasuryCurrencyAdapter::total_balance(&ALICE), 101);
-		});
-}
+			TreasuryCurrencyAdapter::set_lock(ID_1, &TREASURY_ACCOUNT, 10, WithdrawReasons::TRANSFER);
+			assert_eq!(Tokens::accounts(&TREASURY_ACCOUNT, DOT).misc_frozen, 10);
+			assert_eq!(Tokens::accounts(&TREASURY_ACCOUNT, DOT).fee_frozen, 0);
+			assert_noop!(
+				TreasuryCurrencyAdapter::transfer(&TREASURY_ACCOUNT, &ALICE, 91, ExistenceRequirement::AllowDeath),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+			assert_noop!(
+				TreasuryCurrencyAdapter::reserve(&TREASURY_ACCOUNT, 91),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+			// fee payment is not restricted by the lock
+			assert_ok!(TreasuryCurrencyAdapter::withdraw(
+				&TREASURY_ACCOUNT,
+				91,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_eq!(TreasuryCurrencyAdapter::free_balance(&TREASURY_ACCOUNT), 9);
+		});
+
+	ExtBuilder::default()
+		.balances(vec![(TREASURY_ACCOUNT, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			TreasuryCurrencyAdapter::set_lock(ID_1, &TREASURY_ACCOUNT, 10, WithdrawReasons::TRANSACTION_PAYMENT);
+			assert_eq!(Tokens::accounts(&TREASURY_ACCOUNT, DOT).misc_frozen, 0);
+			assert_eq!(Tokens::accounts(&TREASURY_ACCOUNT, DOT).fee_frozen, 10);
+			assert_noop!(
+				TreasuryCurrencyAdapter::withdraw(
+					&TREASURY_ACCOUNT,
+					91,
+					WithdrawReasons::TRANSACTION_PAYMENT,
+					ExistenceRequirement::AllowDeath
+				),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+			assert_ok!(TreasuryCurrencyAdapter::transfer(
+				&TREASURY_ACCOUNT,
+				&ALICE,
+				91,
+				ExistenceRequirement::AllowDeath
+			));
+		});
+
+	ExtBuilder::default()
+		.balances(vec![(TREASURY_ACCOUNT, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			TreasuryCurrencyAdapter::set_lock(ID_1, &TREASURY_ACCOUNT, 10, WithdrawReasons::TRANSACTION_PAYMENT);
+			TreasuryCurrencyAdapter::extend_lock(ID_1, &TREASURY_ACCOUNT, 10, WithdrawReasons::TRANSFER);
+			assert_eq!(Tokens::locks(TREASURY_ACCOUNT, DOT)[0].reasons, Reasons::All);
+			assert_eq!(Tokens::accounts(&TREASURY_ACCOUNT, DOT).misc_frozen, 10);
+			assert_eq!(Tokens::accounts(&TREASURY_ACCOUNT, DOT).fee_frozen, 10);
+		});
+}
+
+#[test]
+fn currency_adapter_deposit_creating_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(TreasuryCurrencyAdapter::total_issuance(), 100);
+			assert_eq!(TreasuryCurrencyAdapter::total_balance(&TREASURY_ACCOUNT), 0);
+			let _ = TreasuryCurrencyAdapter::deposit_creating(&TREASURY_ACCOUNT, 2);
+			assert_eq!(TreasuryCurrencyAdapter::total_issuance(), 102);
+			assert_eq!(TreasuryCurrencyAdapter::total_balance(&TREASURY_ACCOUNT), 2);
+
+			assert_eq!(TreasuryCurrencyAdapter::total_balance(&ALICE), 100);
+			let _ = TreasuryCurrencyAdapter::deposit_creating(&ALICE, 1);
+			assert_eq!(TreasuryCurrencyAdapter::total_issuance(), 103);
+			assert_eq!(TreasuryCurrencyAdapter::total_balance(&ALICE), 101);
+		});
+}
 
 #[test]
 fn currency_adapter_deposit_into_existing_should_work() {
@@ -2046,6 +2595,103 @@ fn fungibles_unbalanced_trait_should_work() {
 		});
 }
 
+#[test]
+fn fungibles_unbalanced_set_balance_should_check_frozen_and_call_hooks() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Tokens as fungibles::Unbalanced<_>>::set_balance(DOT, &ALICE, 90));
+			assert_eq!(MockHooks::take_calls(), vec![HookCall::Withdraw(DOT, ALICE, 10)]);
+			assert_ok!(<Tokens as fungibles::Unbalanced<_>>::set_balance(DOT, &ALICE, 100));
+			assert_eq!(MockHooks::take_calls(), vec![HookCall::Deposit(DOT, ALICE, 10)]);
+
+			MockHooks::set_veto(true);
+			assert_noop!(
+				<Tokens as fungibles::Unbalanced<_>>::set_balance(DOT, &ALICE, 90),
+				DispatchError::Other("vetoed")
+			);
+			assert_noop!(
+				<Tokens as fungibles::Unbalanced<_>>::set_balance(DOT, &ALICE, 110),
+				DispatchError::Other("vetoed")
+			);
+			MockHooks::set_veto(false);
+
+			assert_ok!(Tokens::freeze_account(RawOrigin::Root.into(), DOT, ALICE));
+			assert_noop!(
+				<Tokens as fungibles::Unbalanced<_>>::set_balance(DOT, &ALICE, 90),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_ok!(<Tokens as fungibles::Unbalanced<_>>::set_balance(DOT, &ALICE, 110));
+			MockAllowFrozenReceiving::set(false);
+			assert_noop!(
+				<Tokens as fungibles::Unbalanced<_>>::set_balance(DOT, &ALICE, 120),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 110);
+		});
+}
+
+#[test]
+fn fungibles_unbalanced_should_respect_max_issuance() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, KSM, 900)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				<Tokens as fungibles::Unbalanced<_>>::set_balance(KSM, &ALICE, 1001),
+				Error::<Runtime>::MaxIssuanceExceeded
+			);
+			assert_noop!(
+				<Tokens as fungibles::Unbalanced<_>>::increase_balance(KSM, &BOB, 101),
+				Error::<Runtime>::MaxIssuanceExceeded
+			);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(KSM, &BOB, 101),
+				DepositConsequence::Overflow
+			);
+			assert_ok!(<Tokens as fungibles::Unbalanced<_>>::set_balance(KSM, &ALICE, 1000));
+
+			<Tokens as fungibles::Unbalanced<_>>::set_total_issuance(KSM, 700);
+			assert_eq!(Tokens::total_issuance(KSM), 700);
+			<Tokens as fungibles::Unbalanced<_>>::set_total_issuance(KSM, 800);
+			System::assert_last_event(Event::Tokens(crate::Event::IssuanceHighWaterMarkReached(KSM, 800)));
+		});
+}
+
+#[test]
+fn fungibles_balanced_trait_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 30));
+
+			let credit = <Tokens as fungibles::Balanced<_>>::withdraw(DOT, &ALICE, 50).unwrap();
+			assert_eq!(credit.peek(), 50);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 20);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+			assert!(<Tokens as fungibles::Balanced<_>>::withdraw(DOT, &ALICE, 21).is_err());
+
+			assert!(<Tokens as fungibles::Balanced<_>>::resolve(&BOB, credit).is_ok());
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 50);
+			assert_eq!(Tokens::total_issuance(DOT), 100);
+
+			// dropped credits decrease and dropped debts increase the total issuance
+			drop(<Tokens as fungibles::Balanced<_>>::withdraw(DOT, &BOB, 20).unwrap());
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 30);
+			assert_eq!(Tokens::total_issuance(DOT), 80);
+			drop(<Tokens as fungibles::Balanced<_>>::deposit(DOT, &BOB, 10).unwrap());
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 40);
+			assert_eq!(Tokens::total_issuance(DOT), 90);
+
+			assert_noop!(
+				<Tokens as fungibles::Unbalanced<_>>::set_balance(DOT, &ALICE, 29),
+				ArithmeticError::Underflow
+			);
+		});
+}
+
 #[test]
 fn fungibles_inspect_hold_trait_should_work() {
 	ExtBuilder::default()
@@ -2079,3 +2725,709 @@ fn fungibles_mutate_hold_trait_should_work() {
 			);
 		});
 }
+
+#[test]
+fn holders_should_be_tracked() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Tokens::holders_count(DOT), 2);
+			assert_eq!(Tokens::holders_count(BTC), 1);
+			assert_eq!(Tokens::holders_of(BTC, None, 10), Some(vec![ALICE]));
+			assert_eq!(Tokens::holders_of(BTC, Some(ALICE), 10), Some(vec![]));
+			assert_eq!(Tokens::holders_of(DOT, None, 1).map(|holders| holders.len()), Some(1));
+
+			// new account
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), CHARLIE, DOT, 50));
+			assert_eq!(Tokens::holders_count(DOT), 3);
+			assert_eq!(Tokens::holders(DOT, CHARLIE), Some(()));
+
+			// reaped account
+			assert_ok!(Tokens::transfer_all(Some(BOB).into(), ALICE, DOT, false));
+			assert_eq!(Tokens::holders_count(DOT), 2);
+			assert_eq!(Tokens::holders(DOT, BOB), None);
+
+			// account only holding reserved balance is still a holder
+			assert_ok!(Tokens::reserve(DOT, &CHARLIE, 50));
+			assert_eq!(Tokens::holders_count(DOT), 2);
+
+			// page from the last holder of the previous page
+			let mut holders = Tokens::holders_of(DOT, None, 1).unwrap();
+			holders.extend(Tokens::holders_of(DOT, holders.last().cloned(), 1).unwrap());
+			assert_eq!(Tokens::holders_of(DOT, holders.last().cloned(), 1), Some(vec![]));
+			holders.sort();
+			let mut expected = vec![ALICE, CHARLIE];
+			expected.sort();
+			assert_eq!(holders, expected);
+		});
+}
+
+#[test]
+fn holders_should_be_tracked_for_dust_removal() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 2));
+			assert_eq!(Tokens::holders_count(DOT), 2);
+
+			// BOB falls below ED, the dust is moved to `DustReceiver`
+			assert_ok!(Tokens::transfer(Some(BOB).into(), ALICE, DOT, 1));
+			assert_eq!(Tokens::holders(DOT, BOB), None);
+			assert_eq!(Tokens::holders(DOT, DustReceiver::get()), Some(()));
+			assert_eq!(Tokens::holders_count(DOT), 2);
+			assert_ok!(migrations::ensure_holders::<Runtime>());
+		});
+}
+
+// *************************************************
+// tests for metadata
+// *************************************************
+
+#[test]
+fn set_metadata_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Tokens::set_metadata(Some(ALICE).into(), DOT, b"Polkadot".to_vec(), b"DOT".to_vec(), 10),
+			BadOrigin
+		);
+		assert_noop!(
+			Tokens::set_metadata(RawOrigin::Root.into(), DOT, b"Polkadot!".to_vec(), b"DOT".to_vec(), 10),
+			Error::<Runtime>::BadMetadata
+		);
+
+		assert_ok!(Tokens::set_metadata(
+			RawOrigin::Root.into(),
+			DOT,
+			b"Polkadot".to_vec(),
+			b"DOT".to_vec(),
+			10
+		));
+		System::assert_last_event(Event::Tokens(crate::Event::MetadataSet(
+			DOT,
+			b"Polkadot".to_vec(),
+			b"DOT".to_vec(),
+			10,
+		)));
+		assert_eq!(<Tokens as metadata::Inspect<_>>::name(&DOT), b"Polkadot".to_vec());
+		assert_eq!(<Tokens as metadata::Inspect<_>>::symbol(&DOT), b"DOT".to_vec());
+		assert_eq!(<Tokens as metadata::Inspect<_>>::decimals(&DOT), 10);
+	});
+}
+
+#[test]
+fn genesis_metadata_should_work() {
+	ExtBuilder::default()
+		.metadata(vec![(BTC, b"Bitcoin".to_vec(), b"BTC".to_vec(), 8)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(<Tokens as metadata::Inspect<_>>::name(&BTC), b"Bitcoin".to_vec());
+			assert_eq!(<Tokens as metadata::Inspect<_>>::symbol(&BTC), b"BTC".to_vec());
+			assert_eq!(<Tokens as metadata::Inspect<_>>::decimals(&BTC), 8);
+
+			// no metadata
+			assert_eq!(<Tokens as metadata::Inspect<_>>::name(&DOT), Vec::<u8>::new());
+			assert_eq!(<Tokens as metadata::Inspect<_>>::decimals(&DOT), 0);
+		});
+}
+
+#[test]
+fn fungibles_metadata_mutate_trait_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		// `from` must pass `MetadataOrigin`
+		assert_noop!(
+			<Tokens as metadata::Mutate<_>>::set(ETH, &ALICE, b"Ethereum".to_vec(), b"ETH".to_vec(), 18),
+			BadOrigin
+		);
+		assert_noop!(
+			<Tokens as metadata::Mutate<_>>::set(ETH, &DAVE, b"Ethereum".to_vec(), b"Ethereum".to_vec(), 18),
+			Error::<Runtime>::BadMetadata
+		);
+		assert_ok!(<Tokens as metadata::Mutate<_>>::set(
+			ETH,
+			&DAVE,
+			b"Ethereum".to_vec(),
+			b"ETH".to_vec(),
+			18
+		));
+		assert_eq!(
+			Tokens::metadata(ETH),
+			CurrencyMetadata {
+				name: b"Ethereum".to_vec().try_into().unwrap(),
+				symbol: b"ETH".to_vec().try_into().unwrap(),
+				decimals: 18,
+			}
+		);
+	});
+}
+
+// *************************************************
+// tests for hooks
+// *************************************************
+
+#[test]
+fn hooks_should_be_called() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10));
+			assert_eq!(MockHooks::take_calls(), vec![HookCall::Transfer(DOT, ALICE, BOB, 10)]);
+
+			assert_ok!(Tokens::deposit(DOT, &ALICE, 10));
+			assert_ok!(Tokens::withdraw(DOT, &ALICE, 20));
+			assert_eq!(
+				MockHooks::take_calls(),
+				vec![HookCall::Deposit(DOT, ALICE, 10), HookCall::Withdraw(DOT, ALICE, 20)]
+			);
+
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 30));
+			assert_eq!(Tokens::slash(DOT, &ALICE, 60), 0);
+			assert_eq!(Tokens::slash_reserved(DOT, &ALICE, 10), 0);
+			assert_eq!(
+				MockHooks::take_calls(),
+				vec![HookCall::Slash(DOT, ALICE, 60), HookCall::Slash(DOT, ALICE, 10)]
+			);
+
+			// nothing slashed, no hook called
+			assert_eq!(Tokens::slash(DOT, &CHARLIE, 10), 10);
+			assert_eq!(Tokens::slash_reserved(DOT, &CHARLIE, 10), 10);
+			assert_eq!(KsmCurrencyAdapter::slash(&CHARLIE, 10).1, 10);
+			assert_eq!(MockHooks::take_calls(), vec![]);
+
+			assert_ok!(Tokens::reserve(DOT, &BOB, 30));
+			assert_eq!(
+				Tokens::repatriate_reserved(DOT, &BOB, &ALICE, 40, BalanceStatus::Free),
+				Ok(10)
+			);
+			assert_eq!(MockHooks::take_calls(), vec![HookCall::Transfer(DOT, BOB, ALICE, 30)]);
+		});
+}
+
+#[test]
+fn pre_hooks_can_veto() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &BOB, 30));
+			MockHooks::set_veto(true);
+
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10),
+				DispatchError::Other("vetoed")
+			);
+			assert_noop!(Tokens::deposit(DOT, &ALICE, 10), DispatchError::Other("vetoed"));
+			assert_noop!(Tokens::withdraw(DOT, &ALICE, 10), DispatchError::Other("vetoed"));
+			assert_noop!(
+				Tokens::repatriate_reserved(DOT, &BOB, &ALICE, 10, BalanceStatus::Free),
+				DispatchError::Other("vetoed")
+			);
+			assert_eq!(MockHooks::take_calls(), vec![]);
+
+			// slashing cannot be vetoed
+			assert_eq!(Tokens::slash(DOT, &ALICE, 10), 0);
+			assert_eq!(MockHooks::take_calls(), vec![HookCall::Slash(DOT, ALICE, 10)]);
+		});
+}
+
+#[test]
+fn token_account_hooks_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 50));
+			assert_eq!(
+				MockHooks::take_calls(),
+				vec![
+					HookCall::NewTokenAccount(DOT, BOB),
+					HookCall::Transfer(DOT, ALICE, BOB, 50)
+				]
+			);
+
+			assert_ok!(Tokens::transfer_all(Some(BOB).into(), ALICE, DOT, false));
+			assert_eq!(
+				MockHooks::take_calls(),
+				vec![
+					HookCall::KilledTokenAccount(DOT, BOB),
+					HookCall::Transfer(DOT, BOB, ALICE, 50)
+				]
+			);
+		});
+}
+
+#[test]
+fn sufficient_currency_should_add_sufficient_references() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DAI, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(System::providers(&ALICE), 0);
+			assert_eq!(System::sufficients(&ALICE), 1);
+
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DAI, 50));
+			assert_eq!(System::providers(&BOB), 0);
+			assert_eq!(System::sufficients(&BOB), 1);
+			assert!(System::account_exists(&BOB));
+			// a sufficient reference does not allow consumers
+			assert!(System::inc_consumers(&BOB).is_err());
+
+			// the sufficient reference can always be removed
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::reducible_balance(DAI, &BOB, false),
+				50
+			);
+			assert_ok!(Tokens::transfer_all(Some(BOB).into(), ALICE, DAI, false));
+			assert_eq!(System::sufficients(&BOB), 0);
+			assert!(!System::account_exists(&BOB));
+
+			// other currencies still add provider references
+			assert_ok!(Tokens::deposit(DOT, &ALICE, 100));
+			assert_eq!(System::providers(&ALICE), 1);
+			assert_eq!(System::sufficients(&ALICE), 1);
+		});
+}
+
+#[test]
+fn non_provider_currency_should_need_an_existing_provider() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			// BOB has no provider reference
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(USDC, &BOB, 100),
+				DepositConsequence::CannotCreate
+			);
+			assert_noop!(Tokens::deposit(USDC, &BOB, 100), TokenError::CannotCreate);
+
+			// the token account of ALICE adds a consumer reference, backed by DOT
+			assert_ok!(Tokens::deposit(USDC, &ALICE, 100));
+			assert_eq!(System::providers(&ALICE), 1);
+			assert_eq!(System::consumers(&ALICE), 1);
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, USDC, 50),
+				TokenError::CannotCreate
+			);
+
+			// the provider can't be removed while the consumer exists
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::reducible_balance(DOT, &ALICE, false),
+				98
+			);
+
+			// reaping the token account removes the consumer reference
+			assert_ok!(Tokens::withdraw(USDC, &ALICE, 100));
+			assert_eq!(System::consumers(&ALICE), 0);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::reducible_balance(DOT, &ALICE, false),
+				100
+			);
+		});
+}
+
+#[test]
+fn lock_without_account_references_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		// without any reference, the consumer reference of the lock is skipped with a
+		// warning, only sufficient references refuse locks
+		assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 10));
+		assert_eq!(Tokens::locks(&ALICE, DOT).len(), 1);
+		assert_eq!(System::consumers(&ALICE), 0);
+	});
+}
+
+#[test]
+fn sufficient_only_account_should_refuse_locks() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DAI, 100)])
+		.build()
+		.execute_with(|| {
+			// a lock needs a consumer reference, which needs a provider
+			assert_noop!(Tokens::set_lock(ID_1, DAI, &ALICE, 50), DispatchError::NoProviders);
+			assert_noop!(
+				Tokens::extend_lock_with_reasons(ID_1, DAI, &ALICE, 50, WithdrawReasons::TRANSFER),
+				DispatchError::NoProviders
+			);
+			assert_eq!(System::consumers(&ALICE), 0);
+
+			// reserves do not add consumer references
+			assert_ok!(Tokens::reserve(DAI, &ALICE, 50));
+			assert_eq!(Tokens::reserved_balance(DAI, &ALICE), 50);
+			assert_eq!(System::consumers(&ALICE), 0);
+			assert_eq!(Tokens::unreserve(DAI, &ALICE, 50), 0);
+
+			// with a provider reference, locks add a consumer reference
+			assert_ok!(Tokens::deposit(DOT, &ALICE, 100));
+			assert_ok!(Tokens::set_lock(ID_1, DAI, &ALICE, 50));
+			assert_eq!(System::consumers(&ALICE), 1);
+			assert_ok!(Tokens::set_lock(ID_2, DAI, &ALICE, 50));
+			assert_eq!(System::consumers(&ALICE), 1);
+			assert_ok!(Tokens::remove_lock(ID_1, DAI, &ALICE));
+			assert_ok!(Tokens::remove_lock(ID_2, DAI, &ALICE));
+			assert_eq!(System::consumers(&ALICE), 0);
+			assert_eq!(Tokens::locks(&ALICE, DAI).len(), 0);
+		});
+}
+
+// *************************************************
+// tests for issuance limits
+// *************************************************
+
+#[test]
+fn max_issuance_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, KSM, 900)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::deposit(KSM, &ALICE, 100));
+			assert_eq!(Tokens::total_issuance(KSM), 1000);
+
+			assert_noop!(Tokens::deposit(KSM, &BOB, 1), Error::<Runtime>::MaxIssuanceExceeded);
+			assert_noop!(
+				Tokens::update_balance(KSM, &ALICE, 1),
+				Error::<Runtime>::MaxIssuanceExceeded
+			);
+			assert_noop!(
+				<Tokens as fungibles::Mutate<_>>::mint_into(KSM, &ALICE, 1),
+				Error::<Runtime>::MaxIssuanceExceeded
+			);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(KSM, &ALICE, 1),
+				DepositConsequence::Overflow
+			);
+			assert_noop!(
+				Tokens::set_balance(RawOrigin::Root.into(), ALICE, KSM, 1001, 0),
+				Error::<Runtime>::MaxIssuanceExceeded
+			);
+
+			// transfers do not change the total issuance
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, KSM, 100));
+
+			assert_ok!(Tokens::withdraw(KSM, &ALICE, 10));
+			assert_ok!(Tokens::update_balance(KSM, &ALICE, 10));
+			assert_eq!(Tokens::total_issuance(KSM), 1000);
+
+			// no limit for other currencies
+			assert_ok!(Tokens::deposit(DOT, &ALICE, 10_000));
+		});
+}
+
+#[test]
+fn currency_adapter_issue_should_respect_max_issuance() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, KSM, 900)])
+		.build()
+		.execute_with(|| {
+			let imbalance = KsmCurrencyAdapter::issue(60);
+			assert_eq!(imbalance.peek(), 60);
+			assert_eq!(Tokens::total_issuance(KSM), 960);
+
+			let capped_imbalance = KsmCurrencyAdapter::issue(60);
+			assert_eq!(capped_imbalance.peek(), 40);
+			assert_eq!(Tokens::total_issuance(KSM), 1000);
+
+			assert_eq!(KsmCurrencyAdapter::issue(60).peek(), 0);
+			assert_eq!(Tokens::total_issuance(KSM), 1000);
+		});
+}
+
+#[test]
+fn issuance_high_water_mark_event_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, KSM, 700)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::deposit(KSM, &ALICE, 99));
+			assert!(!System::events().iter().any(|record| matches!(
+				record.event,
+				Event::Tokens(crate::Event::IssuanceHighWaterMarkReached(..))
+			)));
+
+			assert_ok!(Tokens::deposit(KSM, &ALICE, 2));
+			System::assert_last_event(Event::Tokens(crate::Event::IssuanceHighWaterMarkReached(KSM, 801)));
+
+			// only emitted when crossing the mark
+			System::reset_events();
+			assert_ok!(Tokens::deposit(KSM, &ALICE, 10));
+			assert_eq!(System::events().len(), 0);
+
+			assert_ok!(Tokens::withdraw(KSM, &ALICE, 100));
+			assert_ok!(Tokens::set_balance(RawOrigin::Root.into(), ALICE, KSM, 800, 0));
+			System::assert_has_event(Event::Tokens(crate::Event::IssuanceHighWaterMarkReached(KSM, 800)));
+		});
+}
+
+// *************************************************
+// tests for approvals
+// *************************************************
+
+#[test]
+fn approve_transfer_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), BOB, BTC, 50));
+			System::assert_last_event(Event::Tokens(crate::Event::ApprovedTransfer(BTC, ALICE, BOB, 50)));
+			assert_eq!(<Tokens as orml_traits::Approvals<_>>::allowance(BTC, &ALICE, &BOB), 50);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 1);
+
+			// replaces the allowance, the deposit is kept
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), BOB, BTC, 30));
+			assert_eq!(<Tokens as orml_traits::Approvals<_>>::allowance(BTC, &ALICE, &BOB), 30);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 1);
+
+			// a zero allowance cancels the approval
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), BOB, BTC, 0));
+			System::assert_last_event(Event::Tokens(crate::Event::ApprovalCancelled(BTC, ALICE, BOB)));
+			assert_eq!(Tokens::approvals((BTC, ALICE, BOB)), None);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), BOB, BTC, 0));
+
+			// the deposit is required
+			assert_noop!(
+				Tokens::approve_transfer(Some(CHARLIE).into(), BOB, BTC, 50),
+				Error::<Runtime>::BalanceTooLow
+			);
+		});
+}
+
+#[test]
+fn cancel_approval_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Tokens::cancel_approval(Some(ALICE).into(), BOB, BTC),
+				Error::<Runtime>::Unapproved
+			);
+
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), BOB, BTC, 50));
+			assert_ok!(Tokens::cancel_approval(Some(ALICE).into(), BOB, BTC));
+			System::assert_last_event(Event::Tokens(crate::Event::ApprovalCancelled(BTC, ALICE, BOB)));
+			assert_eq!(<Tokens as orml_traits::Approvals<_>>::allowance(BTC, &ALICE, &BOB), 0);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+		});
+}
+
+#[test]
+fn transfer_approved_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Tokens::transfer_approved(Some(BOB).into(), ALICE, CHARLIE, BTC, 10),
+				Error::<Runtime>::Unapproved
+			);
+
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), BOB, BTC, 50));
+			assert_ok!(Tokens::transfer_approved(Some(BOB).into(), ALICE, CHARLIE, BTC, 30));
+			System::assert_last_event(Event::Tokens(crate::Event::TransferredApproved(
+				BTC, ALICE, BOB, CHARLIE, 30,
+			)));
+			assert_eq!(Tokens::free_balance(BTC, &ALICE), 70);
+			assert_eq!(Tokens::free_balance(BTC, &CHARLIE), 30);
+			assert_eq!(<Tokens as orml_traits::Approvals<_>>::allowance(BTC, &ALICE, &BOB), 20);
+
+			assert_noop!(
+				Tokens::transfer_approved(Some(BOB).into(), ALICE, CHARLIE, BTC, 30),
+				Error::<Runtime>::Unapproved
+			);
+			assert_noop!(
+				Tokens::transfer_approved(Some(CHARLIE).into(), ALICE, CHARLIE, BTC, 10),
+				Error::<Runtime>::Unapproved
+			);
+
+			// using up the allowance removes the approval
+			assert_ok!(Tokens::transfer_approved(Some(BOB).into(), ALICE, CHARLIE, BTC, 20));
+			assert_eq!(Tokens::approvals((BTC, ALICE, BOB)), None);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+		});
+}
+
+#[test]
+fn transfer_approved_should_keep_allowance_if_transfer_fails() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), BOB, BTC, 200));
+			assert_noop!(
+				Tokens::transfer_approved(Some(BOB).into(), ALICE, CHARLIE, BTC, 150),
+				Error::<Runtime>::BalanceTooLow
+			);
+			assert_eq!(<Tokens as orml_traits::Approvals<_>>::allowance(BTC, &ALICE, &BOB), 200);
+		});
+}
+
+// *************************************************
+// tests for migrations
+// *************************************************
+
+fn put_v0_account(who: AccountId, currency_id: CurrencyId, free: Balance, reserved: Balance, frozen: Balance) {
+	frame_support::storage::unhashed::put(
+		&Accounts::<Runtime>::hashed_key_for(who, currency_id),
+		&migrations::v0::AccountData { free, reserved, frozen },
+	);
+}
+
+fn put_v0_locks(who: AccountId, currency_id: CurrencyId, locks: Vec<(LockIdentifier, Balance)>) {
+	frame_support::storage::unhashed::put(
+		&Locks::<Runtime>::hashed_key_for(who, currency_id),
+		&locks
+			.into_iter()
+			.map(|(id, amount)| migrations::v0::BalanceLock { id, amount })
+			.collect::<Vec<_>>(),
+	);
+}
+
+/// Builds storage with the layout of `StorageVersion` 0.
+fn build_v0_storage() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| {
+		StorageVersion::new(0).put::<Tokens>();
+
+		put_v0_account(ALICE, DOT, 100, 10, 20);
+		put_v0_account(BOB, DOT, 50, 0, 0);
+		put_v0_account(ALICE, BTC, 30, 0, 30);
+		TotalIssuance::<Runtime>::insert(DOT, 160);
+		TotalIssuance::<Runtime>::insert(BTC, 30);
+
+		put_v0_locks(ALICE, DOT, vec![(ID_1, 20), (ID_2, 5)]);
+		put_v0_locks(ALICE, BTC, vec![(ID_1, 30)]);
+	});
+	ext
+}
+
+#[test]
+fn migrate_to_v1_should_work() {
+	build_v0_storage().execute_with(|| {
+		assert_ok!(migrations::pre_migrate::<Runtime>());
+		migrations::migrate::<Runtime>();
+		assert_ok!(migrations::post_migrate::<Runtime>());
+
+		assert_eq!(StorageVersion::get::<Tokens>(), 2);
+		assert_eq!(
+			Tokens::accounts(&ALICE, DOT),
+			AccountData {
+				free: 100,
+				reserved: 10,
+				misc_frozen: 20,
+				fee_frozen: 20,
+			}
+		);
+		assert_eq!(
+			Tokens::accounts(&BOB, DOT),
+			AccountData {
+				free: 50,
+				reserved: 0,
+				misc_frozen: 0,
+				fee_frozen: 0,
+			}
+		);
+		assert_eq!(
+			Tokens::accounts(&ALICE, BTC),
+			AccountData {
+				free: 30,
+				reserved: 0,
+				misc_frozen: 30,
+				fee_frozen: 30,
+			}
+		);
+		assert_eq!(
+			Tokens::locks(ALICE, DOT).to_vec(),
+			vec![
+				BalanceLock {
+					id: ID_1,
+					amount: 20,
+					reasons: Reasons::All,
+				},
+				BalanceLock {
+					id: ID_2,
+					amount: 5,
+					reasons: Reasons::All,
+				},
+			]
+		);
+		assert_eq!(
+			Tokens::locks(ALICE, BTC).to_vec(),
+			vec![BalanceLock {
+				id: ID_1,
+				amount: 30,
+				reasons: Reasons::All,
+			}]
+		);
+		assert_eq!(Tokens::total_issuance(DOT), 160);
+		assert_eq!(Tokens::total_issuance(BTC), 30);
+		assert_eq!(Tokens::holders_count(DOT), 2);
+		assert_eq!(Tokens::holders_count(BTC), 1);
+		assert_eq!(Tokens::holders(BTC, ALICE), Some(()));
+
+		// migrated locks still restrict all withdrawals
+		assert_noop!(
+			Tokens::transfer(Some(ALICE).into(), BOB, BTC, 1),
+			Error::<Runtime>::LiquidityRestrictions
+		);
+	});
+}
+
+#[test]
+fn migrate_is_idempotent() {
+	build_v0_storage().execute_with(|| {
+		migrations::migrate::<Runtime>();
+		Tokens::update_locks(DOT, &ALICE, &[]).unwrap();
+
+		migrations::migrate::<Runtime>();
+		assert_eq!(Tokens::locks(ALICE, DOT).len(), 0);
+		assert_eq!(Tokens::accounts(&ALICE, DOT).frozen(Reasons::All), 0);
+		assert_ok!(migrations::post_migrate::<Runtime>());
+	});
+}
+
+#[test]
+fn migration_checks_detect_total_issuance_mismatch() {
+	build_v0_storage().execute_with(|| {
+		TotalIssuance::<Runtime>::insert(DOT, 161);
+		assert_eq!(
+			migrations::pre_migrate::<Runtime>(),
+			Err("tokens: total issuance mismatch")
+		);
+
+		TotalIssuance::<Runtime>::remove(DOT);
+		assert_eq!(
+			migrations::pre_migrate::<Runtime>(),
+			Err("tokens: balance without total issuance")
+		);
+
+		TotalIssuance::<Runtime>::insert(DOT, 160);
+		assert_eq!(
+			migrations::post_migrate::<Runtime>(),
+			Err("tokens: storage version not updated")
+		);
+		migrations::migrate::<Runtime>();
+		assert_ok!(migrations::post_migrate::<Runtime>());
+	});
+}
+
+#[test]
+fn migration_checks_work_on_current_layout() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100), (ALICE, BTC, 50)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 30));
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &BOB, 50));
+			assert_ok!(migrations::pre_migrate::<Runtime>());
+			assert_eq!(StorageVersion::get::<Tokens>(), 2);
+
+			migrations::migrate::<Runtime>();
+			assert_ok!(migrations::post_migrate::<Runtime>());
+			assert_eq!(Tokens::accounts(&ALICE, DOT).reserved, 30);
+			assert_eq!(Tokens::accounts(&BOB, DOT).frozen(Reasons::All), 50);
+		});
+}
@@ -31,9 +31,19 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn transfer() -> Weight;
 	fn transfer_all() -> Weight;
+	fn transfer_multiple(c: u32) -> Weight;
 	fn transfer_keep_alive() -> Weight;
 	fn force_transfer() -> Weight;
 	fn set_balance() -> Weight;
+	fn freeze_account() -> Weight;
+	fn thaw_account() -> Weight;
+	fn freeze_currency() -> Weight;
+	fn thaw_currency() -> Weight;
+	fn set_metadata(n: u32) -> Weight;
+	fn approve_transfer() -> Weight;
+	fn cancel_approval() -> Weight;
+	fn transfer_approved() -> Weight;
+	fn flush_dust() -> Weight;
 }
 
 /// Default weights.
@@ -48,6 +58,14 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `transfer_multiple` of `benchmarking.rs` is run.
+	fn transfer_multiple(c: u32) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((69_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)))
+	}
 	fn transfer_keep_alive() -> Weight {
 		(38_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
@@ -63,4 +81,63 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `freeze_account` of `benchmarking.rs` is run.
+	fn freeze_account() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `thaw_account` of `benchmarking.rs` is run.
+	fn thaw_account() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `freeze_currency` of `benchmarking.rs` is run.
+	fn freeze_currency() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `thaw_currency` of `benchmarking.rs` is run.
+	fn thaw_currency() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `set_metadata` of `benchmarking.rs` is run.
+	fn set_metadata(n: u32) -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `approve_transfer` of `benchmarking.rs` is run.
+	fn approve_transfer() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `cancel_approval` of `benchmarking.rs` is run.
+	fn cancel_approval() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `transfer_approved` of `benchmarking.rs` is run.
+	fn transfer_approved() -> Weight {
+		(88_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `flush_dust` of `benchmarking.rs` is run.
+	fn flush_dust() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
 }
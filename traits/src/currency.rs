@@ -1,7 +1,7 @@
 use crate::arithmetic;
 use codec::{Codec, FullCodec};
 pub use frame_support::{
-	traits::{BalanceStatus, LockIdentifier},
+	traits::{BalanceStatus, LockIdentifier, WithdrawReasons},
 	transactional,
 };
 use sp_runtime::{
@@ -9,10 +9,11 @@ use sp_runtime::{
 	DispatchError, DispatchResult,
 };
 use sp_std::{
-	cmp::{Eq, PartialEq},
+	cmp::{Eq, Ordering, PartialEq},
 	convert::{TryFrom, TryInto},
 	fmt::Debug,
 	result,
+	vec::Vec,
 };
 
 /// Abstraction over a fungible multi-currency system.
@@ -126,6 +127,66 @@ pub trait MultiLockableCurrency<AccountId>: MultiCurrency<AccountId> {
 
 	/// Remove an existing lock.
 	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &AccountId) -> DispatchResult;
+
+	/// Same as `set_lock`, but the lock only restricts withdrawals for the
+	/// given `reasons`.
+	///
+	/// The default implementation ignores `reasons`, and the lock restricts
+	/// withdrawals for any reason.
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::set_lock(lock_id, currency_id, who, amount)
+	}
+
+	/// Same as `extend_lock`, but the lock only restricts withdrawals for the
+	/// given `reasons`. `extend_lock_with_reasons` will set:
+	/// - maximum `amount`
+	/// - bitwise mask of all `reasons`
+	///
+	/// The default implementation ignores `reasons`, and the lock restricts
+	/// withdrawals for any reason.
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::extend_lock(lock_id, currency_id, who, amount)
+	}
+
+	/// Same as `ensure_can_withdraw`, but only the locks which restrict any of
+	/// `reasons` are taken into account.
+	///
+	/// The default implementation ignores `reasons`, as the locks restrict
+	/// withdrawals for any reason.
+	fn ensure_can_withdraw_with_reasons(
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::ensure_can_withdraw(currency_id, who, amount)
+	}
+
+	/// Same as `withdraw`, but only the locks which restrict any of `reasons`
+	/// are taken into account.
+	///
+	/// The default implementation ignores `reasons`, as the locks restrict
+	/// withdrawals for any reason.
+	fn withdraw_with_reasons(
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::withdraw(currency_id, who, amount)
+	}
 }
 
 /// A fungible multi-currency system where funds can be reserved from the user.
@@ -186,6 +247,167 @@ pub trait MultiReservableCurrency<AccountId>: MultiCurrency<AccountId> {
 	) -> result::Result<Self::Balance, DispatchError>;
 }
 
+/// A fungible multi-currency system where funds can be reserved from the user
+/// with an identifier.
+pub trait NamedMultiReservableCurrency<AccountId>: MultiReservableCurrency<AccountId> {
+	/// An identifier for a reserve. Used for disambiguating different reserves
+	/// so that they can be individually replaced or removed.
+	type ReserveIdentifier;
+
+	/// Deducts up to `value` from reserved balance of `who`. This function
+	/// cannot fail.
+	///
+	/// As much funds up to `value` will be deducted as possible. If the reserve
+	/// balance of `who` is less than `value`, then a non-zero second item will
+	/// be returned.
+	fn slash_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> Self::Balance;
+
+	/// The amount of the balance of a given account that is externally
+	/// reserved under the given identifier; this can still get slashed, but
+	/// gets slashed last of all.
+	fn reserved_balance_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+	) -> Self::Balance;
+
+	/// Moves `value` from balance to reserved balance under the given
+	/// identifier.
+	///
+	/// If the free balance is lower than `value`, then no funds will be moved
+	/// and an `Err` will be returned to notify of this. This is different
+	/// behavior than `unreserve_named`.
+	fn reserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> DispatchResult;
+
+	/// Moves up to `value` from reserved balance under the given identifier
+	/// to free balance. This function cannot fail.
+	///
+	/// As much funds up to `value` will be moved as possible. If the reserve
+	/// balance of `who` under `id` is less than `value`, then the remaining
+	/// amount will be returned.
+	fn unreserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> Self::Balance;
+
+	/// Moves up to `value` from reserved balance of account `slashed` under
+	/// the given identifier to balance of account `beneficiary`.
+	/// `beneficiary` must exist for this to succeed. If it does not, `Err`
+	/// will be returned. Funds will be placed in either the `free` balance or
+	/// the `reserved` balance, depending on the `status`. If `status` is
+	/// `Reserved`, the funds are reserved under the same identifier on the
+	/// `beneficiary`.
+	///
+	/// As much funds up to `value` will be deducted as possible. If this is
+	/// less than `value`, then `Ok(non_zero)` will be returned.
+	fn repatriate_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError>;
+
+	/// Ensure the reserved balance of `who` under the given identifier is
+	/// equal to `value`.
+	///
+	/// This will reserve extra amount if current reserved balance is less
+	/// than `value`. And unreserve if current reserved balance is greater
+	/// than `value`.
+	fn ensure_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> DispatchResult {
+		let current = Self::reserved_balance_named(id, currency_id, who);
+		match current.cmp(&value) {
+			Ordering::Less => {
+				// we checked value > current
+				Self::reserve_named(id, currency_id, who, value - current)
+			}
+			Ordering::Equal => Ok(()),
+			Ordering::Greater => {
+				// we always have enough balance to unreserve here
+				Self::unreserve_named(id, currency_id, who, current - value);
+				Ok(())
+			}
+		}
+	}
+
+	/// Unreserve all the named reserved balances, returning unreserved
+	/// amount.
+	///
+	/// Is a no-op if the value to be unreserved is zero.
+	fn unreserve_all_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+	) -> Self::Balance {
+		let value = Self::reserved_balance_named(id, currency_id, who);
+		Self::unreserve_named(id, currency_id, who, value);
+		value
+	}
+
+	/// Slash all the reserved balance, returning the amount that was unable
+	/// to be slashed.
+	///
+	/// Is a no-op if the value to be slashed is zero.
+	fn slash_all_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+	) -> Self::Balance {
+		let value = Self::reserved_balance_named(id, currency_id, who);
+		Self::slash_reserved_named(id, currency_id, who, value)
+	}
+
+	/// Move all the named reserved balance of one account into the balance of
+	/// another, according to `status`. If `status` is `Reserved`, the balance
+	/// will be reserved with given `id`.
+	///
+	/// Is a no-op if:
+	/// - the value to be moved is zero; or
+	/// - the `slashed` id equal to `beneficiary` and the `status` is
+	///   `Reserved`.
+	fn repatriate_all_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		status: BalanceStatus,
+	) -> DispatchResult {
+		let value = Self::reserved_balance_named(id, currency_id, slashed);
+		Self::repatriate_reserved_named(id, currency_id, slashed, beneficiary, value, status).map(|_| ())
+	}
+}
+
+/// A fungible multi-currency system which could tell the currencies held by
+/// an account and how much of them could be transferred.
+pub trait MultiReducibleCurrency<AccountId>: MultiCurrency<AccountId> {
+	/// The currencies `who` has a balance of, at most `limit` of them, so the
+	/// storage reads are bounded.
+	fn held_currencies(who: &AccountId, limit: u32) -> Vec<Self::CurrencyId>;
+
+	/// The free balance of `who` under `currency_id` that could be
+	/// transferred, excluding the locked balance, and the existential deposit
+	/// if `keep_alive` is true.
+	fn reducible_balance(currency_id: Self::CurrencyId, who: &AccountId, keep_alive: bool) -> Self::Balance;
+}
+
 /// Abstraction over a fungible (single) currency system.
 pub trait BasicCurrency<AccountId> {
 	/// The balance of an account.
@@ -277,6 +499,58 @@ pub trait BasicLockableCurrency<AccountId>: BasicCurrency<AccountId> {
 
 	/// Remove an existing lock.
 	fn remove_lock(lock_id: LockIdentifier, who: &AccountId) -> DispatchResult;
+
+	/// Same as `set_lock`, but the lock only restricts withdrawals for the
+	/// given `reasons`.
+	///
+	/// The default implementation ignores `reasons`, and the lock restricts
+	/// withdrawals for any reason.
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::set_lock(lock_id, who, amount)
+	}
+
+	/// Same as `extend_lock`, but the lock only restricts withdrawals for the
+	/// given `reasons`. `extend_lock_with_reasons` will set:
+	/// - maximum `amount`
+	/// - bitwise mask of all `reasons`
+	///
+	/// The default implementation ignores `reasons`, and the lock restricts
+	/// withdrawals for any reason.
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::extend_lock(lock_id, who, amount)
+	}
+
+	/// Same as `ensure_can_withdraw`, but only the locks which restrict any of
+	/// `reasons` are taken into account.
+	///
+	/// The default implementation ignores `reasons`, as the locks restrict
+	/// withdrawals for any reason.
+	fn ensure_can_withdraw_with_reasons(
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::ensure_can_withdraw(who, amount)
+	}
+
+	/// Same as `withdraw`, but only the locks which restrict any of `reasons`
+	/// are taken into account.
+	///
+	/// The default implementation ignores `reasons`, as the locks restrict
+	/// withdrawals for any reason.
+	fn withdraw_with_reasons(who: &AccountId, amount: Self::Balance, _reasons: WithdrawReasons) -> DispatchResult {
+		Self::withdraw(who, amount)
+	}
 }
 
 /// A fungible single currency system where funds can be reserved from the user.
@@ -336,6 +610,14 @@ pub trait BasicReservableCurrency<AccountId>: BasicCurrency<AccountId> {
 	) -> result::Result<Self::Balance, DispatchError>;
 }
 
+/// A fungible single currency system which could tell how much of the
+/// balance of an account could be transferred.
+pub trait BasicReducibleCurrency<AccountId>: BasicCurrency<AccountId> {
+	/// The free balance of `who` that could be transferred, excluding the
+	/// locked balance, and the existential deposit if `keep_alive` is true.
+	fn reducible_balance(who: &AccountId, keep_alive: bool) -> Self::Balance;
+}
+
 /// Handler for account which has dust, need to burn or recycle it
 pub trait OnDust<AccountId, CurrencyId, Balance> {
 	fn on_dust(who: &AccountId, currency_id: CurrencyId, amount: Balance);
@@ -345,6 +627,134 @@ impl<AccountId, CurrencyId, Balance> OnDust<AccountId, CurrencyId, Balance> for
 	fn on_dust(_: &AccountId, _: CurrencyId, _: Balance) {}
 }
 
+/// Handler for when a token account of a currency is created.
+pub trait OnNewTokenAccount<AccountId, CurrencyId> {
+	fn on_new_token_account(who: &AccountId, currency_id: CurrencyId);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<AccountId, CurrencyId: Copy> OnNewTokenAccount<AccountId, CurrencyId> for Tuple {
+	fn on_new_token_account(who: &AccountId, currency_id: CurrencyId) {
+		for_tuples!( #( Tuple::on_new_token_account(who, currency_id); )* );
+	}
+}
+
+/// Handler for when a token account of a currency is reaped.
+pub trait OnKilledTokenAccount<AccountId, CurrencyId> {
+	fn on_killed_token_account(who: &AccountId, currency_id: CurrencyId);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<AccountId, CurrencyId: Copy> OnKilledTokenAccount<AccountId, CurrencyId> for Tuple {
+	fn on_killed_token_account(who: &AccountId, currency_id: CurrencyId) {
+		for_tuples!( #( Tuple::on_killed_token_account(who, currency_id); )* );
+	}
+}
+
+/// Hooks called when balance is transferred between accounts.
+pub trait OnTransfer<AccountId, CurrencyId, Balance> {
+	/// Called before the transfer, an error vetoes it.
+	fn pre_transfer(currency_id: CurrencyId, from: &AccountId, to: &AccountId, amount: Balance) -> DispatchResult;
+	/// Called after the transfer succeeded.
+	fn post_transfer(currency_id: CurrencyId, from: &AccountId, to: &AccountId, amount: Balance);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<AccountId, CurrencyId: Copy, Balance: Copy> OnTransfer<AccountId, CurrencyId, Balance> for Tuple {
+	fn pre_transfer(currency_id: CurrencyId, from: &AccountId, to: &AccountId, amount: Balance) -> DispatchResult {
+		for_tuples!( #( Tuple::pre_transfer(currency_id, from, to, amount)?; )* );
+		Ok(())
+	}
+
+	fn post_transfer(currency_id: CurrencyId, from: &AccountId, to: &AccountId, amount: Balance) {
+		for_tuples!( #( Tuple::post_transfer(currency_id, from, to, amount); )* );
+	}
+}
+
+/// Hooks called when balance is deposited into an account.
+pub trait OnDeposit<AccountId, CurrencyId, Balance> {
+	/// Called before the deposit, an error vetoes it.
+	fn pre_deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult;
+	/// Called after the deposit succeeded.
+	fn post_deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<AccountId, CurrencyId: Copy, Balance: Copy> OnDeposit<AccountId, CurrencyId, Balance> for Tuple {
+	fn pre_deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		for_tuples!( #( Tuple::pre_deposit(currency_id, who, amount)?; )* );
+		Ok(())
+	}
+
+	fn post_deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+		for_tuples!( #( Tuple::post_deposit(currency_id, who, amount); )* );
+	}
+}
+
+/// Hooks called when balance is withdrawn from an account.
+pub trait OnWithdraw<AccountId, CurrencyId, Balance> {
+	/// Called before the withdrawal, an error vetoes it.
+	fn pre_withdraw(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult;
+	/// Called after the withdrawal succeeded.
+	fn post_withdraw(currency_id: CurrencyId, who: &AccountId, amount: Balance);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<AccountId, CurrencyId: Copy, Balance: Copy> OnWithdraw<AccountId, CurrencyId, Balance> for Tuple {
+	fn pre_withdraw(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		for_tuples!( #( Tuple::pre_withdraw(currency_id, who, amount)?; )* );
+		Ok(())
+	}
+
+	fn post_withdraw(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+		for_tuples!( #( Tuple::post_withdraw(currency_id, who, amount); )* );
+	}
+}
+
+/// Hook called after balance is slashed from an account. Slashing cannot be
+/// vetoed.
+pub trait OnSlash<AccountId, CurrencyId, Balance> {
+	/// Called with the `amount` actually slashed from the free or reserved
+	/// balance of `who`.
+	fn on_slash(currency_id: CurrencyId, who: &AccountId, amount: Balance);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<AccountId, CurrencyId: Copy, Balance: Copy> OnSlash<AccountId, CurrencyId, Balance> for Tuple {
+	fn on_slash(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+		for_tuples!( #( Tuple::on_slash(currency_id, who, amount); )* );
+	}
+}
+
+/// Allowances of a multi-currency system, approved by owners for delegates
+/// to transfer on their behalf.
+pub trait Approvals<AccountId>: MultiCurrency<AccountId> {
+	/// The amount `delegate` may still transfer from `owner`.
+	fn allowance(currency_id: Self::CurrencyId, owner: &AccountId, delegate: &AccountId) -> Self::Balance;
+
+	/// Approve `delegate` to transfer up to `amount` from `owner`, replacing
+	/// any existing allowance. A zero `amount` cancels the approval.
+	fn approve(
+		currency_id: Self::CurrencyId,
+		owner: &AccountId,
+		delegate: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
+
+	/// Cancel the approval of `delegate` to transfer from `owner`.
+	fn cancel_approval(currency_id: Self::CurrencyId, owner: &AccountId, delegate: &AccountId) -> DispatchResult;
+
+	/// Transfer `amount` from `owner` to `to`, using the allowance of
+	/// `delegate`.
+	fn transfer_approved(
+		currency_id: Self::CurrencyId,
+		owner: &AccountId,
+		delegate: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
+}
+
 pub trait TransferAll<AccountId> {
 	fn transfer_all(source: &AccountId, dest: &AccountId) -> DispatchResult;
 }
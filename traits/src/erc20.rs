@@ -0,0 +1,57 @@
+use codec::{Decode, Encode};
+use sp_core::H160;
+use sp_runtime::RuntimeDebug;
+
+/// The reason an ERC-20 contract call failed.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum Erc20Error {
+	/// No contract is deployed at the address.
+	ContractNotFound,
+	/// The contract call reverted.
+	Reverted,
+	/// The contract call ran out of gas.
+	OutOfGas,
+	/// The output of the contract call can't be decoded.
+	InvalidOutput,
+}
+
+/// Execute calls to ERC-20 contracts, e.g. through an EVM pallet.
+pub trait Erc20Adapter<AccountId, Balance> {
+	/// The `totalSupply` of `contract`.
+	fn total_supply(contract: H160) -> Result<Balance, Erc20Error>;
+
+	/// The `balanceOf` `who` in `contract`.
+	fn balance_of(contract: H160, who: &AccountId) -> Result<Balance, Erc20Error>;
+
+	/// Transfer `amount` from `from` to `to` in `contract`.
+	fn transfer(contract: H160, from: &AccountId, to: &AccountId, amount: Balance) -> Result<(), Erc20Error>;
+
+	/// Mint `amount` to `who` in `contract`.
+	fn mint(contract: H160, who: &AccountId, amount: Balance) -> Result<(), Erc20Error>;
+
+	/// Burn `amount` from `who` in `contract`.
+	fn burn(contract: H160, who: &AccountId, amount: Balance) -> Result<(), Erc20Error>;
+}
+
+/// No contract is deployed.
+impl<AccountId, Balance> Erc20Adapter<AccountId, Balance> for () {
+	fn total_supply(_contract: H160) -> Result<Balance, Erc20Error> {
+		Err(Erc20Error::ContractNotFound)
+	}
+
+	fn balance_of(_contract: H160, _who: &AccountId) -> Result<Balance, Erc20Error> {
+		Err(Erc20Error::ContractNotFound)
+	}
+
+	fn transfer(_contract: H160, _from: &AccountId, _to: &AccountId, _amount: Balance) -> Result<(), Erc20Error> {
+		Err(Erc20Error::ContractNotFound)
+	}
+
+	fn mint(_contract: H160, _who: &AccountId, _amount: Balance) -> Result<(), Erc20Error> {
+		Err(Erc20Error::ContractNotFound)
+	}
+
+	fn burn(_contract: H160, _who: &AccountId, _amount: Balance) -> Result<(), Erc20Error> {
+		Err(Erc20Error::ContractNotFound)
+	}
+}
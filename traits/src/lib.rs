@@ -13,27 +13,33 @@ use serde::{Deserialize, Serialize};
 
 pub use auction::{Auction, AuctionHandler, AuctionInfo, OnNewBidResult};
 pub use currency::{
-	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
-	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency, OnDust,
+	Approvals, BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReducibleCurrency,
+	BasicReservableCurrency, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
+	MultiReducibleCurrency, MultiReservableCurrency, NamedMultiReservableCurrency, OnDeposit, OnDust,
+	OnKilledTokenAccount, OnNewTokenAccount, OnSlash, OnTransfer, OnWithdraw, WithdrawReasons,
 };
 pub use data_provider::{DataFeeder, DataProvider, DataProviderExtended};
+pub use erc20::{Erc20Adapter, Erc20Error};
 pub use get_by_key::GetByKey;
 pub use multi_asset::ConcreteFungibleAsset;
 pub use nft::NFT;
 pub use price::{DefaultPriceProvider, PriceProvider};
 pub use rewards::RewardHandler;
+pub use tokens::{BalanceLock, CurrencyMetadata, Reasons};
 pub use xcm_transfer::XcmTransfer;
 
 pub mod arithmetic;
 pub mod auction;
 pub mod currency;
 pub mod data_provider;
+pub mod erc20;
 pub mod get_by_key;
 pub mod location;
 pub mod multi_asset;
 pub mod nft;
 pub mod price;
 pub mod rewards;
+pub mod tokens;
 pub mod xcm_transfer;
 
 /// New data handler
@@ -0,0 +1,71 @@
+//! Types of the `orml-tokens` module which are shared with its runtime API.
+
+use crate::{LockIdentifier, WithdrawReasons};
+use codec::{Decode, Encode, MaxEncodedLen};
+use sp_runtime::RuntimeDebug;
+use sp_std::ops::BitOr;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Simplified reasons for withdrawing balance.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Reasons {
+	/// Paying system transaction fees.
+	Fee = 0,
+	/// Any reason other than paying system transaction fees.
+	Misc = 1,
+	/// Any reason at all.
+	All = 2,
+}
+
+impl From<WithdrawReasons> for Reasons {
+	fn from(r: WithdrawReasons) -> Reasons {
+		if r == WithdrawReasons::TRANSACTION_PAYMENT {
+			Reasons::Fee
+		} else if r.contains(WithdrawReasons::TRANSACTION_PAYMENT) {
+			Reasons::All
+		} else {
+			Reasons::Misc
+		}
+	}
+}
+
+impl BitOr for Reasons {
+	type Output = Reasons;
+	fn bitor(self, other: Reasons) -> Reasons {
+		if self == other {
+			return self;
+		}
+		Reasons::All
+	}
+}
+
+/// A single lock on a balance. There can be many of these on an account and
+/// they "overlap", so the same balance is frozen by multiple locks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug)]
+pub struct BalanceLock<Balance> {
+	/// An identifier for this lock. Only one lock may be in existence for
+	/// each identifier.
+	pub id: LockIdentifier,
+	/// The amount which the free balance may not drop below when this lock
+	/// is in effect.
+	pub amount: Balance,
+	/// The withdraw reasons this lock applies to: `Fee` restricts only the
+	/// payment of transaction fees, `Misc` restricts everything else, and
+	/// `All` restricts both.
+	pub reasons: Reasons,
+}
+
+/// The metadata of a currency.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CurrencyMetadata<BoundedString> {
+	/// The user friendly name of the currency.
+	pub name: BoundedString,
+	/// The ticker symbol of the currency.
+	pub symbol: BoundedString,
+	/// The number of decimals the currency uses to represent one unit.
+	pub decimals: u8,
+}
@@ -0,0 +1,154 @@
+//! Benchmarks for the vesting module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+/// A revocable and transferable step vesting schedule of the native currency, funded by
+/// `funder`.
+fn revocable_schedule<T: Config>(funder: &T::AccountId) -> VestingScheduleOf<T> {
+	VestingSchedule {
+		currency_id: T::GetNativeCurrencyId::get(),
+		timeline: Timeline::BlockNumber,
+		start: Zero::zero(),
+		curve: VestingCurve::Step {
+			period: 2u32.into(),
+			period_count: 2,
+			per_period: T::MinVestedTransfer::get(&T::GetNativeCurrencyId::get()),
+		},
+		revocable: Some(Revocable::Funder(funder.clone())),
+		transferable: true,
+	}
+}
+
+/// Returns the account of `T::VestedTransferOrigin`, with enough balance to
+/// fund `MaxVestingSchedules` vesting schedules.
+fn funded_funder<T: Config>() -> T::AccountId {
+	let funder = T::VestedTransferOrigin::ensure_origin(T::VestedTransferOrigin::successful_origin())
+		.expect("successful origin; qed");
+	let amount = T::MinVestedTransfer::get(&T::GetNativeCurrencyId::get())
+		.saturating_mul(4u32.into())
+		.saturating_mul(T::MaxVestingSchedules::get().into())
+		.saturating_mul(1_000u32.into());
+	T::MultiCurrency::deposit(T::GetNativeCurrencyId::get(), &funder, amount).expect("deposit funder; qed");
+	funder
+}
+
+/// Add `count` vesting schedules funded by `funder` to `to`.
+fn add_vesting_schedules<T: Config>(funder: &T::AccountId, to: &T::AccountId, count: u32) {
+	for _ in 0..count {
+		Pallet::<T>::do_vested_transfer(funder, to, revocable_schedule::<T>(funder)).expect("vested transfer; qed");
+	}
+}
+
+benchmarks! {
+	vested_transfer {
+		let funder = funded_funder::<T>();
+		let to: T::AccountId = account("to", 0, SEED);
+		let schedule = revocable_schedule::<T>(&funder);
+	}: _(T::VestedTransferOrigin::successful_origin(), T::Lookup::unlookup(to.clone()), schedule.clone())
+	verify {
+		assert_eq!(Pallet::<T>::vesting_schedules(&to).into_inner(), sp_std::vec![schedule]);
+	}
+
+	claim {
+		let i in 1 .. T::MaxVestingSchedules::get();
+
+		let funder = funded_funder::<T>();
+		let to: T::AccountId = account("to", 0, SEED);
+		add_vesting_schedules::<T>(&funder, &to, i);
+	}: _(RawOrigin::Signed(to.clone()), T::GetNativeCurrencyId::get())
+	verify {
+		assert_eq!(Pallet::<T>::vesting_schedules(&to).len() as u32, i);
+	}
+
+	update_vesting_schedules {
+		let i in 1 .. T::MaxVestingSchedules::get();
+
+		let funder = funded_funder::<T>();
+		let to: T::AccountId = account("to", 0, SEED);
+		T::MultiCurrency::transfer(
+			T::GetNativeCurrencyId::get(),
+			&funder,
+			&to,
+			T::MinVestedTransfer::get(&T::GetNativeCurrencyId::get())
+				.saturating_mul(2u32.into())
+				.saturating_mul(i.into()),
+		)
+		.expect("transfer; qed");
+		let schedules = (0..i).map(|_| revocable_schedule::<T>(&funder)).collect::<Vec<_>>();
+	}: _(RawOrigin::Root, T::Lookup::unlookup(to.clone()), T::GetNativeCurrencyId::get(), schedules)
+	verify {
+		assert_eq!(Pallet::<T>::vesting_schedules(&to).len() as u32, i);
+	}
+
+	revoke_vesting {
+		let i in 1 .. T::MaxVestingSchedules::get();
+
+		let funder = funded_funder::<T>();
+		let to: T::AccountId = account("to", 0, SEED);
+		add_vesting_schedules::<T>(&funder, &to, i);
+		// revoking the first schedule shifts all the others
+	}: _(RawOrigin::Signed(funder), T::Lookup::unlookup(to.clone()), 0)
+	verify {
+		assert_eq!(Pallet::<T>::vesting_schedules(&to).len() as u32, i - 1);
+	}
+
+	merge_schedules {
+		let i in 2 .. T::MaxVestingSchedules::get();
+
+		let funder = funded_funder::<T>();
+		let to: T::AccountId = account("to", 0, SEED);
+		// the worst case merges two interleaved `MaxMilestones` milestones,
+		// which are sorted and summed up before falling back to a single
+		// milestone, as the merged ones exceed `MaxMilestones`
+		for n in 0..2u32 {
+			let milestones = (0..MaxMilestones::get())
+				.map(|m| ((2 * m + n + 1).into(), T::MinVestedTransfer::get(&T::GetNativeCurrencyId::get())))
+				.collect::<Vec<_>>();
+			let schedule = VestingSchedule {
+				curve: VestingCurve::Milestones(milestones.try_into().expect("`MaxMilestones` milestones; qed")),
+				..revocable_schedule::<T>(&funder)
+			};
+			Pallet::<T>::do_vested_transfer(&funder, &to, schedule).expect("vested transfer; qed");
+		}
+		add_vesting_schedules::<T>(&funder, &to, i - 2);
+		// removing the first schedules shifts all the others
+	}: _(RawOrigin::Signed(to.clone()), 0, 1)
+	verify {
+		let schedules = Pallet::<T>::vesting_schedules(&to);
+		assert_eq!(schedules.len() as u32, i - 1);
+		assert!(matches!(
+			&schedules[i as usize - 2].curve,
+			VestingCurve::Milestones(milestones) if milestones.len() == 1
+		));
+	}
+
+	split_schedule {
+		let i in 0 .. T::MaxVestingSchedules::get() - 1;
+
+		let funder = funded_funder::<T>();
+		let from: T::AccountId = account("from", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		add_vesting_schedules::<T>(&funder, &to, i);
+		let amount = T::MinVestedTransfer::get(&T::GetNativeCurrencyId::get());
+		// a linear vesting schedule could be split in halves
+		let schedule = VestingSchedule {
+			curve: VestingCurve::Linear {
+				duration: 4u32.into(),
+				total: amount.saturating_mul(2u32.into()),
+			},
+			..revocable_schedule::<T>(&funder)
+		};
+		Pallet::<T>::do_vested_transfer(&funder, &from, schedule).expect("vested transfer; qed");
+	}: _(RawOrigin::Signed(from.clone()), 0, T::Lookup::unlookup(to.clone()), amount)
+	verify {
+		assert_eq!(Pallet::<T>::vesting_schedules(&to).len() as u32, i + 1);
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::build(), crate::mock::Runtime,);
@@ -6,23 +6,53 @@
 //! uses the *graded vesting* way, which unlocks a specific amount of balance
 //! every period of time, until all balance unlocked.
 //!
+//! Balances of any currency of `Config::MultiCurrency` could be vested, and
+//! each currency of an account is locked by its own `VESTING_LOCK_ID` lock.
+//!
 //! ### Vesting Schedule
 //!
 //! The schedule of a vesting is described by data structure `VestingSchedule`:
-//! from the block number of `start`, for every `period` amount of blocks,
-//! `per_period` amount of balance would unlocked, until number of periods
-//! `period_count` reached. Note in vesting schedules, *time* is measured by
-//! block number. All `VestingSchedule`s under an account could be queried in
-//! chain state.
+//! from the block number of `start`, the balance of `currency_id` would be
+//! unlocked by the `VestingCurve`:
+//!
+//! - `Step` - for every `period` amount of blocks, `per_period` amount of
+//!   balance would unlocked, until number of periods `period_count` reached.
+//! - `Linear` - the balance is unlocked linearly every block, in `duration`
+//!   amount of blocks.
+//! - `CliffLinear` - like `Linear`, but nothing is unlocked before the `cliff`.
+//! - `Milestones` - each milestone unlocks an amount of balance at an offset.
+//!
+//! Note in vesting schedules, *time* is measured by the `Timeline`, either by
+//! block number of `Config::BlockNumberProvider`, or by timestamp of
+//! `Config::Time`, which doesn't drift when block times change. All
+//! `VestingSchedule`s under an account could be queried in chain state.
+//!
+//! ### Revocable Vesting
+//!
+//! A vesting schedule could be marked as `revocable` in `vested_transfer`,
+//! either by the funder with `Revocable::Funder`, or by `Config::RevokeOrigin`
+//! with `Revocable::Origin`. Revoking keeps the vested balance under the
+//! account, and returns the unvested balance to the funder, or to
+//! `Config::TreasuryAccount`.
+//!
+//! ### Merging and Splitting
+//!
+//! Two vesting schedules of an account could be merged into one, which never
+//! unlocks earlier than the merged ones. A `transferable` vesting schedule
+//! could be split, to transfer part of it to another account.
 //!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
 //! - `vested_transfer` - Add a new vesting schedule for an account.
-//! - `claim` - Claim unlocked balances.
-//! - `update_vesting_schedules` - Update all vesting schedules under an
-//!   account, `root` origin required.
+//! - `claim` - Claim unlocked balances of a currency.
+//! - `update_vesting_schedules` - Update all vesting schedules of a currency
+//!   under an account, `root` origin required.
+//! - `revoke_vesting` - Revoke a revocable vesting schedule of an account.
+//! - `merge_schedules` - Merge two vesting schedules of the sender.
+//! - `split_schedule` - Split part of a transferable vesting schedule of the
+//!   sender to another account.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -31,12 +61,18 @@ use codec::{HasCompact, MaxEncodedLen};
 use frame_support::{
 	ensure,
 	pallet_prelude::*,
-	traits::{Currency, EnsureOrigin, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, WithdrawReasons},
+	parameter_types,
+	traits::{EnsureOrigin, Get, LockIdentifier, StorageVersion, Time},
 	transactional, BoundedVec,
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
+use orml_traits::{GetByKey, MultiCurrency, MultiLockableCurrency};
 use sp_runtime::{
-	traits::{AtLeast32Bit, BlockNumberProvider, CheckedAdd, Saturating, StaticLookup, Zero},
+	helpers_128bit::multiply_by_rational,
+	traits::{
+		AtLeast32Bit, BadOrigin, BlockNumberProvider, CheckedAdd, Saturating, StaticLookup, UniqueSaturatedFrom,
+		UniqueSaturatedInto, Zero,
+	},
 	ArithmeticError, DispatchResult, RuntimeDebug,
 };
 use sp_std::{
@@ -45,6 +81,8 @@ use sp_std::{
 	vec::Vec,
 };
 
+mod benchmarking;
+pub mod migrations;
 mod mock;
 mod tests;
 mod weights;
@@ -54,53 +92,372 @@ pub use weights::WeightInfo;
 
 pub const VESTING_LOCK_ID: LockIdentifier = *b"ormlvest";
 
+parameter_types! {
+	/// The maximum number of milestones of a `VestingCurve::Milestones`.
+	pub const MaxMilestones: u32 = 32;
+}
+
+/// The curve the balance of a vesting schedule is unlocked by, relative to
+/// the `start` of the schedule.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
+pub enum VestingCurve<Moment, Balance: HasCompact> {
+	/// `per_period` amount is unlocked every `period` of time, until
+	/// `period_count` periods are reached.
+	Step {
+		/// Time between vest
+		period: Moment,
+		/// Number of vest
+		period_count: u32,
+		/// Amount of tokens to release per vest
+		#[codec(compact)]
+		per_period: Balance,
+	},
+	/// `total` amount is unlocked linearly, in `duration` of time.
+	Linear {
+		/// Time to unlock all
+		duration: Moment,
+		/// Amount of tokens to release
+		#[codec(compact)]
+		total: Balance,
+	},
+	/// Like `Linear`, but nothing is unlocked before `cliff` of time, when
+	/// the amount vested so far is unlocked at once.
+	CliffLinear {
+		/// Time before the first unlock
+		cliff: Moment,
+		/// Time to unlock all
+		duration: Moment,
+		/// Amount of tokens to release
+		#[codec(compact)]
+		total: Balance,
+	},
+	/// Each `(offset, amount)` milestone unlocks `amount` after `offset` of
+	/// time, the offsets are strictly increasing.
+	Milestones(BoundedVec<(Moment, Balance), MaxMilestones>),
+}
+
+impl<Moment: AtLeast32Bit + Copy, Balance: AtLeast32Bit + Copy> VestingCurve<Moment, Balance> {
+	/// Returns the time to unlock all, `None` if calculation overflows.
+	pub fn duration(&self) -> Option<Moment> {
+		match self {
+			// period * period_count
+			VestingCurve::Step {
+				period, period_count, ..
+			} => period.checked_mul(&(*period_count).into()),
+			VestingCurve::Linear { duration, .. } | VestingCurve::CliffLinear { duration, .. } => Some(*duration),
+			VestingCurve::Milestones(milestones) => Some(milestones.last().map_or_else(Zero::zero, |m| m.0)),
+		}
+	}
+
+	/// Returns all locked amount, `None` if calculation overflows.
+	pub fn total_amount(&self) -> Option<Balance> {
+		match self {
+			VestingCurve::Step {
+				period_count,
+				per_period,
+				..
+			} => per_period.checked_mul(&(*period_count).into()),
+			VestingCurve::Linear { total, .. } | VestingCurve::CliffLinear { total, .. } => Some(*total),
+			VestingCurve::Milestones(milestones) => milestones
+				.iter()
+				.try_fold(Zero::zero(), |acc: Balance, m| acc.checked_add(&m.1)),
+		}
+	}
+
+	/// Returns locked amount `elapsed` time after the start, or `None` if
+	/// not started yet, which locks the total amount.
+	///
+	/// Note this func assumes curve is a valid one(non-zero period and
+	/// duration, non-overflow total amount), and it should be guaranteed by
+	/// callers.
+	pub fn locked_amount(&self, elapsed: Option<Moment>) -> Balance {
+		let total = self.total_amount().expect("ensured non-overflow total amount; qed");
+		let elapsed = match elapsed {
+			Some(elapsed) => elapsed,
+			None => return total,
+		};
+		match self {
+			VestingCurve::Step {
+				period,
+				period_count,
+				per_period,
+			} => {
+				// full = elapsed / period
+				// unrealized = period_count - full
+				// per_period * unrealized
+				let full = elapsed.checked_div(period).expect("ensured non-zero period; qed");
+				let unrealized = period_count.saturating_sub(full.unique_saturated_into());
+				per_period.saturating_mul(unrealized.into())
+			}
+			VestingCurve::Linear { duration, .. } => Self::linear_locked_amount(total, elapsed, *duration),
+			VestingCurve::CliffLinear { cliff, duration, .. } => {
+				if elapsed < *cliff {
+					total
+				} else {
+					Self::linear_locked_amount(total, elapsed, *duration)
+				}
+			}
+			VestingCurve::Milestones(milestones) => milestones
+				.iter()
+				.filter(|m| m.0 > elapsed)
+				.fold(Zero::zero(), |acc: Balance, m| acc.saturating_add(m.1)),
+		}
+	}
+
+	/// Split `amount` of the total out of the curve. Returns the rest and the
+	/// split curves, whose locked amounts add up to no less than the curve's,
+	/// or `None` if `amount` is zero or not less than the total, or a `Step`
+	/// curve can't be split evenly by `period_count`.
+	pub fn split(&self, amount: Balance) -> Option<(Self, Self)> {
+		let total = self.total_amount()?;
+		if amount.is_zero() || amount >= total {
+			return None;
+		}
+		match self {
+			VestingCurve::Step {
+				period,
+				period_count,
+				per_period,
+			} => {
+				let count: Balance = (*period_count).into();
+				if !(amount % count).is_zero() {
+					return None;
+				}
+				let split = amount / count;
+				Some((
+					VestingCurve::Step {
+						period: *period,
+						period_count: *period_count,
+						per_period: *per_period - split,
+					},
+					VestingCurve::Step {
+						period: *period,
+						period_count: *period_count,
+						per_period: split,
+					},
+				))
+			}
+			VestingCurve::Linear { duration, .. } => Some((
+				VestingCurve::Linear {
+					duration: *duration,
+					total: total - amount,
+				},
+				VestingCurve::Linear {
+					duration: *duration,
+					total: amount,
+				},
+			)),
+			VestingCurve::CliffLinear { cliff, duration, .. } => Some((
+				VestingCurve::CliffLinear {
+					cliff: *cliff,
+					duration: *duration,
+					total: total - amount,
+				},
+				VestingCurve::CliffLinear {
+					cliff: *cliff,
+					duration: *duration,
+					total: amount,
+				},
+			)),
+			VestingCurve::Milestones(milestones) => {
+				// split by the cumulative amounts, so the split milestones add
+				// up to `amount` exactly
+				let mut rest = Vec::with_capacity(milestones.len());
+				let mut split = Vec::with_capacity(milestones.len());
+				let (mut cumulative, mut split_cumulative): (Balance, Balance) = (Zero::zero(), Zero::zero());
+				for (offset, milestone_amount) in milestones.iter() {
+					cumulative = cumulative.saturating_add(*milestone_amount);
+					let next_split_cumulative: Balance = multiply_by_rational(
+						cumulative.unique_saturated_into(),
+						amount.unique_saturated_into(),
+						total.unique_saturated_into(),
+					)
+					.ok()?
+					.unique_saturated_into();
+					let split_amount = next_split_cumulative.saturating_sub(split_cumulative);
+					split_cumulative = next_split_cumulative;
+					rest.push((*offset, milestone_amount.saturating_sub(split_amount)));
+					split.push((*offset, split_amount));
+				}
+				Some((
+					VestingCurve::Milestones(rest.try_into().ok()?),
+					VestingCurve::Milestones(split.try_into().ok()?),
+				))
+			}
+		}
+	}
+
+	/// Returns the `(offset, amount)` milestones unlocking the same as the
+	/// curve, or `None` if it's not a stepped curve, or has more than
+	/// `MaxMilestones` steps.
+	fn milestones(&self) -> Option<Vec<(Moment, Balance)>> {
+		match self {
+			VestingCurve::Step {
+				period,
+				period_count,
+				per_period,
+			} => {
+				if *period_count > MaxMilestones::get() {
+					return None;
+				}
+				(1..=*period_count)
+					.map(|n| Some((period.checked_mul(&n.into())?, *per_period)))
+					.collect()
+			}
+			VestingCurve::Milestones(milestones) => Some(milestones.to_vec()),
+			VestingCurve::Linear { .. } | VestingCurve::CliffLinear { .. } => None,
+		}
+	}
+
+	/// Returns the locked amount of `total` unlocked linearly in `duration`,
+	/// `elapsed` time after the start.
+	fn linear_locked_amount(total: Balance, elapsed: Moment, duration: Moment) -> Balance {
+		if elapsed >= duration {
+			return Zero::zero();
+		}
+		// total * elapsed / duration, rounded down
+		let unlocked = multiply_by_rational(
+			total.unique_saturated_into(),
+			elapsed.unique_saturated_into(),
+			duration.unique_saturated_into(),
+		)
+		.map_or(total, |unlocked| unlocked.unique_saturated_into());
+		total.saturating_sub(unlocked)
+	}
+}
+
+/// Who could revoke a vesting schedule, and receives the unvested balance of
+/// it.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
+pub enum Revocable<AccountId> {
+	/// Revocable by the funder, which receives the unvested balance
+	Funder(AccountId),
+	/// Revocable by `Config::RevokeOrigin`, the unvested balance is returned
+	/// to `Config::TreasuryAccount`
+	Origin,
+}
+
+/// How the time of a vesting schedule is measured.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
+pub enum Timeline {
+	/// By the block number of `Config::BlockNumberProvider`
+	BlockNumber,
+	/// By the timestamp of `Config::Time`
+	Timestamp,
+}
+
 /// The vesting schedule.
 ///
-/// Benefits would be granted gradually, `per_period` amount every `period`
-/// of blocks after `start`.
+/// Benefits of `currency_id` would be granted gradually after `start`, by the
+/// `curve`, of the time measured by the `timeline`.
 #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
-pub struct VestingSchedule<BlockNumber, Balance: HasCompact> {
-	/// Vesting starting block
-	pub start: BlockNumber,
-	/// Number of blocks between vest
-	pub period: BlockNumber,
-	/// Number of vest
-	pub period_count: u32,
-	/// Amount of tokens to release per vest
-	#[codec(compact)]
-	pub per_period: Balance,
+pub struct VestingSchedule<AccountId, CurrencyId, Moment, Balance: HasCompact> {
+	/// Vested currency
+	pub currency_id: CurrencyId,
+	/// How the time of the vesting is measured
+	pub timeline: Timeline,
+	/// Vesting starting time, measured by the `timeline`
+	pub start: Moment,
+	/// The vesting curve
+	pub curve: VestingCurve<Moment, Balance>,
+	/// Who could revoke the vesting, `None` if irrevocable
+	pub revocable: Option<Revocable<AccountId>>,
+	/// Whether the beneficiary could split the vesting to other accounts
+	pub transferable: bool,
 }
 
-impl<BlockNumber: AtLeast32Bit + Copy, Balance: AtLeast32Bit + Copy> VestingSchedule<BlockNumber, Balance> {
-	/// Returns the end of all periods, `None` if calculation overflows.
-	pub fn end(&self) -> Option<BlockNumber> {
-		// period * period_count + start
-		self.period
-			.checked_mul(&self.period_count.into())?
-			.checked_add(&self.start)
+impl<AccountId, CurrencyId, Moment: AtLeast32Bit + Copy, Balance: AtLeast32Bit + Copy>
+	VestingSchedule<AccountId, CurrencyId, Moment, Balance>
+{
+	/// Returns the end of the vesting, `None` if calculation overflows.
+	pub fn end(&self) -> Option<Moment> {
+		self.curve.duration()?.checked_add(&self.start)
 	}
 
 	/// Returns all locked amount, `None` if calculation overflows.
 	pub fn total_amount(&self) -> Option<Balance> {
-		self.per_period.checked_mul(&self.period_count.into())
+		self.curve.total_amount()
 	}
 
 	/// Returns locked amount for a given `time`.
 	///
-	/// Note this func assumes schedule is a valid one(non-zero period and
-	/// non-overflow total amount), and it should be guaranteed by callers.
-	pub fn locked_amount(&self, time: BlockNumber) -> Balance {
-		// full = (time - start) / period
-		// unrealized = period_count - full
-		// per_period * unrealized
-		let full = time
-			.saturating_sub(self.start)
-			.checked_div(&self.period)
-			.expect("ensured non-zero period; qed");
-		let unrealized = self.period_count.saturating_sub(full.unique_saturated_into());
-		self.per_period
-			.checked_mul(&unrealized.into())
-			.expect("ensured non-overflow total amount; qed")
+	/// Note this func assumes schedule is a valid one, and it should be
+	/// guaranteed by callers.
+	pub fn locked_amount(&self, time: Moment) -> Balance {
+		self.curve.locked_amount(time.checked_sub(&self.start))
+	}
+}
+
+impl<AccountId: Clone, CurrencyId: Clone, Moment: AtLeast32Bit + Copy, Balance: AtLeast32Bit + Copy>
+	VestingSchedule<AccountId, CurrencyId, Moment, Balance>
+{
+	/// Merge `other` into a vesting schedule unlocking the same, or `None`
+	/// if they can't be merged so.
+	///
+	/// `Step` curves with the same terms are merged into one, and stepped
+	/// curves into `Milestones`. `Linear` curves are not merged, as the
+	/// rounding of the merged one could unlock earlier.
+	///
+	/// The merged schedule is of the `currency_id`, `timeline` and
+	/// `revocable` of `self`, which callers should ensure are the same of
+	/// `other`.
+	pub fn merge(&self, other: &Self) -> Option<Self> {
+		let transferable = self.transferable && other.transferable;
+		if let (
+			VestingCurve::Step {
+				period,
+				period_count,
+				per_period,
+			},
+			VestingCurve::Step {
+				period: other_period,
+				period_count: other_period_count,
+				per_period: other_per_period,
+			},
+		) = (&self.curve, &other.curve)
+		{
+			if self.start == other.start && period == other_period && period_count == other_period_count {
+				return Some(Self {
+					curve: VestingCurve::Step {
+						period: *period,
+						period_count: *period_count,
+						per_period: per_period.checked_add(other_per_period)?,
+					},
+					transferable,
+					..self.clone()
+				});
+			}
+		}
+
+		let start = self.start.min(other.start);
+		let shifted_milestones = |schedule: &Self| -> Option<Vec<(Moment, Balance)>> {
+			let shift = schedule.start - start;
+			schedule
+				.curve
+				.milestones()?
+				.into_iter()
+				.map(|(offset, amount)| Some((offset.checked_add(&shift)?, amount)))
+				.collect()
+		};
+		let mut milestones = shifted_milestones(self)?;
+		milestones.extend(shifted_milestones(other)?);
+		milestones.sort_by_key(|m| m.0);
+
+		// sum up the milestones of the same offset
+		let mut merged: Vec<(Moment, Balance)> = Vec::with_capacity(milestones.len());
+		for (offset, amount) in milestones {
+			match merged.last_mut() {
+				Some(last) if last.0 == offset => last.1 = last.1.checked_add(&amount)?,
+				_ => merged.push((offset, amount)),
+			}
+		}
+
+		Some(Self {
+			start,
+			curve: VestingCurve::Milestones(merged.try_into().ok()?),
+			transferable,
+			..self.clone()
+		})
 	}
 }
 
@@ -109,10 +466,15 @@ pub mod module {
 	use super::*;
 
 	pub(crate) type BalanceOf<T> =
-		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-	pub(crate) type VestingScheduleOf<T> = VestingSchedule<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type CurrencyIdOf<T> =
+		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+	pub(crate) type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+	pub(crate) type VestingScheduleOf<T> =
+		VestingSchedule<<T as frame_system::Config>::AccountId, CurrencyIdOf<T>, MomentOf<T>, BalanceOf<T>>;
 	pub type ScheduledItem<T> = (
 		<T as frame_system::Config>::AccountId,
+		CurrencyIdOf<T>,
 		<T as frame_system::Config>::BlockNumber,
 		<T as frame_system::Config>::BlockNumber,
 		u32,
@@ -123,15 +485,29 @@ pub mod module {
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		type Currency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+		type MultiCurrency: MultiLockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
 
+		/// The native currency id, the vesting schedules before multi-currency
+		/// vesting are migrated to.
 		#[pallet::constant]
-		/// The minimum amount transferred to call `vested_transfer`.
-		type MinVestedTransfer: Get<BalanceOf<Self>>;
+		type GetNativeCurrencyId: Get<CurrencyIdOf<Self>>;
+
+		/// The minimum amount transferred to call `vested_transfer`, of each
+		/// currency.
+		type MinVestedTransfer: GetByKey<CurrencyIdOf<Self>, BalanceOf<Self>>;
 
 		/// Required origin for vested transfer.
 		type VestedTransferOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
 
+		/// Required origin to revoke the vesting schedules of
+		/// `Revocable::Origin`.
+		type RevokeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The account the unvested balance of the vesting schedules revoked
+		/// by `RevokeOrigin` is returned to.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 
@@ -140,6 +516,10 @@ pub mod module {
 
 		// The block number provider
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
+
+		/// The timestamp provider, of the vesting schedules of
+		/// `Timeline::Timestamp`.
+		type Time: Time;
 	}
 
 	#[pallet::error]
@@ -156,21 +536,57 @@ pub mod module {
 		AmountLow,
 		/// Failed because the maximum vesting schedules was exceeded
 		MaxVestingSchedulesExceeded,
+		/// The vesting schedule is not of the given currency
+		CurrencyMismatch,
+		/// The cliff is after the end of the vesting
+		InvalidCliff,
+		/// The milestones are empty, or the offsets are not strictly increasing
+		InvalidMilestones,
+		/// The funder of a revocable vesting schedule is not the sender
+		InvalidFunder,
+		/// The vesting schedule is not revocable
+		NotRevocable,
+		/// The vesting schedule is not found
+		VestingScheduleNotFound,
+		/// The vesting schedules to merge are the same one
+		MergeSameVestingSchedule,
+		/// The vesting schedules to merge are of different revokers
+		RevocableMismatch,
+		/// The vesting schedules to merge are of different timelines
+		TimelineMismatch,
+		/// The vesting schedule is not transferable
+		NotTransferable,
+		/// The split amount is zero, not less than the total of the vesting
+		/// schedule, or not divisible by the period count of a step vesting
+		InvalidSplitAmount,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(fn deposit_event)]
-	#[pallet::metadata(T::AccountId = "AccountId", VestingScheduleOf<T> = "VestingScheduleOf", BalanceOf<T> = "Balance")]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		CurrencyIdOf<T> = "CurrencyId",
+		VestingScheduleOf<T> = "VestingScheduleOf",
+		BalanceOf<T> = "Balance"
+	)]
 	pub enum Event<T: Config> {
 		/// Added new vesting schedule. \[from, to, vesting_schedule\]
 		VestingScheduleAdded(T::AccountId, T::AccountId, VestingScheduleOf<T>),
-		/// Claimed vesting. \[who, locked_amount\]
-		Claimed(T::AccountId, BalanceOf<T>),
-		/// Updated vesting schedules. \[who\]
-		VestingSchedulesUpdated(T::AccountId),
+		/// Claimed vesting. \[who, currency_id, locked_amount\]
+		Claimed(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
+		/// Updated vesting schedules. \[who, currency_id\]
+		VestingSchedulesUpdated(T::AccountId, CurrencyIdOf<T>),
+		/// Revoked a vesting schedule, and returned the unvested amount.
+		/// \[who, vesting_schedule, to, unvested_amount\]
+		VestingScheduleRevoked(T::AccountId, VestingScheduleOf<T>, T::AccountId, BalanceOf<T>),
+		/// Merged two vesting schedules. \[who, merged_vesting_schedule\]
+		VestingSchedulesMerged(T::AccountId, VestingScheduleOf<T>),
+		/// Split part of a vesting schedule to another account. \[from, to,
+		/// split_vesting_schedule\]
+		VestingScheduleSplit(T::AccountId, T::AccountId, VestingScheduleOf<T>),
 	}
 
-	/// Vesting schedules of an account.
+	/// Vesting schedules of an account, of all currencies.
 	///
 	/// VestingSchedules: map AccountId => Vec<VestingSchedule>
 	#[pallet::storage]
@@ -200,44 +616,74 @@ pub mod module {
 		fn build(&self) {
 			self.vesting
 				.iter()
-				.for_each(|(who, start, period, period_count, per_period)| {
-					let total = *per_period * Into::<BalanceOf<T>>::into(*period_count);
-
-					let bounded_schedule: BoundedVec<VestingScheduleOf<T>, T::MaxVestingSchedules> =
-						vec![VestingSchedule {
-							start: *start,
-							period: *period,
-							period_count: *period_count,
-							per_period: *per_period,
-						}]
-						.try_into()
-						.expect("Max vesting schedules exceeded");
+				.for_each(|(who, currency_id, start, period, period_count, per_period)| {
+					VestingSchedules::<T>::try_append(
+						who,
+						VestingSchedule {
+							currency_id: *currency_id,
+							timeline: Timeline::BlockNumber,
+							start: Pallet::<T>::block_number_to_moment(*start),
+							curve: VestingCurve::Step {
+								period: Pallet::<T>::block_number_to_moment(*period),
+								period_count: *period_count,
+								per_period: *per_period,
+							},
+							revocable: None,
+							transferable: false,
+						},
+					)
+					.expect("Max vesting schedules exceeded");
+
+					let total = Pallet::<T>::vesting_schedules(who)
+						.iter()
+						.filter(|s| s.currency_id == *currency_id)
+						.fold(Zero::zero(), |acc: BalanceOf<T>, s| {
+							acc + s.total_amount().expect("Vesting amount overflow")
+						});
 
 					assert!(
-						T::Currency::free_balance(who) >= total,
+						T::MultiCurrency::free_balance(*currency_id, who) >= total,
 						"Account do not have enough balance"
 					);
 
-					T::Currency::set_lock(VESTING_LOCK_ID, who, total, WithdrawReasons::all());
-					VestingSchedules::<T>::insert(who, bounded_schedule);
+					T::MultiCurrency::set_lock(VESTING_LOCK_ID, *currency_id, who, total)
+						.expect("Failed to set vesting lock");
 				});
 		}
 	}
 
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migrations::pre_migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migrations::post_migrate::<T>()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::weight(T::WeightInfo::claim((<T as Config>::MaxVestingSchedules::get() / 2) as u32))]
-		pub fn claim(origin: OriginFor<T>) -> DispatchResult {
+		pub fn claim(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let locked_amount = Self::do_claim(&who);
+			let locked_amount = Self::do_claim(&who, currency_id)?;
 
-			Self::deposit_event(Event::Claimed(who, locked_amount));
+			Self::deposit_event(Event::Claimed(who, currency_id, locked_amount));
 			Ok(())
 		}
 
@@ -259,59 +705,154 @@ pub mod module {
 		pub fn update_vesting_schedules(
 			origin: OriginFor<T>,
 			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
 			vesting_schedules: Vec<VestingScheduleOf<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
 			let account = T::Lookup::lookup(who)?;
-			Self::do_update_vesting_schedules(&account, vesting_schedules)?;
+			Self::do_update_vesting_schedules(&account, currency_id, vesting_schedules)?;
 
-			Self::deposit_event(Event::VestingSchedulesUpdated(account));
+			Self::deposit_event(Event::VestingSchedulesUpdated(account, currency_id));
 			Ok(())
 		}
 
 		#[pallet::weight(T::WeightInfo::claim((<T as Config>::MaxVestingSchedules::get() / 2) as u32))]
-		pub fn claim_for(origin: OriginFor<T>, dest: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
+		pub fn claim_for(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+		) -> DispatchResult {
 			let _ = ensure_signed(origin)?;
 			let who = T::Lookup::lookup(dest)?;
-			let locked_amount = Self::do_claim(&who);
+			let locked_amount = Self::do_claim(&who, currency_id)?;
 
-			Self::deposit_event(Event::Claimed(who, locked_amount));
+			Self::deposit_event(Event::Claimed(who, currency_id, locked_amount));
 			Ok(())
 		}
+
+		/// Revoke the vesting schedule at `index` of `who`.
+		///
+		/// The vested balance is kept by `who`, and the unvested balance is
+		/// returned to the funder for `Revocable::Funder`, which must be the
+		/// sender, or to `T::TreasuryAccount` for `Revocable::Origin`, which
+		/// requires `T::RevokeOrigin`.
+		#[pallet::weight(T::WeightInfo::revoke_vesting(<T as Config>::MaxVestingSchedules::get()))]
+		pub fn revoke_vesting(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			index: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = T::Lookup::lookup(who)?;
+			let schedules = Self::vesting_schedules(&who);
+			let schedule = schedules
+				.get(index as usize)
+				.cloned()
+				.ok_or(Error::<T>::VestingScheduleNotFound)?;
+
+			let to = match &schedule.revocable {
+				Some(Revocable::Funder(funder)) => {
+					ensure!(ensure_signed(origin)? == *funder, BadOrigin);
+					funder.clone()
+				}
+				Some(Revocable::Origin) => {
+					T::RevokeOrigin::ensure_origin(origin)?;
+					T::TreasuryAccount::get()
+				}
+				None => return Err(Error::<T>::NotRevocable.into()),
+			};
+			let unvested_amount = Self::do_revoke_vesting(&who, index, &to)?;
+
+			Self::deposit_event(Event::VestingScheduleRevoked(who, schedule, to, unvested_amount));
+			Ok(Some(T::WeightInfo::revoke_vesting(schedules.len() as u32)).into())
+		}
+
+		/// Merge the vesting schedules at `index1` and `index2` of the sender
+		/// into one, appended to the vesting schedules.
+		///
+		/// The schedules must be of the same currency and revoker. If they
+		/// can't be merged into one unlocking the same, the merged one locks
+		/// their currently locked balance until the later end.
+		#[pallet::weight(T::WeightInfo::merge_schedules(<T as Config>::MaxVestingSchedules::get()))]
+		pub fn merge_schedules(origin: OriginFor<T>, index1: u32, index2: u32) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let schedules_count = Self::vesting_schedules(&who).len() as u32;
+			let merged = Self::do_merge_schedules(&who, index1, index2)?;
+
+			Self::deposit_event(Event::VestingSchedulesMerged(who, merged));
+			Ok(Some(T::WeightInfo::merge_schedules(schedules_count)).into())
+		}
+
+		/// Split `amount` of the total of the transferable vesting schedule at
+		/// `index` of the sender, to `dest`.
+		///
+		/// The split schedule vests the same way, and its currently locked
+		/// balance is transferred to `dest`.
+		#[pallet::weight(T::WeightInfo::split_schedule(<T as Config>::MaxVestingSchedules::get()))]
+		pub fn split_schedule(
+			origin: OriginFor<T>,
+			index: u32,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			let schedules_count = Self::vesting_schedules(&from)
+				.len()
+				.max(Self::vesting_schedules(&to).len()) as u32;
+			let split = Self::do_split_schedule(&from, &to, index, amount)?;
+
+			Self::deposit_event(Event::VestingScheduleSplit(from, to, split));
+			Ok(Some(T::WeightInfo::split_schedule(schedules_count)).into())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
-	fn do_claim(who: &T::AccountId) -> BalanceOf<T> {
-		let locked = Self::locked_balance(who);
+	#[transactional]
+	fn do_claim(who: &T::AccountId, currency_id: CurrencyIdOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+		let locked = Self::locked_balance(who, currency_id);
 		if locked.is_zero() {
-			// cleanup the storage and unlock the fund
-			<VestingSchedules<T>>::remove(who);
-			T::Currency::remove_lock(VESTING_LOCK_ID, who);
+			// unlock the fund
+			T::MultiCurrency::remove_lock(VESTING_LOCK_ID, currency_id, who)?;
 		} else {
-			T::Currency::set_lock(VESTING_LOCK_ID, who, locked, WithdrawReasons::all());
+			T::MultiCurrency::set_lock(VESTING_LOCK_ID, currency_id, who, locked)?;
 		}
-		locked
+		Ok(locked)
+	}
+
+	/// Returns the current time of `timeline`.
+	fn now(timeline: Timeline) -> MomentOf<T> {
+		match timeline {
+			Timeline::BlockNumber => Self::block_number_to_moment(T::BlockNumberProvider::current_block_number()),
+			Timeline::Timestamp => T::Time::now(),
+		}
+	}
+
+	/// Returns the time of `Timeline::BlockNumber` at `block_number`.
+	pub(crate) fn block_number_to_moment(block_number: T::BlockNumber) -> MomentOf<T> {
+		let block_number: u128 = block_number.unique_saturated_into();
+		UniqueSaturatedFrom::unique_saturated_from(block_number)
 	}
 
-	/// Returns locked balance based on current block number.
-	fn locked_balance(who: &T::AccountId) -> BalanceOf<T> {
-		let now = T::BlockNumberProvider::current_block_number();
+	/// Returns locked balance of `currency_id` based on current time, and
+	/// removes the finished vesting schedules of `currency_id`.
+	fn locked_balance(who: &T::AccountId, currency_id: CurrencyIdOf<T>) -> BalanceOf<T> {
 		<VestingSchedules<T>>::mutate_exists(who, |maybe_schedules| {
-			let total = if let Some(schedules) = maybe_schedules.as_mut() {
-				let mut total: BalanceOf<T> = Zero::zero();
+			let mut total: BalanceOf<T> = Zero::zero();
+			if let Some(schedules) = maybe_schedules.as_mut() {
 				schedules.retain(|s| {
-					let amount = s.locked_amount(now);
+					if s.currency_id != currency_id {
+						return true;
+					}
+					let amount = s.locked_amount(Self::now(s.timeline));
 					total = total.saturating_add(amount);
 					!amount.is_zero()
 				});
-				total
-			} else {
-				Zero::zero()
-			};
-			if total.is_zero() {
-				*maybe_schedules = None;
+				// cleanup the storage if no vesting schedules left
+				if schedules.is_empty() {
+					*maybe_schedules = None;
+				}
 			}
 			total
 		})
@@ -319,56 +860,223 @@ impl<T: Config> Pallet<T> {
 
 	#[transactional]
 	fn do_vested_transfer(from: &T::AccountId, to: &T::AccountId, schedule: VestingScheduleOf<T>) -> DispatchResult {
+		let currency_id = schedule.currency_id;
 		let schedule_amount = Self::ensure_valid_vesting_schedule(&schedule)?;
+		if let Some(Revocable::Funder(funder)) = &schedule.revocable {
+			ensure!(funder == from, Error::<T>::InvalidFunder);
+		}
 
-		let total_amount = Self::locked_balance(to)
+		let total_amount = Self::locked_balance(to, currency_id)
 			.checked_add(&schedule_amount)
 			.ok_or(ArithmeticError::Overflow)?;
 
-		T::Currency::transfer(from, to, schedule_amount, ExistenceRequirement::AllowDeath)?;
-		T::Currency::set_lock(VESTING_LOCK_ID, to, total_amount, WithdrawReasons::all());
+		T::MultiCurrency::transfer(currency_id, from, to, schedule_amount)?;
+		T::MultiCurrency::set_lock(VESTING_LOCK_ID, currency_id, to, total_amount)?;
 		<VestingSchedules<T>>::try_append(to, schedule).map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
 		Ok(())
 	}
 
-	fn do_update_vesting_schedules(who: &T::AccountId, schedules: Vec<VestingScheduleOf<T>>) -> DispatchResult {
-		let bounded_schedules: BoundedVec<VestingScheduleOf<T>, T::MaxVestingSchedules> = schedules
-			.try_into()
-			.map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
+	/// Remove the vesting schedule at `index` under `who`, and transfer the
+	/// unvested balance of it to `to`. Returns the unvested balance.
+	#[transactional]
+	fn do_revoke_vesting(who: &T::AccountId, index: u32, to: &T::AccountId) -> Result<BalanceOf<T>, DispatchError> {
+		let schedule = <VestingSchedules<T>>::try_mutate_exists(who, |maybe_schedules| -> Result<_, DispatchError> {
+			let schedules = maybe_schedules
+				.as_mut()
+				.filter(|schedules| (index as usize) < schedules.len())
+				.ok_or(Error::<T>::VestingScheduleNotFound)?;
+			let schedule = schedules.remove(index as usize);
+			// cleanup the storage if no vesting schedules left
+			if schedules.is_empty() {
+				*maybe_schedules = None;
+			}
+			Ok(schedule)
+		})?;
 
-		// empty vesting schedules cleanup the storage and unlock the fund
-		if bounded_schedules.len().is_zero() {
-			<VestingSchedules<T>>::remove(who);
-			T::Currency::remove_lock(VESTING_LOCK_ID, who);
-			return Ok(());
+		let unvested_amount = schedule.locked_amount(Self::now(schedule.timeline));
+
+		// lock the remaining vesting schedules only, to release the unvested
+		// balance
+		Self::do_claim(who, schedule.currency_id)?;
+		if !unvested_amount.is_zero() {
+			T::MultiCurrency::transfer(schedule.currency_id, who, to, unvested_amount)?;
+		}
+		Ok(unvested_amount)
+	}
+
+	/// Merge the vesting schedules at `index1` and `index2` under `who`.
+	/// Returns the merged vesting schedule.
+	#[transactional]
+	fn do_merge_schedules(who: &T::AccountId, index1: u32, index2: u32) -> Result<VestingScheduleOf<T>, DispatchError> {
+		ensure!(index1 != index2, Error::<T>::MergeSameVestingSchedule);
+
+		let merged = <VestingSchedules<T>>::try_mutate(who, |schedules| -> Result<_, DispatchError> {
+			let (index1, index2) = (index1 as usize, index2 as usize);
+			let schedule1 = schedules.get(index1).ok_or(Error::<T>::VestingScheduleNotFound)?;
+			let schedule2 = schedules.get(index2).ok_or(Error::<T>::VestingScheduleNotFound)?;
+			ensure!(
+				schedule1.currency_id == schedule2.currency_id,
+				Error::<T>::CurrencyMismatch
+			);
+			ensure!(
+				schedule1.revocable == schedule2.revocable,
+				Error::<T>::RevocableMismatch
+			);
+			ensure!(schedule1.timeline == schedule2.timeline, Error::<T>::TimelineMismatch);
+			let now = Self::now(schedule1.timeline);
+
+			let merged = schedule1.merge(schedule2).unwrap_or_else(|| {
+				// lock the currently locked balance until the later end, as the
+				// locked amounts are non-increasing
+				let end = schedule1.end().max(schedule2.end()).unwrap_or(now);
+				let locked = schedule1
+					.locked_amount(now)
+					.saturating_add(schedule2.locked_amount(now));
+				VestingSchedule {
+					currency_id: schedule1.currency_id,
+					timeline: schedule1.timeline,
+					start: now,
+					curve: VestingCurve::Milestones(
+						sp_std::vec![(end.saturating_sub(now), locked)]
+							.try_into()
+							.expect("a single milestone is less than `MaxMilestones`; qed"),
+					),
+					revocable: schedule1.revocable.clone(),
+					transferable: schedule1.transferable && schedule2.transferable,
+				}
+			});
+
+			// remove the later one first, so the index of the earlier one is kept
+			schedules.remove(index1.max(index2));
+			schedules.remove(index1.min(index2));
+			schedules
+				.try_push(merged.clone())
+				.map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
+			Ok(merged)
+		})?;
+
+		Self::do_claim(who, merged.currency_id)?;
+		Ok(merged)
+	}
+
+	/// Split `amount` of the vesting schedule at `index` under `from` to
+	/// `to`, and transfer the currently locked balance of the split one.
+	/// Returns the split vesting schedule.
+	#[transactional]
+	fn do_split_schedule(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		index: u32,
+		amount: BalanceOf<T>,
+	) -> Result<VestingScheduleOf<T>, DispatchError> {
+		let split = <VestingSchedules<T>>::try_mutate(from, |schedules| -> Result<_, DispatchError> {
+			let schedule = schedules
+				.get_mut(index as usize)
+				.ok_or(Error::<T>::VestingScheduleNotFound)?;
+			ensure!(schedule.transferable, Error::<T>::NotTransferable);
+			ensure!(
+				amount >= T::MinVestedTransfer::get(&schedule.currency_id),
+				Error::<T>::AmountLow
+			);
+
+			let (rest, split) = schedule.curve.split(amount).ok_or(Error::<T>::InvalidSplitAmount)?;
+			let split = VestingSchedule {
+				curve: split,
+				..schedule.clone()
+			};
+			schedule.curve = rest;
+			Ok(split)
+		})?;
+
+		let currency_id = split.currency_id;
+		let split_locked_amount = split.locked_amount(Self::now(split.timeline));
+		Self::do_claim(from, currency_id)?;
+		if !split_locked_amount.is_zero() {
+			T::MultiCurrency::transfer(currency_id, from, to, split_locked_amount)?;
 		}
+		<VestingSchedules<T>>::try_append(to, split.clone()).map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
+		Self::do_claim(to, currency_id)?;
+		Ok(split)
+	}
+
+	/// Replace the vesting schedules of `currency_id` under `who`, and keep
+	/// the ones of other currencies.
+	fn do_update_vesting_schedules(
+		who: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		schedules: Vec<VestingScheduleOf<T>>,
+	) -> DispatchResult {
+		ensure!(
+			schedules.iter().all(|s| s.currency_id == currency_id),
+			Error::<T>::CurrencyMismatch
+		);
 
-		let total_amount = bounded_schedules
-			.iter()
-			.try_fold::<_, _, Result<BalanceOf<T>, DispatchError>>(Zero::zero(), |acc_amount, schedule| {
+		let total_amount = schedules.iter().try_fold::<_, _, Result<BalanceOf<T>, DispatchError>>(
+			Zero::zero(),
+			|acc_amount, schedule| {
 				let amount = Self::ensure_valid_vesting_schedule(schedule)?;
 				Ok(acc_amount + amount)
-			})?;
+			},
+		)?;
 		ensure!(
-			T::Currency::free_balance(who) >= total_amount,
+			T::MultiCurrency::free_balance(currency_id, who) >= total_amount,
 			Error::<T>::InsufficientBalanceToLock,
 		);
 
-		T::Currency::set_lock(VESTING_LOCK_ID, who, total_amount, WithdrawReasons::all());
-		<VestingSchedules<T>>::insert(who, bounded_schedules);
+		let bounded_schedules: BoundedVec<VestingScheduleOf<T>, T::MaxVestingSchedules> = Self::vesting_schedules(who)
+			.into_iter()
+			.filter(|s| s.currency_id != currency_id)
+			.chain(schedules)
+			.collect::<Vec<_>>()
+			.try_into()
+			.map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
+
+		// empty vesting schedules cleanup the storage and unlock the fund
+		if total_amount.is_zero() {
+			T::MultiCurrency::remove_lock(VESTING_LOCK_ID, currency_id, who)?;
+		} else {
+			T::MultiCurrency::set_lock(VESTING_LOCK_ID, currency_id, who, total_amount)?;
+		}
+		if bounded_schedules.is_empty() {
+			<VestingSchedules<T>>::remove(who);
+		} else {
+			<VestingSchedules<T>>::insert(who, bounded_schedules);
+		}
 
 		Ok(())
 	}
 
 	/// Returns `Ok(amount)` if valid schedule, or error.
 	fn ensure_valid_vesting_schedule(schedule: &VestingScheduleOf<T>) -> Result<BalanceOf<T>, DispatchError> {
-		ensure!(!schedule.period.is_zero(), Error::<T>::ZeroVestingPeriod);
-		ensure!(!schedule.period_count.is_zero(), Error::<T>::ZeroVestingPeriodCount);
+		match &schedule.curve {
+			VestingCurve::Step {
+				period, period_count, ..
+			} => {
+				ensure!(!period.is_zero(), Error::<T>::ZeroVestingPeriod);
+				ensure!(!period_count.is_zero(), Error::<T>::ZeroVestingPeriodCount);
+			}
+			VestingCurve::Linear { duration, .. } => {
+				ensure!(!duration.is_zero(), Error::<T>::ZeroVestingPeriod);
+			}
+			VestingCurve::CliffLinear { cliff, duration, .. } => {
+				ensure!(!duration.is_zero(), Error::<T>::ZeroVestingPeriod);
+				ensure!(cliff <= duration, Error::<T>::InvalidCliff);
+			}
+			VestingCurve::Milestones(milestones) => {
+				ensure!(
+					!milestones.is_empty() && milestones.windows(2).all(|w| w[0].0 < w[1].0),
+					Error::<T>::InvalidMilestones
+				);
+			}
+		}
 		ensure!(schedule.end().is_some(), ArithmeticError::Overflow);
 
 		let total = schedule.total_amount().ok_or(ArithmeticError::Overflow)?;
 
-		ensure!(total >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+		ensure!(
+			total >= T::MinVestedTransfer::get(&schedule.currency_id),
+			Error::<T>::AmountLow
+		);
 
 		Ok(total)
 	}
@@ -0,0 +1,122 @@
+//! Storage migrations for the vesting module.
+//!
+//! The replaced storage layouts are kept in `vN` modules, so that they can be
+//! decoded in migrations and in the `try-runtime` checks.
+
+use super::*;
+use frame_support::storage::unhashed;
+
+/// Storage layouts before `StorageVersion` 1.
+pub mod v0 {
+	use super::*;
+
+	/// The vesting schedule of the single vested currency.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug)]
+	pub struct VestingSchedule<BlockNumber, Balance: HasCompact> {
+		pub start: BlockNumber,
+		pub period: BlockNumber,
+		pub period_count: u32,
+		#[codec(compact)]
+		pub per_period: Balance,
+	}
+
+	pub type VestingSchedulesOf<T> = Vec<VestingSchedule<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>>;
+}
+
+/// Migrate storage to the current `STORAGE_VERSION`.
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	if StorageVersion::get::<Pallet<T>>() < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+		StorageVersion::new(1).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	weight
+}
+
+/// Checks the on-chain state before `migrate` is applied.
+#[cfg(any(feature = "try-runtime", test))]
+pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+	ensure!(
+		StorageVersion::get::<Pallet<T>>() <= STORAGE_VERSION,
+		"vesting: unknown on-chain storage version"
+	);
+	ensure_vesting_schedules::<T>()
+}
+
+/// Checks the on-chain state after `migrate` is applied.
+#[cfg(any(feature = "try-runtime", test))]
+pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+	ensure!(
+		StorageVersion::get::<Pallet<T>>() == STORAGE_VERSION,
+		"vesting: storage version not updated"
+	);
+	ensure_vesting_schedules::<T>()
+}
+
+/// Ensure the `VestingSchedules` of every account decode with the on-chain
+/// layout, and are not empty.
+#[cfg(any(feature = "try-runtime", test))]
+pub fn ensure_vesting_schedules<T: Config>() -> Result<(), &'static str> {
+	let version = StorageVersion::get::<Pallet<T>>();
+
+	for who in VestingSchedules::<T>::iter_keys() {
+		let key = VestingSchedules::<T>::hashed_key_for(&who);
+		let len = if version < 1 {
+			unhashed::get::<v0::VestingSchedulesOf<T>>(&key).map(|schedules| schedules.len())
+		} else {
+			unhashed::get::<Vec<VestingScheduleOf<T>>>(&key).map(|schedules| schedules.len())
+		}
+		.ok_or("vesting: undecodable vesting schedules")?;
+
+		ensure!(len > 0, "vesting: empty vesting schedules");
+	}
+
+	Ok(())
+}
+
+/// Migrate to `StorageVersion` 1, from the single currency step vesting
+/// schedules.
+///
+/// The existing vesting schedules vest `T::GetNativeCurrencyId` by
+/// `VestingCurve::Step`, measured by block number, of `Moment` converted from
+/// the block numbers. They are irrevocable and not transferable.
+///
+/// `T::MultiCurrency` must route the native currency to the previous
+/// `Currency`, e.g. through `orml_currencies`, so the existing
+/// `VESTING_LOCK_ID` locks are kept.
+pub mod v1 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut translated: Weight = 0;
+		let currency_id = T::GetNativeCurrencyId::get();
+		let moment = Pallet::<T>::block_number_to_moment;
+
+		VestingSchedules::<T>::translate::<v0::VestingSchedulesOf<T>, _>(|_, old| {
+			translated += 1;
+			old.into_iter()
+				.map(|schedule| VestingSchedule {
+					currency_id,
+					timeline: Timeline::BlockNumber,
+					start: moment(schedule.start),
+					curve: VestingCurve::Step {
+						period: moment(schedule.period),
+						period_count: schedule.period_count,
+						per_period: schedule.per_period,
+					},
+					revocable: None,
+					transferable: false,
+				})
+				.collect::<Vec<_>>()
+				// the number of vesting schedules is bounded by the same
+				// `MaxVestingSchedules`
+				.try_into()
+				.ok()
+		});
+
+		T::DbWeight::get().reads_writes(translated, translated)
+	}
+}
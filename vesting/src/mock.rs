@@ -5,9 +5,10 @@
 use super::*;
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{EnsureOrigin, Everything},
+	traits::{EnsureOrigin, Everything, Nothing, Time},
 };
 use frame_system::RawOrigin;
+use orml_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
 
@@ -45,6 +46,10 @@ impl frame_system::Config for Runtime {
 }
 
 type Balance = u64;
+pub type CurrencyId = u32;
+
+pub const DOT: CurrencyId = 1;
+pub const BTC: CurrencyId = 2;
 
 parameter_types! {
 	pub const ExistentialDeposit: u64 = 1;
@@ -62,6 +67,68 @@ impl pallet_balances::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+parameter_type_with_key! {
+	pub MaxIssuance: |_currency_id: CurrencyId| -> Option<Balance> {
+		None
+	};
+}
+
+parameter_type_with_key! {
+	pub IssuanceHighWaterMark: |_currency_id: CurrencyId| -> Option<Balance> {
+		None
+	};
+}
+
+parameter_types! {
+	pub const DustAccount: AccountId = 100;
+	pub const MaxLocks: u32 = 10;
+	pub const MaxReserves: u32 = 10;
+	pub const MaxTransfers: u32 = 10;
+	pub const StringLimit: u32 = 50;
+	pub const ApprovalDeposit: Balance = 1;
+	pub const AllowFrozenReceiving: bool = true;
+	pub const MaintainHoldersIndex: bool = false;
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = i64;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type OnTransfer = ();
+	type OnDeposit = ();
+	type OnWithdraw = ();
+	type OnSlash = ();
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+	type SufficientCurrencies = Nothing;
+	type ProviderCurrencies = Everything;
+	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type MaxTransfers = MaxTransfers;
+	type DepositCurrency = PalletBalances;
+	type ApprovalDeposit = ApprovalDeposit;
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+	type DustReceiver = DustAccount;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MetadataOrigin = frame_system::EnsureRoot<AccountId>;
+	type StringLimit = StringLimit;
+	type AllowFrozenReceiving = AllowFrozenReceiving;
+	type MaintainHoldersIndex = MaintainHoldersIndex;
+	type MaxIssuance = MaxIssuance;
+	type IssuanceHighWaterMark = IssuanceHighWaterMark;
+}
+
 pub struct EnsureAliceOrBob;
 impl EnsureOrigin<Origin> for EnsureAliceOrBob {
 	type Success = AccountId;
@@ -76,14 +143,22 @@ impl EnsureOrigin<Origin> for EnsureAliceOrBob {
 
 	#[cfg(feature = "runtime-benchmarks")]
 	fn successful_origin() -> Origin {
-		Origin::from(RawOrigin::Signed(Default::default()))
+		Origin::from(RawOrigin::Signed(ALICE))
 	}
 }
 
 parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = DOT;
 	pub const MaxVestingSchedule: u32 = 2;
-	pub const MinVestedTransfer: u64 = 5;
 	pub static MockBlockNumberProvider: u64 = 0;
+	pub static MockTimestamp: u64 = 0;
+	pub const TreasuryAccount: AccountId = TREASURY;
+}
+
+parameter_type_with_key! {
+	pub MinVestedTransfer: |_currency_id: CurrencyId| -> Balance {
+		5
+	};
 }
 
 impl BlockNumberProvider for MockBlockNumberProvider {
@@ -94,14 +169,26 @@ impl BlockNumberProvider for MockBlockNumberProvider {
 	}
 }
 
+impl Time for MockTimestamp {
+	type Moment = u64;
+
+	fn now() -> Self::Moment {
+		Self::get()
+	}
+}
+
 impl Config for Runtime {
 	type Event = Event;
-	type Currency = PalletBalances;
+	type MultiCurrency = Tokens;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type MinVestedTransfer = MinVestedTransfer;
 	type VestedTransferOrigin = EnsureAliceOrBob;
+	type RevokeOrigin = frame_system::EnsureRoot<AccountId>;
+	type TreasuryAccount = TreasuryAccount;
 	type WeightInfo = ();
 	type MaxVestingSchedules = MaxVestingSchedule;
 	type BlockNumberProvider = MockBlockNumberProvider;
+	type Time = MockTimestamp;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -116,12 +203,14 @@ construct_runtime!(
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
 		Vesting: vesting::{Pallet, Storage, Call, Event<T>, Config<T>},
 		PalletBalances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Tokens: orml_tokens::{Pallet, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
 pub const CHARLIE: AccountId = 3;
+pub const TREASURY: AccountId = 10;
 
 #[derive(Default)]
 pub struct ExtBuilder;
@@ -132,14 +221,16 @@ impl ExtBuilder {
 			.build_storage::<Runtime>()
 			.unwrap();
 
-		pallet_balances::GenesisConfig::<Runtime> {
-			balances: vec![(ALICE, 100), (CHARLIE, 30)],
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, DOT, 100), (CHARLIE, DOT, 30), (ALICE, BTC, 100)],
+			metadata: vec![],
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
 
 		vesting::GenesisConfig::<Runtime> {
-			vesting: vec![(CHARLIE, 2, 3, 4, 5)], // who, start, period, period_count, per_period
+			// who, currency_id, start, period, period_count, per_period
+			vesting: vec![(CHARLIE, DOT, 2, 3, 4, 5)],
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
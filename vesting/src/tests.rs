@@ -5,51 +5,43 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok, error::BadOrigin};
 use mock::{Event, *};
-use pallet_balances::{BalanceLock, Reasons};
+use orml_tokens::{BalanceLock, Reasons};
+use proptest::prelude::*;
 
 #[test]
 fn vesting_from_chain_spec_works() {
 	ExtBuilder::build().execute_with(|| {
-		assert_ok!(PalletBalances::ensure_can_withdraw(
-			&CHARLIE,
-			10,
-			WithdrawReasons::TRANSFER,
-			20
-		));
-		assert!(PalletBalances::ensure_can_withdraw(&CHARLIE, 11, WithdrawReasons::TRANSFER, 19).is_err());
+		assert_ok!(<Tokens as MultiCurrency<_>>::ensure_can_withdraw(DOT, &CHARLIE, 10));
+		assert!(<Tokens as MultiCurrency<_>>::ensure_can_withdraw(DOT, &CHARLIE, 11).is_err());
 
 		assert_eq!(
 			Vesting::vesting_schedules(&CHARLIE),
 			vec![VestingSchedule {
+				currency_id: DOT,
+				timeline: Timeline::BlockNumber,
 				start: 2u64,
-				period: 3u64,
-				period_count: 4u32,
-				per_period: 5u64,
+				curve: VestingCurve::Step {
+					period: 3u64,
+					period_count: 4u32,
+					per_period: 5u64,
+				},
+				revocable: None,
+				transferable: false,
 			}]
 		);
 
 		MockBlockNumberProvider::set(13);
 
-		assert_ok!(Vesting::claim(Origin::signed(CHARLIE)));
+		assert_ok!(Vesting::claim(Origin::signed(CHARLIE), DOT));
 
-		assert_ok!(PalletBalances::ensure_can_withdraw(
-			&CHARLIE,
-			25,
-			WithdrawReasons::TRANSFER,
-			5
-		));
-		assert!(PalletBalances::ensure_can_withdraw(&CHARLIE, 26, WithdrawReasons::TRANSFER, 4).is_err());
+		assert_ok!(<Tokens as MultiCurrency<_>>::ensure_can_withdraw(DOT, &CHARLIE, 25));
+		assert!(<Tokens as MultiCurrency<_>>::ensure_can_withdraw(DOT, &CHARLIE, 26).is_err());
 
 		MockBlockNumberProvider::set(14);
 
-		assert_ok!(Vesting::claim(Origin::signed(CHARLIE)));
+		assert_ok!(Vesting::claim(Origin::signed(CHARLIE), DOT));
 
-		assert_ok!(PalletBalances::ensure_can_withdraw(
-			&CHARLIE,
-			30,
-			WithdrawReasons::TRANSFER,
-			0
-		));
+		assert_ok!(<Tokens as MultiCurrency<_>>::ensure_can_withdraw(DOT, &CHARLIE, 30));
 	});
 }
 
@@ -59,10 +51,16 @@ fn vested_transfer_works() {
 		System::set_block_number(1);
 
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 0u64,
-			period: 10u64,
-			period_count: 1u32,
-			per_period: 100u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 1u32,
+				per_period: 100u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule.clone()));
 		assert_eq!(Vesting::vesting_schedules(&BOB), vec![schedule.clone()]);
@@ -74,25 +72,37 @@ fn vested_transfer_works() {
 fn add_new_vesting_schedule_merges_with_current_locked_balance_and_until() {
 	ExtBuilder::build().execute_with(|| {
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 0u64,
-			period: 10u64,
-			period_count: 2u32,
-			per_period: 10u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 2u32,
+				per_period: 10u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule));
 
 		MockBlockNumberProvider::set(12);
 
 		let another_schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 10u64,
-			period: 13u64,
-			period_count: 1u32,
-			per_period: 7u64,
+			curve: VestingCurve::Step {
+				period: 13u64,
+				period_count: 1u32,
+				per_period: 7u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, another_schedule));
 
 		assert_eq!(
-			PalletBalances::locks(&BOB).get(0),
+			Tokens::locks(&BOB, DOT).get(0),
 			Some(&BalanceLock {
 				id: VESTING_LOCK_ID,
 				amount: 17u64,
@@ -106,13 +116,19 @@ fn add_new_vesting_schedule_merges_with_current_locked_balance_and_until() {
 fn cannot_use_fund_if_not_claimed() {
 	ExtBuilder::build().execute_with(|| {
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 10u64,
-			period: 10u64,
-			period_count: 1u32,
-			per_period: 50u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 1u32,
+				per_period: 50u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule));
-		assert!(PalletBalances::ensure_can_withdraw(&BOB, 1, WithdrawReasons::TRANSFER, 49).is_err());
+		assert!(<Tokens as MultiCurrency<_>>::ensure_can_withdraw(DOT, &BOB, 1).is_err());
 	});
 }
 
@@ -120,10 +136,16 @@ fn cannot_use_fund_if_not_claimed() {
 fn vested_transfer_fails_if_zero_period_or_count() {
 	ExtBuilder::build().execute_with(|| {
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 1u64,
-			period: 0u64,
-			period_count: 1u32,
-			per_period: 100u64,
+			curve: VestingCurve::Step {
+				period: 0u64,
+				period_count: 1u32,
+				per_period: 100u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule),
@@ -131,10 +153,16 @@ fn vested_transfer_fails_if_zero_period_or_count() {
 		);
 
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 1u64,
-			period: 1u64,
-			period_count: 0u32,
-			per_period: 100u64,
+			curve: VestingCurve::Step {
+				period: 1u64,
+				period_count: 0u32,
+				per_period: 100u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule),
@@ -147,14 +175,20 @@ fn vested_transfer_fails_if_zero_period_or_count() {
 fn vested_transfer_fails_if_transfer_err() {
 	ExtBuilder::build().execute_with(|| {
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 1u64,
-			period: 1u64,
-			period_count: 1u32,
-			per_period: 100u64,
+			curve: VestingCurve::Step {
+				period: 1u64,
+				period_count: 1u32,
+				per_period: 100u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(Origin::signed(BOB), ALICE, schedule),
-			pallet_balances::Error::<Runtime, _>::InsufficientBalance,
+			orml_tokens::Error::<Runtime>::BalanceTooLow,
 		);
 	});
 }
@@ -163,10 +197,16 @@ fn vested_transfer_fails_if_transfer_err() {
 fn vested_transfer_fails_if_overflow() {
 	ExtBuilder::build().execute_with(|| {
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 1u64,
-			period: 1u64,
-			period_count: 2u32,
-			per_period: u64::MAX,
+			curve: VestingCurve::Step {
+				period: 1u64,
+				period_count: 2u32,
+				per_period: u64::MAX,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule),
@@ -174,10 +214,16 @@ fn vested_transfer_fails_if_overflow() {
 		);
 
 		let another_schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: u64::MAX,
-			period: 1u64,
-			period_count: 2u32,
-			per_period: 1u64,
+			curve: VestingCurve::Step {
+				period: 1u64,
+				period_count: 2u32,
+				per_period: 1u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(Origin::signed(ALICE), BOB, another_schedule),
@@ -190,10 +236,16 @@ fn vested_transfer_fails_if_overflow() {
 fn vested_transfer_fails_if_bad_origin() {
 	ExtBuilder::build().execute_with(|| {
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 0u64,
-			period: 10u64,
-			period_count: 1u32,
-			per_period: 100u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 1u32,
+				per_period: 100u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(Origin::signed(CHARLIE), BOB, schedule),
@@ -206,33 +258,39 @@ fn vested_transfer_fails_if_bad_origin() {
 fn claim_works() {
 	ExtBuilder::build().execute_with(|| {
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 0u64,
-			period: 10u64,
-			period_count: 2u32,
-			per_period: 10u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 2u32,
+				per_period: 10u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule));
 
 		MockBlockNumberProvider::set(11);
 		// remain locked if not claimed
-		assert!(PalletBalances::transfer(Origin::signed(BOB), ALICE, 10).is_err());
+		assert!(Tokens::transfer(Origin::signed(BOB), ALICE, DOT, 10).is_err());
 		// unlocked after claiming
-		assert_ok!(Vesting::claim(Origin::signed(BOB)));
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
 		assert!(VestingSchedules::<Runtime>::contains_key(BOB));
-		assert_ok!(PalletBalances::transfer(Origin::signed(BOB), ALICE, 10));
+		assert_ok!(Tokens::transfer(Origin::signed(BOB), ALICE, DOT, 10));
 		// more are still locked
-		assert!(PalletBalances::transfer(Origin::signed(BOB), ALICE, 1).is_err());
+		assert!(Tokens::transfer(Origin::signed(BOB), ALICE, DOT, 1).is_err());
 
 		MockBlockNumberProvider::set(21);
 		// claim more
-		assert_ok!(Vesting::claim(Origin::signed(BOB)));
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
 		assert!(!VestingSchedules::<Runtime>::contains_key(BOB));
-		assert_ok!(PalletBalances::transfer(Origin::signed(BOB), ALICE, 10));
+		assert_ok!(Tokens::transfer(Origin::signed(BOB), ALICE, DOT, 10));
 		// all used up
-		assert_eq!(PalletBalances::free_balance(BOB), 0);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 0);
 
 		// no locks anymore
-		assert_eq!(PalletBalances::locks(&BOB), vec![]);
+		assert!(Tokens::locks(&BOB, DOT).is_empty());
 	});
 }
 
@@ -240,17 +298,23 @@ fn claim_works() {
 fn claim_for_works() {
 	ExtBuilder::build().execute_with(|| {
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 0u64,
-			period: 10u64,
-			period_count: 2u32,
-			per_period: 10u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 2u32,
+				per_period: 10u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule));
 
-		assert_ok!(Vesting::claim_for(Origin::signed(ALICE), BOB));
+		assert_ok!(Vesting::claim_for(Origin::signed(ALICE), BOB, DOT));
 
 		assert_eq!(
-			PalletBalances::locks(&BOB).get(0),
+			Tokens::locks(&BOB, DOT).get(0),
 			Some(&BalanceLock {
 				id: VESTING_LOCK_ID,
 				amount: 20u64,
@@ -261,10 +325,10 @@ fn claim_for_works() {
 
 		MockBlockNumberProvider::set(21);
 
-		assert_ok!(Vesting::claim_for(Origin::signed(ALICE), BOB));
+		assert_ok!(Vesting::claim_for(Origin::signed(ALICE), BOB, DOT));
 
 		// no locks anymore
-		assert_eq!(PalletBalances::locks(&BOB), vec![]);
+		assert!(Tokens::locks(&BOB, DOT).is_empty());
 		assert!(!VestingSchedules::<Runtime>::contains_key(&BOB));
 	});
 }
@@ -273,54 +337,67 @@ fn claim_for_works() {
 fn update_vesting_schedules_works() {
 	ExtBuilder::build().execute_with(|| {
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 0u64,
-			period: 10u64,
-			period_count: 2u32,
-			per_period: 10u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 2u32,
+				per_period: 10u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule));
 
 		let updated_schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 0u64,
-			period: 20u64,
-			period_count: 2u32,
-			per_period: 10u64,
+			curve: VestingCurve::Step {
+				period: 20u64,
+				period_count: 2u32,
+				per_period: 10u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_ok!(Vesting::update_vesting_schedules(
 			Origin::root(),
 			BOB,
+			DOT,
 			vec![updated_schedule]
 		));
 
 		MockBlockNumberProvider::set(11);
-		assert_ok!(Vesting::claim(Origin::signed(BOB)));
-		assert!(PalletBalances::transfer(Origin::signed(BOB), ALICE, 1).is_err());
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert!(Tokens::transfer(Origin::signed(BOB), ALICE, DOT, 1).is_err());
 
 		MockBlockNumberProvider::set(21);
-		assert_ok!(Vesting::claim(Origin::signed(BOB)));
-		assert_ok!(PalletBalances::transfer(Origin::signed(BOB), ALICE, 10));
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_ok!(Tokens::transfer(Origin::signed(BOB), ALICE, DOT, 10));
 
 		// empty vesting schedules cleanup the storage and unlock the fund
 		assert!(VestingSchedules::<Runtime>::contains_key(BOB));
 		assert_eq!(
-			PalletBalances::locks(&BOB).get(0),
+			Tokens::locks(&BOB, DOT).get(0),
 			Some(&BalanceLock {
 				id: VESTING_LOCK_ID,
 				amount: 10u64,
 				reasons: Reasons::All,
 			})
 		);
-		assert_ok!(Vesting::update_vesting_schedules(Origin::root(), BOB, vec![]));
+		assert_ok!(Vesting::update_vesting_schedules(Origin::root(), BOB, DOT, vec![]));
 		assert!(!VestingSchedules::<Runtime>::contains_key(BOB));
-		assert_eq!(PalletBalances::locks(&BOB), vec![]);
+		assert!(Tokens::locks(&BOB, DOT).is_empty());
 	});
 }
 
 #[test]
 fn update_vesting_schedules_fails_if_unexpected_existing_locks() {
 	ExtBuilder::build().execute_with(|| {
-		assert_ok!(PalletBalances::transfer(Origin::signed(ALICE), BOB, 1));
-		PalletBalances::set_lock(*b"prelocks", &BOB, 0u64, WithdrawReasons::all());
+		assert_ok!(Tokens::transfer(Origin::signed(ALICE), BOB, DOT, 1));
+		assert_ok!(Tokens::set_lock(*b"prelocks", DOT, &BOB, 0u64));
 	});
 }
 
@@ -328,10 +405,16 @@ fn update_vesting_schedules_fails_if_unexpected_existing_locks() {
 fn vested_transfer_check_for_min() {
 	ExtBuilder::build().execute_with(|| {
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 1u64,
-			period: 1u64,
-			period_count: 1u32,
-			per_period: 3u64,
+			curve: VestingCurve::Step {
+				period: 1u64,
+				period_count: 1u32,
+				per_period: 3u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(Origin::signed(BOB), ALICE, schedule),
@@ -344,18 +427,30 @@ fn vested_transfer_check_for_min() {
 fn multiple_vesting_schedule_claim_works() {
 	ExtBuilder::build().execute_with(|| {
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 0u64,
-			period: 10u64,
-			period_count: 2u32,
-			per_period: 10u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 2u32,
+				per_period: 10u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule.clone()));
 
 		let schedule2 = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 0u64,
-			period: 10u64,
-			period_count: 3u32,
-			per_period: 10u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 3u32,
+				per_period: 10u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule2.clone()));
 
@@ -363,17 +458,17 @@ fn multiple_vesting_schedule_claim_works() {
 
 		MockBlockNumberProvider::set(21);
 
-		assert_ok!(Vesting::claim(Origin::signed(BOB)));
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
 
 		assert_eq!(Vesting::vesting_schedules(&BOB), vec![schedule2]);
 
 		MockBlockNumberProvider::set(31);
 
-		assert_ok!(Vesting::claim(Origin::signed(BOB)));
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
 
 		assert!(!VestingSchedules::<Runtime>::contains_key(&BOB));
 
-		assert_eq!(PalletBalances::locks(&BOB), vec![]);
+		assert!(Tokens::locks(&BOB, DOT).is_empty());
 	});
 }
 
@@ -381,10 +476,16 @@ fn multiple_vesting_schedule_claim_works() {
 fn exceeding_maximum_schedules_should_fail() {
 	ExtBuilder::build().execute_with(|| {
 		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
 			start: 0u64,
-			period: 10u64,
-			period_count: 2u32,
-			per_period: 10u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 2u32,
+				per_period: 10u64,
+			},
+			revocable: None,
+			transferable: false,
 		};
 		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule.clone()));
 		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule.clone()));
@@ -396,8 +497,1003 @@ fn exceeding_maximum_schedules_should_fail() {
 		let schedules = vec![schedule.clone(), schedule.clone(), schedule];
 
 		assert_noop!(
-			Vesting::update_vesting_schedules(Origin::root(), BOB, schedules),
+			Vesting::update_vesting_schedules(Origin::root(), BOB, DOT, schedules),
 			Error::<Runtime>::MaxVestingSchedulesExceeded
 		);
 	});
 }
+
+#[test]
+fn multi_currency_vesting_works() {
+	ExtBuilder::build().execute_with(|| {
+		let dot_schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start: 0u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 2u32,
+				per_period: 10u64,
+			},
+			revocable: None,
+			transferable: false,
+		};
+		let btc_schedule = VestingSchedule {
+			currency_id: BTC,
+			timeline: Timeline::BlockNumber,
+			start: 0u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 1u32,
+				per_period: 30u64,
+			},
+			revocable: None,
+			transferable: false,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			dot_schedule.clone()
+		));
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			btc_schedule.clone()
+		));
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB),
+			vec![dot_schedule.clone(), btc_schedule]
+		);
+
+		// one lock per currency
+		assert_eq!(Tokens::locks(&BOB, DOT).len(), 1);
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 20);
+		assert_eq!(Tokens::locks(&BOB, BTC).len(), 1);
+		assert_eq!(Tokens::locks(&BOB, BTC)[0].amount, 30);
+
+		MockBlockNumberProvider::set(11);
+
+		// claiming a currency doesn't unlock the others
+		assert_ok!(Vesting::claim(Origin::signed(BOB), BTC));
+		System::assert_last_event(Event::Vesting(crate::Event::Claimed(BOB, BTC, 0)));
+		assert!(Tokens::locks(&BOB, BTC).is_empty());
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 20);
+		assert_eq!(Vesting::vesting_schedules(&BOB), vec![dot_schedule]);
+		assert_ok!(Tokens::transfer(Origin::signed(BOB), ALICE, BTC, 30));
+		assert!(Tokens::transfer(Origin::signed(BOB), ALICE, DOT, 10).is_err());
+
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		System::assert_last_event(Event::Vesting(crate::Event::Claimed(BOB, DOT, 10)));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 10);
+		assert_ok!(Tokens::transfer(Origin::signed(BOB), ALICE, DOT, 10));
+	});
+}
+
+#[test]
+fn update_vesting_schedules_keeps_other_currencies() {
+	ExtBuilder::build().execute_with(|| {
+		let dot_schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start: 0u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 2u32,
+				per_period: 10u64,
+			},
+			revocable: None,
+			transferable: false,
+		};
+		let btc_schedule = VestingSchedule {
+			currency_id: BTC,
+			timeline: Timeline::BlockNumber,
+			start: 0u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 1u32,
+				per_period: 30u64,
+			},
+			revocable: None,
+			transferable: false,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			dot_schedule.clone()
+		));
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			btc_schedule.clone()
+		));
+
+		assert_noop!(
+			Vesting::update_vesting_schedules(Origin::root(), BOB, DOT, vec![btc_schedule.clone()]),
+			Error::<Runtime>::CurrencyMismatch
+		);
+
+		let updated_schedule = VestingSchedule {
+			currency_id: BTC,
+			timeline: Timeline::BlockNumber,
+			start: 0u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 3u32,
+				per_period: 10u64,
+			},
+			revocable: None,
+			transferable: false,
+		};
+		assert_ok!(Vesting::update_vesting_schedules(
+			Origin::root(),
+			BOB,
+			BTC,
+			vec![updated_schedule.clone()]
+		));
+		System::assert_last_event(Event::Vesting(crate::Event::VestingSchedulesUpdated(BOB, BTC)));
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB),
+			vec![dot_schedule.clone(), updated_schedule]
+		);
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 20);
+		assert_eq!(Tokens::locks(&BOB, BTC)[0].amount, 30);
+
+		assert_ok!(Vesting::update_vesting_schedules(Origin::root(), BOB, BTC, vec![]));
+		assert_eq!(Vesting::vesting_schedules(&BOB), vec![dot_schedule]);
+		assert!(Tokens::locks(&BOB, BTC).is_empty());
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 20);
+	});
+}
+
+#[test]
+fn vested_transfer_fails_if_invalid_funder() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start: 0u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 2u32,
+				per_period: 20u64,
+			},
+			revocable: Some(Revocable::Funder(CHARLIE)),
+			transferable: false,
+		};
+		assert_noop!(
+			Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule),
+			Error::<Runtime>::InvalidFunder
+		);
+	});
+}
+
+#[test]
+fn revoke_vesting_by_funder_works() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start: 0u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 2u32,
+				per_period: 20u64,
+			},
+			revocable: Some(Revocable::Funder(ALICE)),
+			transferable: false,
+		};
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule.clone()));
+
+		MockBlockNumberProvider::set(10);
+		assert_noop!(Vesting::revoke_vesting(Origin::signed(BOB), BOB, 0), BadOrigin);
+		assert_noop!(Vesting::revoke_vesting(Origin::root(), BOB, 0), BadOrigin);
+
+		assert_ok!(Vesting::revoke_vesting(Origin::signed(ALICE), BOB, 0));
+		System::assert_last_event(Event::Vesting(crate::Event::VestingScheduleRevoked(
+			BOB, schedule, ALICE, 20,
+		)));
+
+		// the vested balance is kept, and unlocked
+		assert!(Vesting::vesting_schedules(&BOB).is_empty());
+		assert!(Tokens::locks(&BOB, DOT).is_empty());
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 20);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 80);
+	});
+}
+
+#[test]
+fn revoke_vesting_by_origin_works() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start: 0u64,
+			curve: VestingCurve::Linear {
+				duration: 40u64,
+				total: 40u64,
+			},
+			revocable: Some(Revocable::Origin),
+			transferable: false,
+		};
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule));
+
+		MockBlockNumberProvider::set(10);
+		assert_noop!(Vesting::revoke_vesting(Origin::signed(ALICE), BOB, 0), BadOrigin);
+
+		assert_ok!(Vesting::revoke_vesting(Origin::root(), BOB, 0));
+		assert!(Vesting::vesting_schedules(&BOB).is_empty());
+		assert!(Tokens::locks(&BOB, DOT).is_empty());
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 10);
+		assert_eq!(Tokens::free_balance(DOT, &TREASURY), 30);
+	});
+}
+
+#[test]
+fn revoke_vesting_keeps_other_vesting_schedules() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start: 0u64,
+			curve: VestingCurve::Step {
+				period: 10u64,
+				period_count: 2u32,
+				per_period: 10u64,
+			},
+			revocable: None,
+			transferable: false,
+		};
+		let revocable_schedule = VestingSchedule {
+			revocable: Some(Revocable::Funder(ALICE)),
+			..schedule.clone()
+		};
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule.clone()));
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, revocable_schedule));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 40);
+
+		assert_ok!(Vesting::revoke_vesting(Origin::signed(ALICE), BOB, 1));
+		assert_eq!(Vesting::vesting_schedules(&BOB), vec![schedule]);
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 20);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 20);
+	});
+}
+
+#[test]
+fn revoke_vesting_fails_if_not_revocable_or_not_found() {
+	ExtBuilder::build().execute_with(|| {
+		assert_noop!(
+			Vesting::revoke_vesting(Origin::root(), CHARLIE, 0),
+			Error::<Runtime>::NotRevocable
+		);
+		assert_noop!(
+			Vesting::revoke_vesting(Origin::root(), CHARLIE, 1),
+			Error::<Runtime>::VestingScheduleNotFound
+		);
+		assert_noop!(
+			Vesting::revoke_vesting(Origin::root(), BOB, 0),
+			Error::<Runtime>::VestingScheduleNotFound
+		);
+	});
+}
+
+// *************************************************
+// tests for merging and splitting
+// *************************************************
+
+fn step_schedule(start: u64, per_period: u64) -> VestingSchedule<AccountId, CurrencyId, u64, u64> {
+	VestingSchedule {
+		currency_id: DOT,
+		timeline: Timeline::BlockNumber,
+		start,
+		curve: VestingCurve::Step {
+			period: 10u64,
+			period_count: 2u32,
+			per_period,
+		},
+		revocable: None,
+		transferable: false,
+	}
+}
+
+#[test]
+fn merge_schedules_of_same_terms_works() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			step_schedule(0, 10)
+		));
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			step_schedule(0, 15)
+		));
+
+		assert_ok!(Vesting::merge_schedules(Origin::signed(BOB), 1, 0));
+		assert_eq!(Vesting::vesting_schedules(&BOB), vec![step_schedule(0, 25)]);
+		System::assert_last_event(Event::Vesting(crate::Event::VestingSchedulesMerged(
+			BOB,
+			step_schedule(0, 25),
+		)));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 50);
+	});
+}
+
+#[test]
+fn merge_stepped_schedules_into_milestones_works() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			step_schedule(5, 10)
+		));
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			step_schedule(0, 10)
+		));
+
+		assert_ok!(Vesting::merge_schedules(Origin::signed(BOB), 0, 1));
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB),
+			vec![VestingSchedule {
+				currency_id: DOT,
+				timeline: Timeline::BlockNumber,
+				start: 0u64,
+				curve: VestingCurve::Milestones(vec![(10u64, 10u64), (15, 10), (20, 10), (25, 10)].try_into().unwrap()),
+				revocable: None,
+				transferable: false,
+			}]
+		);
+
+		MockBlockNumberProvider::set(15);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 20);
+	});
+}
+
+#[test]
+fn merge_linear_schedules_locks_until_later_end() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = |duration| VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start: 0u64,
+			curve: VestingCurve::Linear { duration, total: 20u64 },
+			revocable: None,
+			transferable: false,
+		};
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule(20)));
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule(40)));
+
+		MockBlockNumberProvider::set(10);
+		assert_ok!(Vesting::merge_schedules(Origin::signed(BOB), 0, 1));
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB),
+			vec![VestingSchedule {
+				currency_id: DOT,
+				timeline: Timeline::BlockNumber,
+				start: 10u64,
+				curve: VestingCurve::Milestones(vec![(30u64, 25u64)].try_into().unwrap()),
+				revocable: None,
+				transferable: false,
+			}]
+		);
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 25);
+
+		MockBlockNumberProvider::set(39);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 25);
+
+		MockBlockNumberProvider::set(40);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert!(Tokens::locks(&BOB, DOT).is_empty());
+	});
+}
+
+#[test]
+fn merge_schedules_fails_if_not_mergeable() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			step_schedule(0, 10)
+		));
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			VestingSchedule {
+				currency_id: BTC,
+				..step_schedule(0, 10)
+			}
+		));
+
+		assert_noop!(
+			Vesting::merge_schedules(Origin::signed(BOB), 0, 0),
+			Error::<Runtime>::MergeSameVestingSchedule
+		);
+		assert_noop!(
+			Vesting::merge_schedules(Origin::signed(BOB), 0, 2),
+			Error::<Runtime>::VestingScheduleNotFound
+		);
+		assert_noop!(
+			Vesting::merge_schedules(Origin::signed(BOB), 0, 1),
+			Error::<Runtime>::CurrencyMismatch
+		);
+	});
+
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			step_schedule(0, 10)
+		));
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			VestingSchedule {
+				revocable: Some(Revocable::Funder(ALICE)),
+				..step_schedule(0, 10)
+			}
+		));
+
+		assert_noop!(
+			Vesting::merge_schedules(Origin::signed(BOB), 0, 1),
+			Error::<Runtime>::RevocableMismatch
+		);
+	});
+}
+
+#[test]
+fn split_schedule_works() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		let schedule = VestingSchedule {
+			transferable: true,
+			..step_schedule(0, 10)
+		};
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule));
+
+		MockBlockNumberProvider::set(10);
+		assert_ok!(Vesting::split_schedule(Origin::signed(BOB), 0, CHARLIE, 10));
+
+		let split = VestingSchedule {
+			transferable: true,
+			..step_schedule(0, 5)
+		};
+		System::assert_last_event(Event::Vesting(crate::Event::VestingScheduleSplit(
+			BOB,
+			CHARLIE,
+			split.clone(),
+		)));
+		assert_eq!(Vesting::vesting_schedules(&BOB), vec![split.clone()]);
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 5);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 15);
+
+		// the genesis vesting of `CHARLIE` locks 10
+		assert_eq!(Vesting::vesting_schedules(&CHARLIE)[1], split);
+		assert_eq!(Tokens::locks(&CHARLIE, DOT)[0].amount, 15);
+		assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 35);
+	});
+}
+
+#[test]
+fn split_schedule_fails_if_invalid() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			step_schedule(0, 10)
+		));
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			VestingSchedule {
+				transferable: true,
+				..step_schedule(0, 10)
+			}
+		));
+
+		assert_noop!(
+			Vesting::split_schedule(Origin::signed(BOB), 2, CHARLIE, 10),
+			Error::<Runtime>::VestingScheduleNotFound
+		);
+		assert_noop!(
+			Vesting::split_schedule(Origin::signed(BOB), 0, CHARLIE, 10),
+			Error::<Runtime>::NotTransferable
+		);
+		assert_noop!(
+			Vesting::split_schedule(Origin::signed(BOB), 1, CHARLIE, 4),
+			Error::<Runtime>::AmountLow
+		);
+		assert_noop!(
+			Vesting::split_schedule(Origin::signed(BOB), 1, CHARLIE, 7),
+			Error::<Runtime>::InvalidSplitAmount
+		);
+		assert_noop!(
+			Vesting::split_schedule(Origin::signed(BOB), 1, CHARLIE, 20),
+			Error::<Runtime>::InvalidSplitAmount
+		);
+	});
+}
+
+#[test]
+fn split_milestones_works() {
+	let curve = VestingCurve::<u64, u64>::Milestones(vec![(0, 5), (10, 10), (30, 15)].try_into().unwrap());
+	assert_eq!(
+		curve.split(10),
+		Some((
+			VestingCurve::Milestones(vec![(0, 4), (10, 6), (30, 10)].try_into().unwrap()),
+			VestingCurve::Milestones(vec![(0, 1), (10, 4), (30, 5)].try_into().unwrap()),
+		))
+	);
+	assert_eq!(curve.split(0), None);
+	assert_eq!(curve.split(30), None);
+}
+
+// *************************************************
+// tests for timestamp vesting
+// *************************************************
+
+fn timestamp_schedule(start: u64, per_period: u64) -> VestingSchedule<AccountId, CurrencyId, u64, u64> {
+	VestingSchedule {
+		currency_id: DOT,
+		timeline: Timeline::Timestamp,
+		start,
+		curve: VestingCurve::Step {
+			period: 1_000u64,
+			period_count: 2u32,
+			per_period,
+		},
+		revocable: None,
+		transferable: false,
+	}
+}
+
+#[test]
+fn timestamp_vesting_works() {
+	ExtBuilder::build().execute_with(|| {
+		MockTimestamp::set(1_000);
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			timestamp_schedule(1_000, 10)
+		));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 20);
+
+		// the block number doesn't unlock timestamp vesting
+		MockBlockNumberProvider::set(1_000_000);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 20);
+
+		MockTimestamp::set(2_999);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 10);
+
+		MockTimestamp::set(3_000);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert!(Tokens::locks(&BOB, DOT).is_empty());
+		assert!(Vesting::vesting_schedules(&BOB).is_empty());
+	});
+}
+
+#[test]
+fn block_and_timestamp_vesting_works_together() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			step_schedule(0, 10)
+		));
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			timestamp_schedule(0, 15)
+		));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 50);
+
+		MockBlockNumberProvider::set(20);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 30);
+		assert_eq!(Vesting::vesting_schedules(&BOB), vec![timestamp_schedule(0, 15)]);
+
+		MockTimestamp::set(1_000);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 15);
+	});
+}
+
+#[test]
+fn merge_timestamp_schedules_works() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			timestamp_schedule(0, 10)
+		));
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			step_schedule(0, 10)
+		));
+		assert_noop!(
+			Vesting::merge_schedules(Origin::signed(BOB), 0, 1),
+			Error::<Runtime>::TimelineMismatch
+		);
+
+		// merging locks until the later end, by timestamp
+		assert_ok!(Vesting::update_vesting_schedules(
+			Origin::root(),
+			BOB,
+			DOT,
+			vec![
+				timestamp_schedule(0, 10),
+				VestingSchedule {
+					curve: VestingCurve::Linear {
+						duration: 4_000u64,
+						total: 20u64,
+					},
+					..timestamp_schedule(0, 10)
+				},
+			]
+		));
+		MockTimestamp::set(1_000);
+		assert_ok!(Vesting::merge_schedules(Origin::signed(BOB), 0, 1));
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB),
+			vec![VestingSchedule {
+				start: 1_000u64,
+				curve: VestingCurve::Milestones(vec![(3_000u64, 25u64)].try_into().unwrap()),
+				..timestamp_schedule(0, 10)
+			}]
+		);
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 25);
+	});
+}
+
+// *************************************************
+// tests for migrations
+// *************************************************
+
+#[test]
+fn migrate_from_v0_should_work() {
+	ExtBuilder::build().execute_with(|| {
+		StorageVersion::new(0).put::<Vesting>();
+		frame_support::storage::unhashed::put(
+			&VestingSchedules::<Runtime>::hashed_key_for(&CHARLIE),
+			&vec![migrations::v0::VestingSchedule {
+				start: 2u64,
+				period: 3u64,
+				period_count: 4u32,
+				per_period: 5u64,
+			}],
+		);
+
+		assert_ok!(migrations::pre_migrate::<Runtime>());
+		migrations::migrate::<Runtime>();
+		assert_ok!(migrations::post_migrate::<Runtime>());
+
+		assert_eq!(StorageVersion::get::<Vesting>(), 1);
+		assert_eq!(
+			Vesting::vesting_schedules(&CHARLIE),
+			vec![VestingSchedule {
+				currency_id: DOT,
+				timeline: Timeline::BlockNumber,
+				start: 2u64,
+				curve: VestingCurve::Step {
+					period: 3u64,
+					period_count: 4u32,
+					per_period: 5u64,
+				},
+				revocable: None,
+				transferable: false,
+			}]
+		);
+
+		// the existing lock is kept
+		assert_eq!(Tokens::locks(&CHARLIE, DOT)[0].amount, 20);
+		MockBlockNumberProvider::set(14);
+		assert_ok!(Vesting::claim(Origin::signed(CHARLIE), DOT));
+		assert!(Tokens::locks(&CHARLIE, DOT).is_empty());
+
+		// migrating again is a no-op
+		migrations::migrate::<Runtime>();
+		assert_ok!(migrations::post_migrate::<Runtime>());
+	});
+}
+
+// *************************************************
+// tests for vesting curves
+// *************************************************
+
+#[test]
+fn linear_vesting_works() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start: 10u64,
+			curve: VestingCurve::Linear {
+				duration: 40u64,
+				total: 20u64,
+			},
+			revocable: None,
+			transferable: false,
+		};
+		assert_eq!(schedule.end(), Some(50));
+		assert_eq!(schedule.total_amount(), Some(20));
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 20);
+
+		// 21 / 40 * 20 = 10.5 unlocked, rounded down
+		MockBlockNumberProvider::set(31);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 10);
+
+		MockBlockNumberProvider::set(50);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert!(Tokens::locks(&BOB, DOT).is_empty());
+		assert!(!VestingSchedules::<Runtime>::contains_key(&BOB));
+	});
+}
+
+#[test]
+fn cliff_linear_vesting_works() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start: 0u64,
+			curve: VestingCurve::CliffLinear {
+				cliff: 20u64,
+				duration: 40u64,
+				total: 40u64,
+			},
+			revocable: None,
+			transferable: false,
+		};
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule));
+
+		// nothing unlocked before the cliff
+		MockBlockNumberProvider::set(19);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 40);
+
+		// the amount vested so far is unlocked at the cliff
+		MockBlockNumberProvider::set(20);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 20);
+
+		MockBlockNumberProvider::set(30);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 10);
+
+		MockBlockNumberProvider::set(40);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert!(Tokens::locks(&BOB, DOT).is_empty());
+	});
+}
+
+#[test]
+fn milestones_vesting_works() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start: 10u64,
+			curve: VestingCurve::Milestones(vec![(0u64, 5u64), (10, 10), (30, 15)].try_into().unwrap()),
+			revocable: None,
+			transferable: false,
+		};
+		assert_eq!(schedule.end(), Some(40));
+		assert_eq!(schedule.total_amount(), Some(30));
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 30);
+
+		MockBlockNumberProvider::set(10);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 25);
+
+		MockBlockNumberProvider::set(39);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert_eq!(Tokens::locks(&BOB, DOT)[0].amount, 15);
+
+		MockBlockNumberProvider::set(40);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), DOT));
+		assert!(Tokens::locks(&BOB, DOT).is_empty());
+	});
+}
+
+#[test]
+fn vested_transfer_fails_if_invalid_curve() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = |curve| VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start: 0u64,
+			curve,
+			revocable: None,
+			transferable: false,
+		};
+		assert_noop!(
+			Vesting::vested_transfer(
+				Origin::signed(ALICE),
+				BOB,
+				schedule(VestingCurve::Linear {
+					duration: 0u64,
+					total: 10u64,
+				})
+			),
+			Error::<Runtime>::ZeroVestingPeriod
+		);
+		assert_noop!(
+			Vesting::vested_transfer(
+				Origin::signed(ALICE),
+				BOB,
+				schedule(VestingCurve::CliffLinear {
+					cliff: 11u64,
+					duration: 10u64,
+					total: 10u64,
+				})
+			),
+			Error::<Runtime>::InvalidCliff
+		);
+		assert_noop!(
+			Vesting::vested_transfer(
+				Origin::signed(ALICE),
+				BOB,
+				schedule(VestingCurve::Milestones(Default::default()))
+			),
+			Error::<Runtime>::InvalidMilestones
+		);
+		assert_noop!(
+			Vesting::vested_transfer(
+				Origin::signed(ALICE),
+				BOB,
+				schedule(VestingCurve::Milestones(
+					vec![(10u64, 5u64), (10, 5)].try_into().unwrap()
+				))
+			),
+			Error::<Runtime>::InvalidMilestones
+		);
+		assert_noop!(
+			Vesting::vested_transfer(
+				Origin::signed(ALICE),
+				BOB,
+				schedule(VestingCurve::Milestones(
+					vec![(1u64, u64::MAX), (2, 1)].try_into().unwrap()
+				))
+			),
+			ArithmeticError::Overflow
+		);
+	});
+}
+
+fn vesting_curve() -> impl Strategy<Value = VestingCurve<u64, u64>> {
+	prop_oneof![
+		(1..1_000u64, 1..100u32, 0..1_000_000u64).prop_map(|(period, period_count, per_period)| {
+			VestingCurve::Step {
+				period,
+				period_count,
+				per_period,
+			}
+		}),
+		(1..100_000u64, 0..u64::MAX).prop_map(|(duration, total)| VestingCurve::Linear { duration, total }),
+		(1..100_000u64, 0..u64::MAX)
+			.prop_flat_map(|(duration, total)| (0..=duration, Just(duration), Just(total)))
+			.prop_map(|(cliff, duration, total)| VestingCurve::CliffLinear { cliff, duration, total }),
+		prop::collection::vec((1..1_000u64, 0..1_000_000u64), 1..MaxMilestones::get() as usize).prop_map(
+			|milestones| {
+				let mut offset = 0;
+				VestingCurve::Milestones(
+					milestones
+						.into_iter()
+						.map(|(step, amount)| {
+							offset += step;
+							(offset, amount)
+						})
+						.collect::<Vec<_>>()
+						.try_into()
+						.unwrap(),
+				)
+			}
+		),
+	]
+}
+
+proptest! {
+	#[test]
+	fn locked_amount_is_non_increasing(
+		curve in vesting_curve(),
+		start in 0..1_000_000u64,
+		time in 0..2_000_000u64,
+		later in 0..1_000_000u64,
+	) {
+		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start,
+			curve,
+			revocable: None,
+			transferable: false,
+		};
+		prop_assert!(schedule.locked_amount(time) >= schedule.locked_amount(time + later));
+	}
+
+	#[test]
+	fn locked_amount_is_bounded_by_total_amount(
+		curve in vesting_curve(),
+		start in 0..1_000_000u64,
+		time in 0..2_000_000u64,
+	) {
+		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start,
+			curve,
+			revocable: None,
+			transferable: false,
+		};
+		let total = schedule.total_amount().unwrap();
+		let end = schedule.end().unwrap();
+
+		prop_assert!(schedule.locked_amount(time) <= total);
+		// nothing is unlocked before the start, and all is unlocked at the end
+		prop_assert_eq!(schedule.locked_amount(start.saturating_sub(1)), total);
+		prop_assert_eq!(schedule.locked_amount(end), 0);
+	}
+
+	#[test]
+	fn split_locked_amounts_add_up_to_no_less_than_locked_amount(
+		curve in vesting_curve(),
+		elapsed in prop::option::of(0..2_000_000u64),
+		numerator in 0..1_000u64,
+	) {
+		let total = curve.total_amount().unwrap();
+		let amount = (total as u128 * numerator as u128 / 1_000) as u64;
+		if let Some((rest, split)) = curve.split(amount) {
+			prop_assert_eq!(rest.total_amount().unwrap() + split.total_amount().unwrap(), total);
+			prop_assert!(
+				rest.locked_amount(elapsed) as u128 + split.locked_amount(elapsed) as u128
+					>= curve.locked_amount(elapsed) as u128
+			);
+		}
+	}
+
+	#[test]
+	fn merged_locked_amount_is_no_less_than_locked_amounts(
+		curve in vesting_curve(),
+		other_curve in vesting_curve(),
+		start in 0..1_000u64,
+		other_start in 0..1_000u64,
+		time in 0..2_000_000u64,
+	) {
+		let schedule = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start,
+			curve,
+			revocable: None,
+			transferable: false,
+		};
+		let other = VestingSchedule {
+			currency_id: DOT,
+			timeline: Timeline::BlockNumber,
+			start: other_start,
+			curve: other_curve,
+			revocable: None,
+			transferable: false,
+		};
+		if let Some(merged) = schedule.merge(&other) {
+			prop_assert!(
+				merged.locked_amount(time) as u128
+					>= schedule.locked_amount(time) as u128 + other.locked_amount(time) as u128
+			);
+		}
+	}
+}
@@ -33,6 +33,9 @@ pub trait WeightInfo {
 	fn vested_transfer() -> Weight;
 	fn claim(i: u32, ) -> Weight;
 	fn update_vesting_schedules(i: u32, ) -> Weight;
+	fn revoke_vesting(i: u32, ) -> Weight;
+	fn merge_schedules(i: u32, ) -> Weight;
+	fn split_schedule(i: u32, ) -> Weight;
 }
 
 /// Default weights.
@@ -56,4 +59,28 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `revoke_vesting` of `benchmarking.rs` is run.
+	fn revoke_vesting(i: u32, ) -> Weight {
+		(74_372_000 as Weight)
+			.saturating_add((121_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `merge_schedules` of `benchmarking.rs` is run.
+	fn merge_schedules(i: u32, ) -> Weight {
+		(41_286_000 as Weight)
+			.saturating_add((158_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// NOTE: not benchmarked yet, estimated from the storage accesses until
+	// `split_schedule` of `benchmarking.rs` is run.
+	fn split_schedule(i: u32, ) -> Weight {
+		(86_913_000 as Weight)
+			.saturating_add((176_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
 }
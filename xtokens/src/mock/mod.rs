@@ -135,6 +135,7 @@ pub fn para_ext(para_id: u32) -> TestExternalities {
 
 	orml_tokens::GenesisConfig::<Runtime> {
 		balances: vec![(ALICE, CurrencyId::R, 1_000)],
+		metadata: vec![],
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
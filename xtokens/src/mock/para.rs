@@ -64,6 +64,12 @@ parameter_types! {
 	pub ExistentialDeposit: Balance = 1;
 	pub const MaxLocks: u32 = 50;
 	pub const MaxReserves: u32 = 50;
+	pub const MaxTransfers: u32 = 100;
+	pub const StringLimit: u32 = 50;
+	pub DustAccount: AccountId = AccountId::new([0u8; 32]);
+	pub const ApprovalDeposit: Balance = 1;
+	pub const AllowFrozenReceiving: bool = true;
+	pub const MaintainHoldersIndex: bool = false;
 }
 
 impl pallet_balances::Config for Runtime {
@@ -84,6 +90,18 @@ parameter_type_with_key! {
 	};
 }
 
+parameter_type_with_key! {
+	pub MaxIssuance: |_currency_id: CurrencyId| -> Option<Balance> {
+		None
+	};
+}
+
+parameter_type_with_key! {
+	pub IssuanceHighWaterMark: |_currency_id: CurrencyId| -> Option<Balance> {
+		None
+	};
+}
+
 impl orml_tokens::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
@@ -92,8 +110,29 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = ();
+	type OnTransfer = ();
+	type OnDeposit = ();
+	type OnWithdraw = ();
+	type OnSlash = ();
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+	type SufficientCurrencies = Nothing;
+	type ProviderCurrencies = Everything;
 	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type MaxTransfers = MaxTransfers;
+	type DepositCurrency = Balances;
+	type ApprovalDeposit = ApprovalDeposit;
+	type ReserveIdentifier = [u8; 8];
 	type DustRemovalWhitelist = Everything;
+	type DustReceiver = DustAccount;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MetadataOrigin = frame_system::EnsureRoot<AccountId>;
+	type StringLimit = StringLimit;
+	type AllowFrozenReceiving = AllowFrozenReceiving;
+	type MaintainHoldersIndex = MaintainHoldersIndex;
+	type MaxIssuance = MaxIssuance;
+	type IssuanceHighWaterMark = IssuanceHighWaterMark;
 }
 
 parameter_types! {